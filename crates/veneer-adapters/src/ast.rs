@@ -0,0 +1,289 @@
+//! Shared helpers for walking component source with the oxc AST.
+//!
+//! Adapters parse component files into an oxc [`Program`] and then read
+//! static values (class strings, lookup records, prop declarations) out of it.
+//! Anything that cannot be evaluated statically is reported with its span.
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{
+    Argument, ArrayExpressionElement, BinaryOperator, Declaration, Expression, ObjectPropertyKind,
+    Program, PropertyKey, Statement,
+};
+use oxc_parser::Parser;
+use oxc_span::{GetSpan, SourceType, Span};
+
+use crate::traits::{SourceSpan, TransformError};
+
+/// Parse component source into an AST.
+///
+/// Recoverable syntax errors are reported as [`TransformError::Syntax`] with the
+/// span of the first error, since a partially understood file would produce a
+/// misleading preview.
+pub(crate) fn parse_program<'a>(
+    allocator: &'a Allocator,
+    source: &'a str,
+    source_type: SourceType,
+) -> Result<Program<'a>, TransformError> {
+    let ret = Parser::new(allocator, source, source_type).parse();
+
+    if let Some(error) = ret.errors.first() {
+        let label = error.labels.as_ref().and_then(|labels| labels.first());
+        return Err(syntax_error(
+            error.message.to_string(),
+            label.map(|l| (l.offset(), l.len())),
+        ));
+    }
+
+    Ok(ret.program)
+}
+
+/// Parse a standalone expression, such as a Vue `:class` binding.
+///
+/// Pass the expression at its offset in the component file (see
+/// `markup::mask_except`) so reported spans point into that file.
+pub(crate) fn parse_expression<'a>(
+    allocator: &'a Allocator,
+    source: &'a str,
+    source_type: SourceType,
+) -> Result<Expression<'a>, TransformError> {
+    Parser::new(allocator, source, source_type)
+        .parse_expression()
+        .map_err(|errors| {
+            let Some(error) = errors.first() else {
+                return TransformError::ParseError("invalid expression".to_string());
+            };
+            let label = error.labels.as_ref().and_then(|labels| labels.first());
+            syntax_error(
+                error.message.to_string(),
+                label.map(|l| (l.offset(), l.len())),
+            )
+        })
+}
+
+/// Build the error for a parse failure with an optional `(offset, len)` label.
+fn syntax_error(message: String, label: Option<(usize, usize)>) -> TransformError {
+    match label {
+        Some((offset, len)) => TransformError::Syntax {
+            message,
+            span: SourceSpan::new(offset as u32, (offset + len) as u32),
+        },
+        None => TransformError::ParseError(message),
+    }
+}
+
+/// Iterate over the top-level declarations of a module, looking through
+/// `export` wrappers.
+pub(crate) fn top_level_declarations<'b, 'a>(
+    program: &'b Program<'a>,
+) -> impl Iterator<Item = &'b Declaration<'a>> {
+    program.body.iter().filter_map(|stmt| match stmt {
+        Statement::ExportNamedDeclaration(export) => export.declaration.as_ref(),
+        stmt => stmt.as_declaration(),
+    })
+}
+
+/// Find the initializer of a top-level `const`/`let` binding by name.
+pub(crate) fn find_binding<'b, 'a>(
+    program: &'b Program<'a>,
+    name: &str,
+) -> Option<&'b Expression<'a>> {
+    top_level_declarations(program).find_map(|decl| match decl {
+        Declaration::VariableDeclaration(var) => var
+            .declarations
+            .iter()
+            .find(|d| d.id.get_identifier_name().is_some_and(|n| n == name))
+            .and_then(|d| d.init.as_ref()),
+        _ => None,
+    })
+}
+
+/// Evaluate an expression to a static class string.
+///
+/// Supports string literals, template literals without substitutions and
+/// `+` concatenation of those. Parentheses and TypeScript wrappers such as
+/// `as const` and `satisfies` are looked through. Whitespace is normalized
+/// so multi-line values collapse into a single space-separated list.
+pub(crate) fn static_string(expr: &Expression) -> Result<String, TransformError> {
+    let mut out = String::new();
+    collect_string(expr, &mut out)?;
+    Ok(normalize_classes(&out))
+}
+
+fn collect_string(expr: &Expression, out: &mut String) -> Result<(), TransformError> {
+    match expr.get_inner_expression() {
+        Expression::StringLiteral(lit) => {
+            out.push_str(&lit.value);
+            Ok(())
+        }
+        Expression::TemplateLiteral(tpl) if tpl.expressions.is_empty() => {
+            for quasi in &tpl.quasis {
+                out.push_str(quasi.value.cooked.as_ref().unwrap_or(&quasi.value.raw));
+            }
+            Ok(())
+        }
+        Expression::BinaryExpression(bin) if bin.operator == BinaryOperator::Addition => {
+            collect_string(&bin.left, out)?;
+            collect_string(&bin.right, out)
+        }
+        other => Err(unsupported(
+            other.span(),
+            "expected a static string (string literal, plain template literal or `+` concatenation)",
+        )),
+    }
+}
+
+/// Evaluate a class value: a static string or an array of static strings,
+/// as accepted by `cva` and `tailwind-variants`.
+pub(crate) fn class_list(expr: &Expression) -> Result<String, TransformError> {
+    match expr.get_inner_expression() {
+        Expression::ArrayExpression(arr) => {
+            let mut parts = Vec::with_capacity(arr.elements.len());
+            for element in &arr.elements {
+                match element {
+                    ArrayExpressionElement::SpreadElement(spread) => {
+                        return Err(unsupported(
+                            spread.span,
+                            "spread elements are not supported",
+                        ));
+                    }
+                    ArrayExpressionElement::Elision(_) => {}
+                    element => parts.push(class_list(element.to_expression())?),
+                }
+            }
+            Ok(normalize_classes(&parts.join(" ")))
+        }
+        _ => static_string(expr),
+    }
+}
+
+/// Evaluate a scalar literal (string, boolean or number) to its string form.
+///
+/// Used for `defaultVariants` and compound variant conditions, where
+/// `disabled: true` selects the `true` key of a boolean variant.
+pub(crate) fn static_scalar(expr: &Expression) -> Result<String, TransformError> {
+    match expr.get_inner_expression() {
+        Expression::BooleanLiteral(lit) => Ok(lit.value.to_string()),
+        Expression::NumericLiteral(lit) => Ok(lit.value.to_string()),
+        _ => static_string(expr),
+    }
+}
+
+/// Evaluate an object literal into ordered `(key, classes)` entries.
+pub(crate) fn static_record(expr: &Expression) -> Result<Vec<(String, String)>, TransformError> {
+    object_properties(expr)?
+        .into_iter()
+        .map(|(key, value)| Ok((key, class_list(value)?)))
+        .collect()
+}
+
+/// Get the properties of an object literal as ordered `(key, value)` pairs.
+///
+/// Spread entries and computed keys are reported as unsupported.
+pub(crate) fn object_properties<'b, 'a>(
+    expr: &'b Expression<'a>,
+) -> Result<Vec<(String, &'b Expression<'a>)>, TransformError> {
+    let Expression::ObjectExpression(obj) = expr.get_inner_expression() else {
+        return Err(unsupported(expr.span(), "expected an object literal"));
+    };
+
+    let mut entries = Vec::with_capacity(obj.properties.len());
+
+    for prop in &obj.properties {
+        let ObjectPropertyKind::ObjectProperty(prop) = prop else {
+            return Err(unsupported(prop.span(), "spread entries are not supported"));
+        };
+
+        let key = property_key_name(&prop.key)
+            .ok_or_else(|| unsupported(prop.key.span(), "computed keys are not supported"))?;
+
+        entries.push((key, &prop.value));
+    }
+
+    Ok(entries)
+}
+
+/// Get a call argument as an expression, rejecting spread arguments.
+pub(crate) fn argument_expression<'b, 'a>(
+    arg: &'b Argument<'a>,
+) -> Result<&'b Expression<'a>, TransformError> {
+    arg.as_expression()
+        .ok_or_else(|| unsupported(arg.span(), "spread arguments are not supported"))
+}
+
+/// Get the static name of a property key, e.g. `primary` or `'outline-primary'`.
+pub(crate) fn property_key_name(key: &PropertyKey) -> Option<String> {
+    match key {
+        PropertyKey::StaticIdentifier(_)
+        | PropertyKey::StringLiteral(_)
+        | PropertyKey::NumericLiteral(_)
+        | PropertyKey::TemplateLiteral(_) => key.static_name().map(|n| n.into_owned()),
+        _ => None,
+    }
+}
+
+/// Build an [`TransformError::Unsupported`] for a node.
+pub(crate) fn unsupported(span: Span, message: &str) -> TransformError {
+    TransformError::Unsupported {
+        message: message.to_string(),
+        span: SourceSpan::new(span.start, span.end),
+    }
+}
+
+/// Collapse runs of whitespace in a class list.
+pub(crate) fn normalize_classes(classes: &str) -> String {
+    classes.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(source: &str) -> Result<String, TransformError> {
+        let allocator = Allocator::default();
+        let source = format!("const x = {source};");
+        let program = parse_program(&allocator, &source, SourceType::tsx())?;
+        static_string(find_binding(&program, "x").unwrap())
+    }
+
+    #[test]
+    fn evaluates_static_strings() {
+        assert_eq!(eval("'a b'").unwrap(), "a b");
+        assert_eq!(eval("`a\n  b`").unwrap(), "a b");
+        assert_eq!(eval("('a' + \"b\") as const").unwrap(), "ab");
+        assert_eq!(
+            eval("'px-2 bg-' + 'red-500 ' + `rounded`").unwrap(),
+            "px-2 bg-red-500 rounded"
+        );
+    }
+
+    #[test]
+    fn evaluates_class_arrays() {
+        let allocator = Allocator::default();
+        let source = "const x = ['a', ['b  c'], 'd'];";
+        let program = parse_program(&allocator, source, SourceType::tsx()).unwrap();
+
+        assert_eq!(
+            class_list(find_binding(&program, "x").unwrap()).unwrap(),
+            "a b c d"
+        );
+    }
+
+    #[test]
+    fn reports_span_of_dynamic_values() {
+        let err = eval("'a' + size").unwrap_err();
+
+        // `const x = ` is 10 bytes; `size` starts after `'a' + `
+        assert!(matches!(
+            err,
+            TransformError::Unsupported { span, .. } if span == SourceSpan::new(16, 20)
+        ));
+    }
+
+    #[test]
+    fn reports_syntax_errors_with_span() {
+        let allocator = Allocator::default();
+        let err = parse_program(&allocator, "const x = {", SourceType::tsx()).unwrap_err();
+
+        assert!(matches!(err, TransformError::Syntax { .. }));
+    }
+}
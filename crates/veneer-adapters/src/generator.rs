@@ -1,25 +1,26 @@
 //! Web Component code generator.
 
-use crate::react::ComponentStructure;
+use crate::react::{ComponentStructure, CompoundVariant};
 
 /// Generate a Web Component class from the extracted component structure.
 /// Uses adoptedStyleSheets to inherit page-level Tailwind CSS.
 pub fn generate_web_component(tag_name: &str, structure: &ComponentStructure) -> String {
+    if !structure.slots.is_empty() {
+        return generate_slotted_component(tag_name, structure);
+    }
+
     let class_name = to_pascal_case(tag_name);
 
-    let variant_entries: String = structure
-        .variant_lookup
-        .iter()
-        .map(|(k, v)| format!("  {}: '{}',", k, escape_string(v)))
-        .collect::<Vec<_>>()
-        .join("\n");
+    let variant_entries = dimension_entries(
+        structure
+            .dimensions
+            .iter()
+            .map(|d| (d.name.as_str(), d.lookup.as_slice())),
+        "  ",
+    );
+    let default_entries = default_entries(structure);
 
-    let size_entries: String = structure
-        .size_lookup
-        .iter()
-        .map(|(k, v)| format!("  {}: '{}',", k, escape_string(v)))
-        .collect::<Vec<_>>()
-        .join("\n");
+    let compound_entries = compound_entries(&structure.compound_variants, "  ");
 
     let attrs_array: String = structure
         .observed_attributes
@@ -28,8 +29,6 @@ pub fn generate_web_component(tag_name: &str, structure: &ComponentStructure) ->
         .collect::<Vec<_>>()
         .join(", ");
 
-    let default_variant = &structure.default_variant;
-    let default_size = &structure.default_size;
     let base_classes = escape_string(&structure.base_classes);
     let disabled_classes = escape_string(&structure.disabled_classes);
 
@@ -40,24 +39,33 @@ pub fn generate_web_component(tag_name: &str, structure: &ComponentStructure) ->
  * Tag: <{tag_name}>
  */
 
+// Classes per variant dimension and value
 const variantClasses = {{
 {variant_entries}
 }};
 
-const sizeClasses = {{
-{size_entries}
+// Value of each variant dimension when its attribute is absent
+const defaultVariants = {{
+{default_entries}
 }};
 
+// Rules that apply when every listed attribute matches one of its values
+const compoundVariants = [
+{compound_entries}
+];
+
 const baseClasses = '{base_classes}';
 const disabledClasses = '{disabled_classes}';
 
+{root_constants}
+
 // Cache for adopted stylesheets (all page stylesheets)
 let cachedSheets = null;
 
 export class {class_name} extends HTMLElement {{
   static observedAttributes = [{attrs_array}];
 
-  #button = null;
+  #root = null;
 
   constructor() {{
     super();
@@ -73,83 +81,68 @@ export class {class_name} extends HTMLElement {{
     this.#render();
   }}
 
-  #adoptStyles() {{
-    if (!this.shadowRoot) return;
+{adopt_styles}
 
-    // Use cached sheets if available
-    if (cachedSheets) {{
-      this.shadowRoot.adoptedStyleSheets = cachedSheets;
-      return;
-    }}
-
-    // Find and adopt page stylesheets
-    const sheets = [];
-    for (const sheet of document.styleSheets) {{
-      try {{
-        // Clone the stylesheet for adoption
-        const clone = new CSSStyleSheet();
-        const rules = Array.from(sheet.cssRules).map(r => r.cssText).join('\\n');
-        clone.replaceSync(rules);
-        sheets.push(clone);
-      }} catch (e) {{
-        // Cross-origin stylesheets can't be accessed, skip them
-      }}
-    }}
+{selected}
 
-    if (sheets.length > 0) {{
-      cachedSheets = sheets; // Cache all sheets
-      this.shadowRoot.adoptedStyleSheets = sheets;
-    }}
-  }}
+{create_root}
 
   #render() {{
     if (!this.shadowRoot) return;
 
-    const variant = this.getAttribute('variant') || '{default_variant}';
-    const size = this.getAttribute('size') || '{default_size}';
     const disabled = this.hasAttribute('disabled');
     const loading = this.hasAttribute('loading');
 
     const isDisabled = disabled || loading;
 
+    const selected = {{ ...this.#selected(), disabled: String(disabled), loading: String(loading) }};
+    const compoundClasses = compoundVariants
+      .filter(rule => Object.entries(rule.when).every(([attr, values]) => values.includes(selected[attr])))
+      .map(rule => rule.classes);
+
     const classes = [
       baseClasses,
-      variantClasses[variant] ?? variantClasses['{default_variant}'],
-      sizeClasses[size] ?? sizeClasses['{default_size}'],
+      ...Object.entries(variantClasses).map(
+        ([attr, lookup]) => lookup[selected[attr]] ?? lookup[defaultVariants[attr]] ?? '',
+      ),
+      ...compoundClasses,
       isDisabled ? disabledClasses : '',
     ]
       .filter(Boolean)
       .join(' ');
 
-    // Clear existing button if any
-    if (this.#button) {{
-      this.#button.remove();
+    // Clear existing element if any
+    if (this.#root) {{
+      this.#root.remove();
     }}
 
-    this.#button = document.createElement('button');
-    this.#button.type = 'button';
-    this.#button.className = classes;
-    this.#button.disabled = isDisabled;
+    this.#root = this.#createRoot();
+    this.#root.className = classes;
 
     if (isDisabled) {{
-      this.#button.setAttribute('aria-disabled', 'true');
+      if ('disabled' in this.#root) {{
+        this.#root.disabled = true;
+      }}
+      this.#root.setAttribute('aria-disabled', 'true');
     }}
     if (loading) {{
-      this.#button.setAttribute('aria-busy', 'true');
+      this.#root.setAttribute('aria-busy', 'true');
     }}
 
-    if (loading) {{
-      const span = document.createElement('span');
-      span.setAttribute('aria-hidden', 'true');
-      span.textContent = 'Loading...';
-      this.#button.appendChild(span);
-    }} else {{
-      // Use slot for content
-      const slot = document.createElement('slot');
-      this.#button.appendChild(slot);
+    if (!rootIsVoid) {{
+      if (loading) {{
+        const span = document.createElement('span');
+        span.setAttribute('aria-hidden', 'true');
+        span.textContent = 'Loading...';
+        this.#root.appendChild(span);
+      }} else {{
+        // Use slot for content
+        const slot = document.createElement('slot');
+        this.#root.appendChild(slot);
+      }}
     }}
 
-    this.shadowRoot.appendChild(this.#button);
+    this.shadowRoot.appendChild(this.#root);
   }}
 }}
 
@@ -164,15 +157,323 @@ export default {class_name};
         name = structure.name,
         tag_name = tag_name,
         variant_entries = variant_entries,
-        size_entries = size_entries,
+        default_entries = default_entries,
+        compound_entries = compound_entries,
         base_classes = base_classes,
         disabled_classes = disabled_classes,
         attrs_array = attrs_array,
-        default_variant = default_variant,
-        default_size = default_size,
+        root_constants = root_constants(structure),
+        adopt_styles = ADOPT_STYLES,
+        selected = SELECTED,
+        create_root = CREATE_ROOT,
     )
 }
 
+/// Generate a Web Component that renders a multi-slot component as a small
+/// element tree: the root slot wraps one `<div part="...">` per other slot,
+/// each projecting a named `<slot>`. The `body`/`content` slot (or the root,
+/// if there is none) receives the default slot.
+fn generate_slotted_component(tag_name: &str, structure: &ComponentStructure) -> String {
+    let class_name = to_pascal_case(tag_name);
+
+    let slot_entries: String = structure
+        .slots
+        .iter()
+        .map(|slot| {
+            let variants = dimension_entries(
+                slot.variants
+                    .iter()
+                    .map(|(dimension, lookup)| (dimension.as_str(), lookup.as_slice())),
+                "      ",
+            );
+            format!(
+                "  {{\n    name: '{}',\n    classes: '{}',\n    variants: {{\n{}\n    }},\n    compoundVariants: [\n{}\n    ],\n  }},",
+                escape_string(&slot.name),
+                escape_string(&slot.base_classes),
+                variants,
+                compound_entries(&slot.compound_variants, "      ")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let default_entries = default_entries(structure);
+
+    let attrs_array: String = structure
+        .observed_attributes
+        .iter()
+        .map(|a| format!("'{}'", a))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let default_slot = structure
+        .slots
+        .iter()
+        .skip(1)
+        .find(|slot| slot.name == "body" || slot.name == "content")
+        .map(|slot| escape_string(&slot.name))
+        .unwrap_or_default();
+
+    format!(
+        r#"/**
+ * {class_name} - Generated Web Component Preview
+ * Auto-generated from {name} component
+ * Tag: <{tag_name}>
+ */
+
+// Element parts; the first one is the root element
+const slots = [
+{slot_entries}
+];
+
+const defaultVariants = {{
+{default_entries}
+}};
+
+// Slot that receives unnamed children ('' means the root element)
+const defaultSlot = '{default_slot}';
+
+{root_constants}
+
+// Cache for adopted stylesheets (all page stylesheets)
+let cachedSheets = null;
+
+export class {class_name} extends HTMLElement {{
+  static observedAttributes = [{attrs_array}];
+
+  #root = null;
+
+  constructor() {{
+    super();
+    this.attachShadow({{ mode: 'open' }});
+  }}
+
+  connectedCallback() {{
+    this.#adoptStyles();
+    this.#render();
+  }}
+
+  attributeChangedCallback() {{
+    this.#render();
+  }}
+
+{adopt_styles}
+
+{selected}
+
+{create_root}
+
+  #classesFor(slot, selected) {{
+    const compoundClasses = slot.compoundVariants
+      .filter(rule => Object.entries(rule.when).every(([attr, values]) => values.includes(selected[attr])))
+      .map(rule => rule.classes);
+
+    return [
+      slot.classes,
+      ...Object.entries(slot.variants).map(
+        ([attr, lookup]) => lookup[selected[attr]] ?? lookup[defaultVariants[attr]] ?? '',
+      ),
+      ...compoundClasses,
+    ]
+      .filter(Boolean)
+      .join(' ');
+  }}
+
+  #render() {{
+    if (!this.shadowRoot) return;
+
+    const selected = this.#selected();
+
+    // Clear existing tree if any
+    if (this.#root) {{
+      this.#root.remove();
+    }}
+
+    const [rootSlot, ...parts] = slots;
+
+    this.#root = this.#createRoot();
+    this.#root.setAttribute('part', rootSlot.name);
+    this.#root.className = this.#classesFor(rootSlot, selected);
+
+    for (const part of parts) {{
+      const el = document.createElement('div');
+      el.setAttribute('part', part.name);
+      el.className = this.#classesFor(part, selected);
+
+      const slot = document.createElement('slot');
+      if (part.name !== defaultSlot) {{
+        slot.name = part.name;
+      }}
+      el.appendChild(slot);
+      this.#root.appendChild(el);
+    }}
+
+    if (!defaultSlot) {{
+      this.#root.appendChild(document.createElement('slot'));
+    }}
+
+    this.shadowRoot.appendChild(this.#root);
+  }}
+}}
+
+// Register the custom element
+if (typeof customElements !== 'undefined') {{
+  customElements.define('{tag_name}', {class_name});
+}}
+
+export default {class_name};
+"#,
+        class_name = class_name,
+        name = structure.name,
+        tag_name = tag_name,
+        slot_entries = slot_entries,
+        default_entries = default_entries,
+        default_slot = default_slot,
+        attrs_array = attrs_array,
+        root_constants = root_constants(structure),
+        adopt_styles = ADOPT_STYLES,
+        selected = SELECTED,
+        create_root = CREATE_ROOT,
+    )
+}
+
+/// Shared `#adoptStyles()` method: copies page stylesheets into the shadow root.
+const ADOPT_STYLES: &str = r#"  #adoptStyles() {
+    if (!this.shadowRoot) return;
+
+    // Use cached sheets if available
+    if (cachedSheets) {
+      this.shadowRoot.adoptedStyleSheets = cachedSheets;
+      return;
+    }
+
+    // Find and adopt page stylesheets
+    const sheets = [];
+    for (const sheet of document.styleSheets) {
+      try {
+        // Clone the stylesheet for adoption
+        const clone = new CSSStyleSheet();
+        const rules = Array.from(sheet.cssRules).map(r => r.cssText).join('\\n');
+        clone.replaceSync(rules);
+        sheets.push(clone);
+      } catch (e) {
+        // Cross-origin stylesheets can't be accessed, skip them
+      }
+    }
+
+    if (sheets.length > 0) {
+      cachedSheets = sheets; // Cache all sheets
+      this.shadowRoot.adoptedStyleSheets = sheets;
+    }
+  }"#;
+
+/// Shared `#selected()` method: the current value of every observed attribute.
+/// Variant dimensions fall back to their default; boolean attributes read as
+/// `'true'` when present and `'false'` when absent.
+const SELECTED: &str = r#"  #selected() {
+    const selected = {};
+    for (const attr of this.constructor.observedAttributes) {
+      const fallback = defaultVariants[attr] ?? 'false';
+      selected[attr] = this.getAttribute(attr) || (this.hasAttribute(attr) ? 'true' : fallback);
+    }
+    return selected;
+  }"#;
+
+/// Shared `#createRoot()` method: the component's root element with its
+/// static attributes.
+const CREATE_ROOT: &str = r#"  #createRoot() {
+    const root = document.createElement(rootTag);
+    for (const [name, value] of Object.entries(rootAttributes)) {
+      root.setAttribute(name, value);
+    }
+    // Keep preview buttons from submitting surrounding forms
+    if (rootTag === 'button' && !root.hasAttribute('type')) {
+      root.type = 'button';
+    }
+    return root;
+  }"#;
+
+/// Render the root element's tag and static attributes as JavaScript constants.
+fn root_constants(structure: &ComponentStructure) -> String {
+    let root = &structure.root;
+    format!(
+        "// Element rendered at the root, with its static attributes\nconst rootTag = '{}';\nconst rootAttributes = {{\n{}\n}};\nconst rootIsVoid = {};",
+        escape_string(&root.tag),
+        lookup_entries(&root.attributes, "  "),
+        root.is_void()
+    )
+}
+
+/// Render variant dimensions as nested JavaScript object entries.
+fn dimension_entries<'a>(
+    dimensions: impl Iterator<Item = (&'a str, &'a [(String, String)])>,
+    indent: &str,
+) -> String {
+    dimensions
+        .map(|(name, lookup)| {
+            format!(
+                "{indent}'{}': {{\n{}\n{indent}}},",
+                escape_string(name),
+                lookup_entries(lookup, &format!("{indent}  "))
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render the default value of each variant dimension as JavaScript object entries.
+fn default_entries(structure: &ComponentStructure) -> String {
+    structure
+        .dimensions
+        .iter()
+        .map(|d| {
+            format!(
+                "  '{}': '{}',",
+                escape_string(&d.name),
+                escape_string(&d.default)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render `(key, classes)` pairs as JavaScript object entries.
+fn lookup_entries(lookup: &[(String, String)], indent: &str) -> String {
+    lookup
+        .iter()
+        .map(|(k, v)| format!("{indent}'{}': '{}',", escape_string(k), escape_string(v)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render compound variant rules as JavaScript array entries.
+fn compound_entries(rules: &[CompoundVariant], indent: &str) -> String {
+    rules
+        .iter()
+        .map(|rule| {
+            let conditions = rule
+                .conditions
+                .iter()
+                .map(|(attr, values)| {
+                    let values = values
+                        .iter()
+                        .map(|v| format!("'{}'", escape_string(v)))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("'{}': [{}]", escape_string(attr), values)
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(
+                "{indent}{{ when: {{ {} }}, classes: '{}' }},",
+                conditions,
+                escape_string(&rule.classes)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// Convert kebab-case to PascalCase.
 fn to_pascal_case(s: &str) -> String {
     s.split('-')
@@ -196,6 +497,7 @@ fn escape_string(s: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::react::{ComponentSlot, RootElement, VariantDimension};
 
     #[test]
     fn to_pascal_case_works() {
@@ -215,33 +517,112 @@ mod tests {
     fn generates_valid_web_component() {
         let structure = ComponentStructure {
             name: "Button".to_string(),
-            variant_lookup: vec![
-                (
-                    "primary".to_string(),
-                    "bg-primary text-primary-foreground".to_string(),
-                ),
-                (
-                    "secondary".to_string(),
-                    "bg-secondary text-secondary-foreground".to_string(),
-                ),
-            ],
-            size_lookup: vec![
-                ("sm".to_string(), "h-8 px-3".to_string()),
-                ("md".to_string(), "h-10 px-4".to_string()),
+            dimensions: vec![
+                VariantDimension {
+                    name: "variant".to_string(),
+                    lookup: vec![
+                        (
+                            "primary".to_string(),
+                            "bg-primary text-primary-foreground".to_string(),
+                        ),
+                        (
+                            "secondary".to_string(),
+                            "bg-secondary text-secondary-foreground".to_string(),
+                        ),
+                    ],
+                    default: "primary".to_string(),
+                },
+                VariantDimension {
+                    name: "size".to_string(),
+                    lookup: vec![
+                        ("sm".to_string(), "h-8 px-3".to_string()),
+                        ("md".to_string(), "h-10 px-4".to_string()),
+                    ],
+                    default: "md".to_string(),
+                },
             ],
             base_classes: "inline-flex items-center".to_string(),
             disabled_classes: "opacity-50".to_string(),
-            default_variant: "primary".to_string(),
-            default_size: "md".to_string(),
+            root: RootElement {
+                tag: "a".to_string(),
+                attributes: vec![("role".to_string(), "button".to_string())],
+            },
             observed_attributes: vec!["variant".to_string(), "size".to_string()],
+            compound_variants: vec![CompoundVariant {
+                conditions: vec![
+                    ("variant".to_string(), vec!["primary".to_string()]),
+                    ("size".to_string(), vec!["sm".to_string(), "md".to_string()]),
+                ],
+                classes: "uppercase".to_string(),
+            }],
+            slots: vec![],
+            props: vec![],
         };
 
         let output = generate_web_component("my-button", &structure);
 
         assert!(output.contains("class MyButton extends HTMLElement"));
+        assert!(output.contains("    'primary': 'bg-primary text-primary-foreground',"));
+        assert!(output.contains("  'size': 'md',"));
+        assert!(output.contains(
+            "{ when: { 'variant': ['primary'], 'size': ['sm', 'md'] }, classes: 'uppercase' },"
+        ));
+        assert!(output.contains("const rootTag = 'a';"));
+        assert!(output.contains("  'role': 'button',"));
+        assert!(output.contains("const rootIsVoid = false;"));
         assert!(output.contains("static observedAttributes"));
         assert!(output.contains("customElements.define('my-button'"));
         assert!(output.contains("bg-primary"));
         assert!(output.contains("adoptedStyleSheets"));
     }
+
+    #[test]
+    fn generates_slotted_component() {
+        let structure = ComponentStructure {
+            name: "Card".to_string(),
+            slots: vec![
+                ComponentSlot {
+                    name: "base".to_string(),
+                    base_classes: "rounded-lg border".to_string(),
+                    variants: vec![(
+                        "shadow".to_string(),
+                        vec![
+                            ("none".to_string(), String::new()),
+                            ("md".to_string(), "shadow-md".to_string()),
+                        ],
+                    )],
+                    ..Default::default()
+                },
+                ComponentSlot {
+                    name: "header".to_string(),
+                    base_classes: "p-6".to_string(),
+                    ..Default::default()
+                },
+                ComponentSlot {
+                    name: "body".to_string(),
+                    base_classes: "p-6 pt-0".to_string(),
+                    ..Default::default()
+                },
+            ],
+            dimensions: vec![VariantDimension {
+                name: "shadow".to_string(),
+                lookup: vec![
+                    ("none".to_string(), String::new()),
+                    ("md".to_string(), "shadow-md".to_string()),
+                ],
+                default: "none".to_string(),
+            }],
+            observed_attributes: vec!["shadow".to_string()],
+            ..Default::default()
+        };
+
+        let output = generate_web_component("card-preview", &structure);
+
+        assert!(output.contains("class CardPreview extends HTMLElement"));
+        assert!(output.contains("name: 'header',"));
+        assert!(output.contains("'md': 'shadow-md',"));
+        assert!(output.contains("const defaultSlot = 'body';"));
+        assert!(output.contains("#adoptStyles() {"));
+        assert!(!output.contains("document.createElement('button')"));
+    }
 }
@@ -1,16 +1,32 @@
 //! Framework adapters for transforming JSX to Web Components.
 //!
 //! This crate provides the core transformation logic that converts React/Solid JSX
-//! components into static Web Components for documentation previews.
+//! components, Vue single-file components and Svelte components into static Web
+//! Components for documentation previews.
 
+mod ast;
 pub mod generator;
 pub mod inline;
+mod markup;
+pub mod props;
 pub mod react;
 pub mod registry;
+pub mod solid;
+pub mod svelte;
 pub mod traits;
+mod variants;
+pub mod vue;
 
 pub use generator::generate_web_component;
 pub use inline::{parse_inline_jsx, to_custom_element, InlineJsx, PropValue};
-pub use react::{ComponentStructure, ReactAdapter};
-pub use registry::{CachedComponent, ComponentRegistry, RegistryError};
-pub use traits::{FrameworkAdapter, TransformContext, TransformError, TransformedBlock};
+pub use props::PropDoc;
+pub use react::{
+    ComponentSlot, ComponentStructure, CompoundVariant, ReactAdapter, RootElement, VariantDimension,
+};
+pub use registry::{adapter_for, CachedComponent, ComponentRegistry, RegistryError};
+pub use solid::SolidAdapter;
+pub use svelte::SvelteAdapter;
+pub use traits::{
+    FrameworkAdapter, SourceSpan, TransformContext, TransformError, TransformedBlock,
+};
+pub use vue::VueAdapter;
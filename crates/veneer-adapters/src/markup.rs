@@ -0,0 +1,334 @@
+//! Minimal scanner for HTML-like component markup.
+//!
+//! Vue single-file components and Svelte components keep their script in
+//! `<script>` blocks next to HTML-like markup. This scanner finds those blocks
+//! and reads opening tags with their attributes; it does not build a tree.
+//! All offsets are byte offsets into the component file.
+
+use std::ops::Range;
+
+use oxc_span::SourceType;
+
+/// An opening tag.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Tag {
+    /// Tag name as written (e.g., "button", "template", "svelte:options")
+    pub name: String,
+
+    /// Attributes in source order
+    pub attributes: Vec<Attribute>,
+
+    /// Offset just past the closing `>`
+    pub end: usize,
+
+    /// Whether the tag ends with `/>`
+    pub self_closing: bool,
+}
+
+/// An attribute of an opening tag.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Attribute {
+    /// Attribute name as written (e.g., "class", ":class", "class:active", "{disabled}")
+    pub name: String,
+
+    /// Value without quotes or braces; `None` for bare attributes
+    pub value: Option<String>,
+
+    /// Offset of the first byte of the value
+    pub value_offset: usize,
+
+    /// Whether the value was written as a `{...}` expression
+    pub expression: bool,
+}
+
+impl Tag {
+    /// Get an attribute by name.
+    pub fn attribute(&self, name: &str) -> Option<&Attribute> {
+        self.attributes.iter().find(|attr| attr.name == name)
+    }
+}
+
+impl Attribute {
+    /// Byte range of the value in the component file.
+    pub fn value_range(&self) -> Range<usize> {
+        let len = self.value.as_ref().map_or(0, String::len);
+        self.value_offset..self.value_offset + len
+    }
+}
+
+/// Elements whose content is raw text rather than markup.
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style"];
+
+/// Iterate over the opening tags of `source` in document order, starting at
+/// `from`. Comments, closing tags and the content of `<script>` and
+/// `<style>` elements are skipped.
+pub(crate) fn tags(source: &str, from: usize) -> impl Iterator<Item = Tag> + '_ {
+    let mut pos = from;
+
+    std::iter::from_fn(move || loop {
+        let start = pos + source.get(pos..)?.find('<')?;
+        let rest = &source[start..];
+
+        if rest.starts_with("<!--") {
+            pos = rest.find("-->").map_or(source.len(), |end| start + end + 3);
+            continue;
+        }
+        if rest.starts_with("</") || rest.starts_with("<!") {
+            pos = rest.find('>').map_or(source.len(), |end| start + end + 1);
+            continue;
+        }
+
+        let Some(tag) = parse_tag(source, start) else {
+            pos = start + 1;
+            continue;
+        };
+
+        pos = tag.end;
+        if RAW_TEXT_ELEMENTS.contains(&tag.name.to_ascii_lowercase().as_str()) && !tag.self_closing
+        {
+            pos = closing_tag(source, &tag.name, tag.end).map_or(source.len(), |r| r.end);
+        }
+        return Some(tag);
+    })
+}
+
+/// Find top-level blocks such as `<script setup lang="ts">`, returning each
+/// opening tag with the byte range of its content.
+pub(crate) fn blocks<'s>(
+    source: &'s str,
+    name: &'s str,
+) -> impl Iterator<Item = (Tag, Range<usize>)> + 's {
+    tags(source, 0)
+        .filter(move |tag| tag.name == name && !tag.self_closing)
+        .filter_map(move |tag| {
+            let close = closing_tag(source, name, tag.end)?;
+            let content = tag.end..close.start;
+            Some((tag, content))
+        })
+}
+
+/// Get the first `<template>` block and the byte range of its content.
+///
+/// Templates may nest, so the content runs to the last `</template>`.
+pub(crate) fn template_block(source: &str) -> Option<(Tag, Range<usize>)> {
+    let tag = tags(source, 0).find(|tag| tag.name == "template" && !tag.self_closing)?;
+    let end = source.rfind("</template>").filter(|&end| end >= tag.end)?;
+    let content = tag.end..end;
+    Some((tag, content))
+}
+
+/// Get the code of all `<script>` blocks with everything else blanked, and
+/// the source type to parse it with (TypeScript for `lang="ts"`).
+pub(crate) fn script_source(source: &str) -> (String, SourceType) {
+    let scripts: Vec<_> = blocks(source, "script").collect();
+    let typescript = scripts.iter().any(|(tag, _)| {
+        tag.attribute("lang")
+            .and_then(|attr| attr.value.as_deref())
+            .is_some_and(|lang| lang == "ts" || lang == "tsx")
+    });
+    let ranges: Vec<_> = scripts.into_iter().map(|(_, range)| range).collect();
+
+    let source_type = if typescript {
+        SourceType::ts()
+    } else {
+        SourceType::mjs()
+    };
+    (mask_except(source, &ranges), source_type)
+}
+
+/// Copy `source`, blanking everything outside `ranges` with spaces.
+///
+/// Newlines are kept, so offsets, lines and columns of the kept parts match
+/// the original file and parse errors point at the right place.
+pub(crate) fn mask_except(source: &str, ranges: &[Range<usize>]) -> String {
+    let mut out = String::with_capacity(source.len());
+
+    for (offset, c) in source.char_indices() {
+        if c == '\n' || ranges.iter().any(|r| r.contains(&offset)) {
+            out.push(c);
+        } else {
+            out.extend((0..c.len_utf8()).map(|_| ' '));
+        }
+    }
+
+    out
+}
+
+/// Find `</name>` after `from`, returning its byte range.
+fn closing_tag(source: &str, name: &str, from: usize) -> Option<Range<usize>> {
+    let needle = format!("</{name}");
+    let start = from + source.get(from..)?.find(&needle)?;
+    let end = start + source[start..].find('>')? + 1;
+    Some(start..end)
+}
+
+/// Parse an opening tag starting at the `<` at `start`.
+fn parse_tag(source: &str, start: usize) -> Option<Tag> {
+    let bytes = source.as_bytes();
+    let mut pos = start + 1;
+
+    let name_end = pos + source[pos..].find(|c: char| c.is_whitespace() || c == '>' || c == '/')?;
+    let name = &source[pos..name_end];
+    if name.is_empty() || !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    pos = name_end;
+
+    let mut attributes = Vec::new();
+
+    loop {
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        match bytes.get(pos)? {
+            b'>' => {
+                return Some(Tag {
+                    name: name.to_string(),
+                    attributes,
+                    end: pos + 1,
+                    self_closing: false,
+                });
+            }
+            b'/' if bytes.get(pos + 1) == Some(&b'>') => {
+                return Some(Tag {
+                    name: name.to_string(),
+                    attributes,
+                    end: pos + 2,
+                    self_closing: true,
+                });
+            }
+            b'/' => pos += 1,
+            b'{' => {
+                // Svelte shorthand (`{disabled}`) or spread (`{...props}`)
+                let end = expression_end(source, pos)?;
+                attributes.push(Attribute {
+                    name: source[pos..end].to_string(),
+                    value: None,
+                    value_offset: end,
+                    expression: false,
+                });
+                pos = end;
+            }
+            _ => {
+                let name_end = pos
+                    + source[pos..]
+                        .find(|c: char| c.is_whitespace() || matches!(c, '=' | '>' | '/'))?;
+                let attr_name = source[pos..name_end].to_string();
+                pos = name_end;
+
+                if bytes.get(pos) != Some(&b'=') {
+                    attributes.push(Attribute {
+                        name: attr_name,
+                        value: None,
+                        value_offset: pos,
+                        expression: false,
+                    });
+                    continue;
+                }
+                pos += 1;
+
+                let (value_range, expression, next) = match bytes.get(pos)? {
+                    quote @ (b'"' | b'\'') => {
+                        let end = pos + 1 + source[pos + 1..].find(*quote as char)?;
+                        (pos + 1..end, false, end + 1)
+                    }
+                    b'{' => {
+                        let end = expression_end(source, pos)?;
+                        (pos + 1..end - 1, true, end)
+                    }
+                    _ => {
+                        let end = pos
+                            + source[pos..]
+                                .find(|c: char| c.is_whitespace() || c == '>')
+                                .unwrap_or(source.len() - pos);
+                        (pos..end, false, end)
+                    }
+                };
+
+                attributes.push(Attribute {
+                    name: attr_name,
+                    value: Some(source[value_range.clone()].to_string()),
+                    value_offset: value_range.start,
+                    expression,
+                });
+                pos = next;
+            }
+        }
+    }
+}
+
+/// Find the end (exclusive) of a `{...}` expression starting at `start`,
+/// skipping braces inside string literals.
+fn expression_end(source: &str, start: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+
+    for (offset, c) in source[start..].char_indices() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' | '`' => quote = Some(c),
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(start + offset + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_tags_and_attributes() {
+        let source = r#"<!-- <fake> --><button type="button" :class="{ a: b }" disabled class:active={on} {...rest} />"#;
+        let tag = tags(source, 0).next().unwrap();
+
+        assert_eq!(tag.name, "button");
+        assert!(tag.self_closing);
+        assert_eq!(
+            tag.attribute("type").unwrap().value.as_deref(),
+            Some("button")
+        );
+        assert_eq!(
+            tag.attribute(":class").unwrap().value.as_deref(),
+            Some("{ a: b }")
+        );
+        assert_eq!(tag.attribute("disabled").unwrap().value, None);
+
+        let active = tag.attribute("class:active").unwrap();
+        assert!(active.expression);
+        assert_eq!(&source[active.value_range()], "on");
+        assert!(tag.attribute("{...rest}").is_some());
+    }
+
+    #[test]
+    fn skips_script_content_and_masks_blocks() {
+        let source = "<script>\nif (a < b) {}\n</script>\n<div class=\"x\"></div>";
+
+        let names: Vec<_> = tags(source, 0).map(|t| t.name).collect();
+        assert_eq!(names, vec!["script", "div"]);
+
+        let (_, content) = blocks(source, "script").next().unwrap();
+        let masked = mask_except(source, &[content]);
+        assert_eq!(masked.len(), source.len());
+        assert!(masked.starts_with("        \nif (a < b) {}\n"));
+        assert!(!masked.contains("div"));
+    }
+}
@@ -0,0 +1,220 @@
+//! Props documentation from TypeScript types.
+//!
+//! Reads the property signatures of a props interface or type literal into
+//! [`PropDoc`]s for API tables:
+//!
+//! ```tsx
+//! interface ButtonProps {
+//!   /** Visual style of the button. */
+//!   variant?: 'default' | 'outline';
+//!   /**
+//!    * Shows a spinner and disables the button.
+//!    * @default false
+//!    */
+//!   loading?: boolean;
+//! }
+//! ```
+
+use oxc_ast::ast::{Declaration, Program, TSSignature, TSType, TSTypeName};
+use oxc_span::GetSpan;
+
+use crate::ast::{property_key_name, top_level_declarations};
+
+/// A documented component prop.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PropDoc {
+    /// Prop name (e.g., "variant")
+    pub name: String,
+
+    /// TypeScript type as written (e.g., "'default' | 'outline'")
+    pub ty: String,
+
+    /// Whether the prop is required (declared without `?`)
+    pub required: bool,
+
+    /// Default value from destructuring, the variant definition or `@default`
+    pub default: Option<String>,
+
+    /// Description from the JSDoc comment, without tags
+    pub description: Option<String>,
+}
+
+/// Collect the props of the first `*Props` interface or type alias.
+pub(crate) fn declared_props(program: &Program) -> Vec<PropDoc> {
+    props_matching(program, |name| name.ends_with("Props"))
+}
+
+/// Collect the props of a props type annotation: an inline type literal or
+/// a reference to an interface or type alias declared in the module.
+pub(crate) fn type_props(program: &Program, ty: &TSType) -> Vec<PropDoc> {
+    let mut props = Vec::new();
+    match ty {
+        TSType::TSTypeReference(reference) => {
+            if let TSTypeName::IdentifierReference(id) = &reference.type_name {
+                props = props_matching(program, |candidate| candidate == id.name);
+            }
+        }
+        ty => collect_type_props(program, ty, &mut props),
+    }
+    props
+}
+
+/// Collect the props of the first interface or type alias whose name matches.
+fn props_matching(program: &Program, matches: impl Fn(&str) -> bool) -> Vec<PropDoc> {
+    let mut props = Vec::new();
+
+    for decl in top_level_declarations(program) {
+        match decl {
+            Declaration::TSInterfaceDeclaration(iface) if matches(&iface.id.name) => {
+                collect_signature_props(program, &iface.body.body, &mut props);
+                break;
+            }
+            Declaration::TSTypeAliasDeclaration(alias) if matches(&alias.id.name) => {
+                collect_type_props(program, &alias.type_annotation, &mut props);
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    props
+}
+
+/// Collect the props of a type literal or an intersection of type literals.
+fn collect_type_props(program: &Program, ty: &TSType, props: &mut Vec<PropDoc>) {
+    match ty {
+        TSType::TSTypeLiteral(lit) => collect_signature_props(program, &lit.members, props),
+        TSType::TSIntersectionType(inter) => {
+            for ty in &inter.types {
+                collect_type_props(program, ty, props);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn collect_signature_props(
+    program: &Program,
+    signatures: &[TSSignature],
+    props: &mut Vec<PropDoc>,
+) {
+    for sig in signatures {
+        let TSSignature::TSPropertySignature(prop) = sig else {
+            continue;
+        };
+        let Some(name) = property_key_name(&prop.key) else {
+            continue;
+        };
+
+        let ty = prop
+            .type_annotation
+            .as_ref()
+            .map(|annotation| {
+                let span = annotation.type_annotation.span();
+                program.source_text[span.start as usize..span.end as usize].to_string()
+            })
+            .unwrap_or_else(|| "any".to_string());
+
+        let (description, default) = jsdoc(program, prop.span.start);
+        props.push(PropDoc {
+            name,
+            ty,
+            required: !prop.optional,
+            default,
+            description,
+        });
+    }
+}
+
+/// Read the JSDoc comment attached to the node starting at `start`, returning
+/// its description and `@default` tag.
+fn jsdoc(program: &Program, start: u32) -> (Option<String>, Option<String>) {
+    let Some(comment) = program
+        .comments
+        .iter()
+        .find(|comment| comment.attached_to == start && comment.is_jsdoc())
+    else {
+        return (None, None);
+    };
+
+    let span = comment.content_span();
+    let text = &program.source_text[span.start as usize..span.end as usize];
+    let lines = text.lines().map(|line| {
+        let line = line.trim();
+        line.strip_prefix('*').unwrap_or(line).trim()
+    });
+
+    let mut description: Vec<&str> = Vec::new();
+    let mut default = None;
+    let mut in_tags = false;
+    for line in lines {
+        if let Some(tag) = line.strip_prefix('@') {
+            in_tags = true;
+            if let Some(value) = tag.strip_prefix("default") {
+                default = Some(value.trim().to_string()).filter(|v| !v.is_empty());
+            }
+        } else if !in_tags && !line.is_empty() {
+            description.push(line);
+        }
+    }
+
+    let description = Some(description.join(" ")).filter(|d| !d.is_empty());
+    (description, default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::parse_program;
+    use oxc_allocator::Allocator;
+    use oxc_span::SourceType;
+
+    #[test]
+    fn reads_types_optionality_and_jsdoc() {
+        let source = r#"
+export interface ButtonProps {
+  /** Visual style of the button. */
+  variant?: 'default' | 'outline';
+  /**
+   * Shows a spinner and disables
+   * the button.
+   * @default false
+   */
+  loading?: boolean;
+  // Not JSDoc
+  label: string;
+}
+        "#;
+
+        let allocator = Allocator::default();
+        let program = parse_program(&allocator, source, SourceType::tsx()).unwrap();
+        let props = declared_props(&program);
+
+        assert_eq!(
+            props,
+            vec![
+                PropDoc {
+                    name: "variant".to_string(),
+                    ty: "'default' | 'outline'".to_string(),
+                    required: false,
+                    default: None,
+                    description: Some("Visual style of the button.".to_string()),
+                },
+                PropDoc {
+                    name: "loading".to_string(),
+                    ty: "boolean".to_string(),
+                    required: false,
+                    default: Some("false".to_string()),
+                    description: Some("Shows a spinner and disables the button.".to_string()),
+                },
+                PropDoc {
+                    name: "label".to_string(),
+                    ty: "string".to_string(),
+                    required: true,
+                    default: None,
+                    description: None,
+                },
+            ]
+        );
+    }
+}
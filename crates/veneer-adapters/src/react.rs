@@ -1,10 +1,20 @@
 //! React/JSX adapter for transforming components to Web Components.
 
-use regex::Regex;
-use std::sync::LazyLock;
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{
+    BinaryOperator, BindingPattern, BindingPatternKind, Declaration, ExportDefaultDeclarationKind,
+    Expression, FormalParameters, Function, FunctionBody, JSXAttributeItem, JSXAttributeName,
+    JSXAttributeValue, JSXElement, JSXElementName, ObjectPattern, Program, Statement,
+};
+use oxc_span::SourceType;
 
-use crate::generator::generate_web_component;
-use crate::traits::{FrameworkAdapter, TransformContext, TransformError, TransformedBlock};
+use crate::ast::{
+    find_binding, normalize_classes, object_properties, parse_program, property_key_name,
+    static_record, static_scalar, static_string, top_level_declarations,
+};
+use crate::props::{declared_props, PropDoc};
+use crate::traits::{FrameworkAdapter, TransformError};
+use crate::variants::find_variant_config;
 
 /// Extracted component structure from source code.
 #[derive(Debug, Clone, Default)]
@@ -12,11 +22,8 @@ pub struct ComponentStructure {
     /// Component name (e.g., "Button")
     pub name: String,
 
-    /// Variant classes mapping
-    pub variant_lookup: Vec<(String, String)>,
-
-    /// Size classes mapping
-    pub size_lookup: Vec<(String, String)>,
+    /// Variant dimensions in declaration order (e.g., `variant`, `size`, `tone`)
+    pub dimensions: Vec<VariantDimension>,
 
     /// Base classes applied to all variants
     pub base_classes: String,
@@ -24,14 +31,149 @@ pub struct ComponentStructure {
     /// Classes applied when disabled
     pub disabled_classes: String,
 
-    /// Default variant value
-    pub default_variant: String,
-
-    /// Default size value
-    pub default_size: String,
+    /// Element rendered at the root of the component
+    pub root: RootElement,
 
     /// Observed attributes from props
     pub observed_attributes: Vec<String>,
+
+    /// Classes applied when several variant values match at once
+    pub compound_variants: Vec<CompoundVariant>,
+
+    /// Element parts for multi-slot components (empty for single-element components)
+    pub slots: Vec<ComponentSlot>,
+
+    /// Documented props from the props type, for API tables
+    pub props: Vec<PropDoc>,
+}
+
+impl ComponentStructure {
+    /// Get a variant dimension by attribute name.
+    pub fn dimension(&self, name: &str) -> Option<&VariantDimension> {
+        self.dimensions
+            .iter()
+            .find(|dimension| dimension.name == name)
+    }
+
+    /// Collect every class the component can render, without duplicates.
+    pub fn all_classes(&self) -> Vec<String> {
+        let lookups = self
+            .dimensions
+            .iter()
+            .flat_map(|dimension| dimension.lookup.iter().map(|(_, c)| c.as_str()));
+        let compound = self.compound_variants.iter().map(|c| c.classes.as_str());
+        let slots = self.slots.iter().flat_map(|slot| {
+            std::iter::once(slot.base_classes.as_str())
+                .chain(
+                    slot.variants
+                        .iter()
+                        .flat_map(|(_, lookup)| lookup.iter().map(|(_, c)| c.as_str())),
+                )
+                .chain(slot.compound_variants.iter().map(|c| c.classes.as_str()))
+        });
+
+        let mut classes: Vec<String> = Vec::new();
+        for list in std::iter::once(self.base_classes.as_str())
+            .chain(lookups)
+            .chain(compound)
+            .chain(slots)
+            .chain(std::iter::once(self.disabled_classes.as_str()))
+        {
+            for class in list.split_whitespace() {
+                if !classes.iter().any(|c| c == class) {
+                    classes.push(class.to_string());
+                }
+            }
+        }
+
+        classes
+    }
+}
+
+/// The element a component renders at its root, such as `<button>` or
+/// `<div role="alert">`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RootElement {
+    /// Tag name (e.g., "button", "a", "input")
+    pub tag: String,
+
+    /// Attributes with static values, in source order
+    pub attributes: Vec<(String, String)>,
+}
+
+/// Roots that cannot be resolved render as a `<button>`, the element
+/// previews used before root detection.
+impl Default for RootElement {
+    fn default() -> Self {
+        Self {
+            tag: "button".to_string(),
+            attributes: Vec::new(),
+        }
+    }
+}
+
+impl RootElement {
+    /// Whether this is a void element such as `<input>`, which cannot have children.
+    pub fn is_void(&self) -> bool {
+        VOID_ELEMENTS.contains(&self.tag.as_str())
+    }
+}
+
+/// HTML elements that cannot have children.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// One variant axis of a component, observed as an attribute of the same name.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VariantDimension {
+    /// Attribute name (e.g., "variant", "size", "tone")
+    pub name: String,
+
+    /// Classes per value
+    pub lookup: Vec<(String, String)>,
+
+    /// Value used when the attribute is absent
+    pub default: String,
+}
+
+/// One element of a multi-part component, such as the `header` slot of a
+/// tailwind-variants `tv({ slots })` definition.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ComponentSlot {
+    /// Slot name (e.g., "base", "header", "body")
+    pub name: String,
+
+    /// Classes always applied to this slot
+    pub base_classes: String,
+
+    /// Classes per variant dimension and value for this slot
+    pub variants: Vec<(String, Vec<(String, String)>)>,
+
+    /// Compound rules that add classes to this slot
+    pub compound_variants: Vec<CompoundVariant>,
+}
+
+impl ComponentSlot {
+    /// Get this slot's lookup for a variant dimension.
+    pub fn lookup(&self, dimension: &str) -> Option<&[(String, String)]> {
+        self.variants
+            .iter()
+            .find(|(name, _)| name == dimension)
+            .map(|(_, lookup)| lookup.as_slice())
+    }
+}
+
+/// A class rule applied when several variant values match at once,
+/// as declared by `compoundVariants` in `cva` and `tv`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompoundVariant {
+    /// Required values per attribute (any of the listed values matches)
+    pub conditions: Vec<(String, Vec<String>)>,
+
+    /// Classes added when every condition matches
+    pub classes: String,
 }
 
 /// React/JSX to Web Component adapter.
@@ -43,41 +185,6 @@ impl ReactAdapter {
     pub fn new() -> Self {
         Self
     }
-
-    /// Extract component structure from source code using regex patterns.
-    pub fn extract_structure(&self, source: &str) -> Result<ComponentStructure, TransformError> {
-        // Extract variantClasses Record (required)
-        let variant_lookup = extract_record(source, "variantClasses")?;
-        if variant_lookup.is_empty() {
-            return Err(TransformError::MissingVariants);
-        }
-
-        // Extract sizeClasses Record (optional)
-        let size_lookup = extract_record(source, "sizeClasses").unwrap_or_default();
-
-        // Compute defaults from lookups
-        let default_variant = variant_lookup
-            .first()
-            .map(|(k, _)| k.clone())
-            .unwrap_or_else(|| "default".to_string());
-
-        let default_size = size_lookup
-            .first()
-            .map(|(k, _)| k.clone())
-            .unwrap_or_else(|| "default".to_string());
-
-        Ok(ComponentStructure {
-            name: extract_component_name(source).unwrap_or_else(|| "Component".to_string()),
-            base_classes: extract_base_classes(source).unwrap_or_default(),
-            disabled_classes: extract_disabled_classes(source)
-                .unwrap_or_else(|| "opacity-50 pointer-events-none cursor-not-allowed".to_string()),
-            variant_lookup,
-            size_lookup,
-            default_variant,
-            default_size,
-            observed_attributes: extract_attributes(source),
-        })
-    }
 }
 
 impl FrameworkAdapter for ReactAdapter {
@@ -89,227 +196,706 @@ impl FrameworkAdapter for ReactAdapter {
         &["tsx", "jsx"]
     }
 
-    fn transform(
-        &self,
-        source: &str,
-        tag_name: &str,
-        _ctx: &TransformContext,
-    ) -> Result<TransformedBlock, TransformError> {
-        let structure = self.extract_structure(source)?;
+    /// Parses the source as TSX and reads either a `cva(...)` / `tv(...)`
+    /// definition or `<dimension>Classes` Records (such as `variantClasses`,
+    /// `sizeClasses` or `toneClasses`) together with `baseClasses` and
+    /// `disabledClasses`, plus the component's props.
+    /// Values that cannot be evaluated statically are reported as
+    /// [`TransformError::Unsupported`] with their span.
+    fn extract_structure(&self, source: &str) -> Result<ComponentStructure, TransformError> {
+        let allocator = Allocator::default();
+        let program = parse_program(&allocator, source, SourceType::tsx())?;
+
+        let component = find_component(&program);
+
+        // Destructured props and their defaults, e.g. `({ variant = 'default' })`
+        let mut hints = ComponentHints::default();
+        let param = component
+            .as_ref()
+            .and_then(|c| c.params)
+            .and_then(|params| params.items.first());
+        if let Some(param) = param {
+            hints.props = destructured_props(&param.pattern);
+            hints.defaults = destructured_defaults(&param.pattern);
+        }
+
+        build_structure(&program, component.as_ref(), hints)
+    }
+}
+
+/// Framework-specific facts about a component that complement the variant
+/// definition, such as props from `splitProps` or classes from `classList`.
+#[derive(Debug, Default)]
+pub(crate) struct ComponentHints {
+    /// Prop names the component reads
+    pub props: Vec<String>,
 
-        // Collect all classes used
-        let mut classes_used: Vec<String> = Vec::new();
+    /// Default prop values
+    pub defaults: Vec<(String, String)>,
 
-        // Add base classes
-        for class in structure.base_classes.split_whitespace() {
-            if !classes_used.contains(&class.to_string()) {
-                classes_used.push(class.to_string());
+    /// Lookups derived from conditional class bindings
+    pub lookups: Vec<(String, Lookup)>,
+
+    /// Classes bound to the `disabled` prop
+    pub disabled_classes: Option<String>,
+
+    /// Static classes of the root element
+    pub base_classes: Option<String>,
+
+    /// Root element, when it is not described by JSX in the component body
+    pub root: Option<RootElement>,
+
+    /// Documented props from a props type other than a `*Props` declaration,
+    /// such as Vue's `defineProps<{ ... }>()`
+    pub prop_docs: Vec<PropDoc>,
+}
+
+impl ComponentHints {
+    /// Add classes applied when dimension `name` has value `value`.
+    pub fn add_lookup(&mut self, name: &str, value: &str, classes: &str) {
+        let index = match self.lookups.iter().position(|(n, _)| n == name) {
+            Some(index) => index,
+            None => {
+                self.lookups.push((name.to_string(), Vec::new()));
+                self.lookups.len() - 1
             }
+        };
+        merge_entry(&mut self.lookups[index].1, value, classes);
+    }
+
+    /// Add classes applied while boolean prop `name` is set.
+    ///
+    /// `disabled` feeds the disabled classes; other flags become a dimension
+    /// with `false` and `true` values that defaults to `false`.
+    pub fn add_flag(&mut self, name: &str, classes: &str) {
+        if name == "disabled" {
+            let disabled = self.disabled_classes.get_or_insert_with(String::new);
+            *disabled = normalize_classes(&format!("{disabled} {classes}"));
+            return;
         }
+        self.add_lookup(name, "false", "");
+        self.add_lookup(name, "true", classes);
+        if !self.defaults.iter().any(|(n, _)| n == name) {
+            self.defaults.push((name.to_string(), "false".to_string()));
+        }
+    }
 
-        // Add variant classes
-        for (_, classes) in &structure.variant_lookup {
-            for class in classes.split_whitespace() {
-                if !classes_used.contains(&class.to_string()) {
-                    classes_used.push(class.to_string());
-                }
+    /// Add props documented by a props type, reading their names as well.
+    pub fn add_prop_docs(&mut self, props: Vec<PropDoc>) {
+        for prop in &props {
+            if !self.props.contains(&prop.name) {
+                self.props.push(prop.name.clone());
             }
         }
+        self.prop_docs.extend(props);
+    }
 
-        // Add size classes
-        for (_, classes) in &structure.size_lookup {
-            for class in classes.split_whitespace() {
-                if !classes_used.contains(&class.to_string()) {
-                    classes_used.push(class.to_string());
-                }
-            }
+    /// Add classes applied while `condition` holds, as in Solid's `classList`
+    /// or Vue's `:class` objects. Conditions the preview cannot evaluate from
+    /// attributes are ignored.
+    pub fn add_conditional(&mut self, classes: &str, condition: &Expression) {
+        match read_condition(condition) {
+            Some(Condition::Flag(prop)) => self.add_flag(&prop, classes),
+            Some(Condition::Equals(prop, value)) => self.add_lookup(&prop, &value, classes),
+            None => {}
         }
+    }
 
-        // Add disabled classes
-        for class in structure.disabled_classes.split_whitespace() {
-            if !classes_used.contains(&class.to_string()) {
-                classes_used.push(class.to_string());
+    /// Add a class binding such as Vue's `:class` or Svelte 5's `class={...}`:
+    /// an object of conditional classes, a static string, or an array of
+    /// those. Other values are left to the variant definition.
+    pub fn add_class_binding(&mut self, expr: &Expression) -> Result<(), TransformError> {
+        match expr.get_inner_expression() {
+            Expression::ObjectExpression(_) => {
+                for (classes, condition) in object_properties(expr)? {
+                    self.add_conditional(&classes, condition);
+                }
+            }
+            Expression::ArrayExpression(items) => {
+                for item in items
+                    .elements
+                    .iter()
+                    .filter_map(|item| item.as_expression())
+                {
+                    self.add_class_binding(item)?;
+                }
+            }
+            expr => {
+                if let Ok(classes) = static_string(expr) {
+                    let base = self.base_classes.get_or_insert_with(String::new);
+                    *base = normalize_classes(&format!("{base} {classes}"));
+                }
             }
         }
 
-        // Generate the Web Component
-        let web_component = generate_web_component(tag_name, &structure);
+        Ok(())
+    }
 
-        Ok(TransformedBlock {
-            web_component,
-            tag_name: tag_name.to_string(),
-            classes_used,
-            attributes: structure.observed_attributes,
-        })
+    fn default_for(&self, name: &str) -> Option<&str> {
+        self.defaults
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
     }
 }
 
-// Regex patterns for extraction
-static COMPONENT_NAME_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?:export\s+)?(?:function|const)\s+([A-Z][a-zA-Z0-9]*)")
-        .expect("Invalid component name regex")
-});
+/// A class condition the preview can evaluate from attributes.
+enum Condition {
+    /// `props.disabled`: applies while the prop is set
+    Flag(String),
 
-static RECORD_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"const\s+(\w+)\s*(?::\s*Record<[^>]+>)?\s*=\s*\{([^}]+)\}")
-        .expect("Invalid record regex")
-});
+    /// `props.variant === 'primary'`: applies for one prop value
+    Equals(String, String),
+}
 
-static ENTRY_RE: LazyLock<Regex> = LazyLock::new(|| {
-    // Match: key: 'value' or key: "value"
-    Regex::new(r#"(\w+)\s*:\s*['"]([^'"]*)['""]"#).expect("Invalid entry regex")
-});
+fn read_condition(expr: &Expression) -> Option<Condition> {
+    match expr.get_inner_expression() {
+        Expression::BinaryExpression(bin)
+            if matches!(
+                bin.operator,
+                BinaryOperator::StrictEquality | BinaryOperator::Equality
+            ) =>
+        {
+            let (prop, value) = match prop_name(&bin.left) {
+                Some(prop) => (prop, &bin.right),
+                None => (prop_name(&bin.right)?, &bin.left),
+            };
+            Some(Condition::Equals(prop, static_scalar(value).ok()?))
+        }
+        expr => prop_name(expr).map(Condition::Flag),
+    }
+}
 
-static BASE_CLASSES_CONCAT_RE: LazyLock<Regex> = LazyLock::new(|| {
-    // Match: const baseClasses = 'string' + 'string' ...
-    Regex::new(r"const\s+baseClasses\s*=\s*\n?\s*(['\x22][^;]+)")
-        .expect("Invalid base classes concat regex")
-});
+/// Get the prop a value reads: `props.size`, `local.size`, `size` or an
+/// accessor call such as `size()`.
+fn prop_name(expr: &Expression) -> Option<String> {
+    match expr.get_inner_expression() {
+        Expression::StaticMemberExpression(member) => Some(member.property.name.to_string()),
+        Expression::Identifier(id) => Some(id.name.to_string()),
+        Expression::CallExpression(call) if call.arguments.is_empty() => prop_name(&call.callee),
+        _ => None,
+    }
+}
 
-static BASE_CLASSES_SIMPLE_RE: LazyLock<Regex> = LazyLock::new(|| {
-    // Match: const baseClasses = 'simple string'
-    Regex::new(r#"const\s+baseClasses\s*=\s*['"]([^'"]+)['"]"#)
-        .expect("Invalid base classes simple regex")
-});
+/// Add `classes` to the entry for `value`, creating it if needed.
+fn merge_entry(lookup: &mut Lookup, value: &str, classes: &str) {
+    match lookup.iter_mut().find(|(v, _)| v == value) {
+        Some((_, existing)) => *existing = normalize_classes(&format!("{existing} {classes}")),
+        None => lookup.push((value.to_string(), normalize_classes(classes))),
+    }
+}
 
-static DISABLED_CLASSES_RE: LazyLock<Regex> = LazyLock::new(|| {
-    // Match: disabledClasses = 'classes' or const disabledCls = 'classes'
-    Regex::new(r#"(?:const\s+)?disabledCl(?:asse)?s\s*=\s*['"]([^'"]+)['"]"#)
-        .expect("Invalid disabled classes regex")
-});
+/// Build a component structure from a parsed module.
+///
+/// Reads a `cva(...)` / `tv(...)` definition or `*Classes` Records from the
+/// module and merges in the framework-specific `hints`.
+pub(crate) fn build_structure(
+    program: &Program,
+    component: Option<&ComponentFn>,
+    mut hints: ComponentHints,
+) -> Result<ComponentStructure, TransformError> {
+    // Prefer a cva()/tv() definition, then fall back to `*Classes` Records
+    let cva = find_variant_config(program)?;
+
+    let (mut lookups, base_classes, disabled_classes) = match &cva {
+        Some(cva) => (
+            cva.root()
+                .into_iter()
+                .flat_map(|root| &root.variants)
+                .filter(|(dimension, _)| dimension != "disabled")
+                .cloned()
+                .collect::<Vec<_>>(),
+            cva.root()
+                .map(|root| root.base_classes.clone())
+                .unwrap_or_default(),
+            cva.lookup("disabled").and_then(|lookup| {
+                lookup
+                    .iter()
+                    .find(|(value, _)| value == "true")
+                    .map(|(_, classes)| classes.clone())
+            }),
+        ),
+        None => (
+            class_records(program)?,
+            find_binding(program, "baseClasses")
+                .map(static_string)
+                .transpose()?
+                .unwrap_or_default(),
+            ["disabledClasses", "disabledCls"]
+                .iter()
+                .find_map(|name| find_binding(program, name))
+                .map(static_string)
+                .transpose()?,
+        ),
+    };
+
+    for (name, entries) in &hints.lookups {
+        let index = match lookups.iter().position(|(n, _)| n == name) {
+            Some(index) => index,
+            None => {
+                lookups.push((name.clone(), Vec::new()));
+                lookups.len() - 1
+            }
+        };
+        for (value, classes) in entries {
+            merge_entry(&mut lookups[index].1, value, classes);
+        }
+    }
 
-static PROPS_INTERFACE_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"interface\s+\w*Props\s*\{([^}]+)\}").expect("Invalid props interface regex")
-});
+    // Multi-slot components may carry their variants on other slots only
+    let slotted = cva.as_ref().is_some_and(|cva| cva.slotted);
+    if lookups.is_empty() && !slotted {
+        return Err(TransformError::MissingVariants);
+    }
 
-static DESTRUCTURE_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"\{\s*([^}]+)\s*\}\s*(?::\s*\w+)?\s*\)").expect("Invalid destructure regex")
-});
+    // Declared defaults win, then prop defaults, otherwise the first key of each lookup
+    let dimensions = lookups
+        .into_iter()
+        .map(|(name, lookup)| {
+            let default = cva
+                .as_ref()
+                .and_then(|cva| cva.default_for(&name))
+                .or_else(|| hints.default_for(&name))
+                .map(str::to_string)
+                .or_else(|| lookup.first().map(|(k, _)| k.clone()))
+                .unwrap_or_else(|| "default".to_string());
+            VariantDimension {
+                name,
+                lookup,
+                default,
+            }
+        })
+        .collect();
+
+    // Document the props type; defaults come from destructuring, then JSDoc
+    // `@default`, then the variant definition
+    let mut props = if hints.prop_docs.is_empty() {
+        declared_props(program)
+    } else {
+        std::mem::take(&mut hints.prop_docs)
+    };
+    for prop in &mut props {
+        prop.default = hints
+            .default_for(&prop.name)
+            .map(str::to_string)
+            .or(prop.default.take())
+            .or_else(|| {
+                cva.as_ref()
+                    .and_then(|cva| cva.default_for(&prop.name))
+                    .map(str::to_string)
+            });
+    }
 
-/// Extract component name from source.
-pub fn extract_component_name(source: &str) -> Option<String> {
-    COMPONENT_NAME_RE
-        .captures(source)
-        .map(|c| c.get(1).unwrap().as_str().to_string())
+    let base_classes = match hints.base_classes {
+        Some(classes) if base_classes.is_empty() => classes,
+        _ => base_classes,
+    };
+    let disabled_classes = disabled_classes
+        .or(hints.disabled_classes)
+        .unwrap_or_else(|| "opacity-50 pointer-events-none cursor-not-allowed".to_string());
+
+    let (compound_variants, slots) = match cva {
+        Some(cva) if cva.slotted => (Vec::new(), cva.slots),
+        Some(mut cva) => {
+            let compound = cva
+                .slots
+                .first_mut()
+                .map(|root| std::mem::take(&mut root.compound_variants))
+                .unwrap_or_default();
+            (compound, Vec::new())
+        }
+        None => (Vec::new(), Vec::new()),
+    };
+
+    let root = hints
+        .root
+        .or_else(|| component.and_then(|c| c.body).and_then(root_element))
+        .unwrap_or_default();
+
+    let mut structure = ComponentStructure {
+        name: component
+            .map(|c| c.name.clone())
+            .unwrap_or_else(|| "Component".to_string()),
+        dimensions,
+        base_classes,
+        disabled_classes,
+        root,
+        observed_attributes: Vec::new(),
+        compound_variants,
+        slots,
+        props,
+    };
+    structure.observed_attributes = observed_attributes(program, &hints.props, &structure);
+
+    Ok(structure)
 }
 
-/// Extract a Record<string, string> from source.
-pub fn extract_record(source: &str, name: &str) -> Result<Vec<(String, String)>, TransformError> {
-    let mut entries = Vec::new();
+/// Classes per value of a variant dimension.
+pub(crate) type Lookup = Vec<(String, String)>;
 
-    for cap in RECORD_RE.captures_iter(source) {
-        let record_name = cap.get(1).unwrap().as_str();
-        if record_name != name {
-            continue;
-        }
+/// `*Classes` bindings that hold plain class strings rather than a lookup.
+const NON_DIMENSION_CLASSES: &[&str] = &["base", "disabled"];
 
-        let content = cap.get(2).unwrap().as_str();
+/// Collect `<dimension>Classes` object literals, such as `variantClasses` or
+/// `toneClasses`, in declaration order.
+fn class_records(program: &Program) -> Result<Vec<(String, Lookup)>, TransformError> {
+    let mut records = Vec::new();
 
-        for entry_cap in ENTRY_RE.captures_iter(content) {
-            let key = entry_cap.get(1).unwrap().as_str().to_string();
-            let value = entry_cap.get(2).unwrap().as_str().to_string();
-            entries.push((key, value));
+    for decl in top_level_declarations(program) {
+        let Declaration::VariableDeclaration(var) = decl else {
+            continue;
+        };
+        for declarator in &var.declarations {
+            let (Some(name), Some(init)) = (
+                declarator.id.get_identifier_name(),
+                declarator.init.as_ref(),
+            ) else {
+                continue;
+            };
+            let Some(dimension) = name.strip_suffix("Classes") else {
+                continue;
+            };
+            if dimension.is_empty()
+                || NON_DIMENSION_CLASSES.contains(&dimension)
+                || !matches!(init.get_inner_expression(), Expression::ObjectExpression(_))
+            {
+                continue;
+            }
+            records.push((dimension.to_string(), static_record(init)?));
         }
     }
 
-    Ok(entries)
+    Ok(records)
+}
+
+/// A component function found at the top level of a module.
+pub(crate) struct ComponentFn<'b, 'a> {
+    /// Component name (e.g., "Button")
+    pub name: String,
+
+    /// Parameters of the render function, if it could be located
+    pub params: Option<&'b FormalParameters<'a>>,
+
+    /// Body of the render function, if it has one
+    pub body: Option<&'b FunctionBody<'a>>,
 }
 
-/// Extract base classes from source.
-pub fn extract_base_classes(source: &str) -> Option<String> {
-    // Try concatenated format first
-    if let Some(cap) = BASE_CLASSES_CONCAT_RE.captures(source) {
-        let raw = cap.get(1).unwrap().as_str();
-        // Parse concatenated strings like "'a' + 'b'"
-        let classes = parse_concatenated_string(raw);
-        if !classes.is_empty() {
-            return Some(classes);
+/// Find the component function: the first top-level function, or `const`
+/// bound to a function-like value, whose name starts with an uppercase letter.
+pub(crate) fn find_component<'b, 'a>(program: &'b Program<'a>) -> Option<ComponentFn<'b, 'a>> {
+    for stmt in &program.body {
+        let decl = match stmt {
+            Statement::ExportNamedDeclaration(export) => export.declaration.as_ref(),
+            Statement::ExportDefaultDeclaration(export) => {
+                if let ExportDefaultDeclarationKind::FunctionDeclaration(func) = &export.declaration
+                {
+                    if let Some(component) = function_component(func) {
+                        return Some(component);
+                    }
+                }
+                None
+            }
+            stmt => stmt.as_declaration(),
+        };
+
+        match decl {
+            Some(Declaration::FunctionDeclaration(func)) => {
+                if let Some(component) = function_component(func) {
+                    return Some(component);
+                }
+            }
+            Some(Declaration::VariableDeclaration(var)) => {
+                for declarator in &var.declarations {
+                    let Some(name) = declarator.id.get_identifier_name() else {
+                        continue;
+                    };
+                    if !is_component_name(&name) {
+                        continue;
+                    }
+                    if let Some((params, body)) = declarator.init.as_ref().and_then(render_function)
+                    {
+                        return Some(ComponentFn {
+                            name: name.to_string(),
+                            params: Some(params),
+                            body,
+                        });
+                    }
+                }
+            }
+            _ => {}
         }
     }
 
-    // Fall back to simple format
-    BASE_CLASSES_SIMPLE_RE
-        .captures(source)
-        .map(|c| c.get(1).unwrap().as_str().to_string())
+    None
+}
+
+fn function_component<'b, 'a>(func: &'b Function<'a>) -> Option<ComponentFn<'b, 'a>> {
+    let name = func.id.as_ref()?.name;
+    is_component_name(&name).then(|| ComponentFn {
+        name: name.to_string(),
+        params: Some(&func.params),
+        body: func.body.as_deref(),
+    })
 }
 
-/// Parse a concatenated string expression like "'a' + 'b' + 'c'".
-fn parse_concatenated_string(raw: &str) -> String {
-    // Match string literals in single or double quotes
-    let string_re = Regex::new(r#"['"]([^'"]*)['""]"#).unwrap();
+fn is_component_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+}
 
-    string_re
-        .captures_iter(raw)
-        .map(|c| c.get(1).unwrap().as_str())
-        .collect::<Vec<_>>()
-        .join(" ")
-        .split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ")
+/// Get the parameters and body of a render function, looking through
+/// wrappers such as `forwardRef(...)` and `memo(...)`.
+fn render_function<'b, 'a>(
+    expr: &'b Expression<'a>,
+) -> Option<(&'b FormalParameters<'a>, Option<&'b FunctionBody<'a>>)> {
+    match expr.get_inner_expression() {
+        Expression::ArrowFunctionExpression(arrow) => Some((&arrow.params, Some(&arrow.body))),
+        Expression::FunctionExpression(func) => Some((&func.params, func.body.as_deref())),
+        Expression::CallExpression(call) => call
+            .arguments
+            .first()
+            .and_then(|arg| arg.as_expression())
+            .and_then(render_function),
+        _ => None,
+    }
 }
 
-/// Extract disabled classes from source.
-pub fn extract_disabled_classes(source: &str) -> Option<String> {
-    DISABLED_CLASSES_RE
-        .captures(source)
-        .map(|c| c.get(1).unwrap().as_str().to_string())
+/// JSX attributes that never become static attributes of the root element.
+const IGNORED_ATTRIBUTES: &[&str] = &[
+    "children",
+    "className",
+    "class",
+    "classList",
+    "style",
+    "key",
+    "ref",
+];
+
+/// Find the element a component renders: the JSX returned from the top
+/// level of its body.
+///
+/// Polymorphic roots such as `const Comp = asChild ? Slot : 'button'` and
+/// primitives such as `<Primitive.button>` resolve to their intrinsic tag.
+/// Roots that are other components or fragments are not resolved.
+fn root_element(body: &FunctionBody) -> Option<RootElement> {
+    let (element, tag) = root_jsx(body)?;
+
+    Some(RootElement {
+        tag,
+        attributes: static_attributes(&element.opening_element.attributes),
+    })
 }
 
-/// Extract observed attributes from props interface or destructuring.
-pub fn extract_attributes(source: &str) -> Vec<String> {
-    let mut attrs = Vec::new();
+/// Find the JSX element a component returns, with its resolved tag name.
+pub(crate) fn root_jsx<'b, 'a>(body: &'b FunctionBody<'a>) -> Option<(&'b JSXElement<'a>, String)> {
+    body.statements.iter().find_map(|stmt| match stmt {
+        Statement::ReturnStatement(ret) => ret
+            .argument
+            .as_ref()
+            .and_then(|expr| returned_jsx(body, expr)),
+        // Concise arrow bodies hold a single expression statement
+        Statement::ExpressionStatement(expr) => returned_jsx(body, &expr.expression),
+        _ => None,
+    })
+}
 
-    // Common button attributes we always look for
-    let common = ["variant", "size", "disabled", "loading"];
-    for attr in common {
-        if source.contains(attr) {
-            attrs.push(attr.to_string());
+fn returned_jsx<'b, 'a>(
+    body: &'b FunctionBody<'a>,
+    expr: &'b Expression<'a>,
+) -> Option<(&'b JSXElement<'a>, String)> {
+    match expr.get_inner_expression() {
+        Expression::JSXElement(element) => {
+            let tag = match &element.opening_element.name {
+                JSXElementName::Identifier(id) => id.name.to_string(),
+                JSXElementName::IdentifierReference(id) => local_tag(body, &id.name)?,
+                // `<Primitive.button>` renders the intrinsic element it names
+                JSXElementName::MemberExpression(member)
+                    if member
+                        .property
+                        .name
+                        .starts_with(|c: char| c.is_ascii_lowercase()) =>
+                {
+                    member.property.name.to_string()
+                }
+                _ => return None,
+            };
+            Some((element, tag))
         }
+        Expression::ConditionalExpression(cond) => {
+            returned_jsx(body, &cond.consequent).or_else(|| returned_jsx(body, &cond.alternate))
+        }
+        _ => None,
     }
+}
 
-    // Try to extract from props interface
-    if let Some(cap) = PROPS_INTERFACE_RE.captures(source) {
-        let content = cap.get(1).unwrap().as_str();
-        for line in content.lines() {
-            let line = line.trim();
-            if let Some(name) = line.split([':', '?']).next() {
-                let name = name.trim();
-                if !name.is_empty()
-                    && !attrs.contains(&name.to_string())
-                    && !name.starts_with("//")
-                    && name != "children"
-                    && name != "className"
-                    && name != "style"
+/// Resolve a local binding such as `const Comp = asChild ? Slot : 'button'`
+/// to the intrinsic tag it may hold.
+fn local_tag(body: &FunctionBody, name: &str) -> Option<String> {
+    let init = body.statements.iter().find_map(|stmt| match stmt {
+        Statement::VariableDeclaration(var) => var
+            .declarations
+            .iter()
+            .find(|d| d.id.get_identifier_name().is_some_and(|n| n == name))
+            .and_then(|d| d.init.as_ref()),
+        _ => None,
+    })?;
+
+    let tag_of = |expr: &Expression| match expr.get_inner_expression() {
+        Expression::StringLiteral(lit) => Some(lit.value.to_string()),
+        _ => None,
+    };
+
+    match init.get_inner_expression() {
+        Expression::ConditionalExpression(cond) => {
+            tag_of(&cond.consequent).or_else(|| tag_of(&cond.alternate))
+        }
+        expr => tag_of(expr),
+    }
+}
+
+/// Collect attributes with static values, e.g. `role="alert"`, `type={'submit'}`
+/// or a bare `disabled`. Dynamic values, spreads and event handlers are skipped.
+fn static_attributes(items: &[JSXAttributeItem]) -> Vec<(String, String)> {
+    let mut attributes = Vec::new();
+
+    for item in items {
+        let JSXAttributeItem::Attribute(attr) = item else {
+            continue;
+        };
+
+        let name = match &attr.name {
+            JSXAttributeName::Identifier(id) => id.name.to_string(),
+            JSXAttributeName::NamespacedName(ns) => ns.to_string(),
+        };
+        // `onClick` in React, also `on:click` in Solid
+        let is_handler = name.starts_with("on:")
+            || (name.starts_with("on") && name[2..].starts_with(char::is_uppercase));
+        if IGNORED_ATTRIBUTES.contains(&name.as_str()) || is_handler {
+            continue;
+        }
+
+        let value = match &attr.value {
+            None => String::new(),
+            Some(JSXAttributeValue::StringLiteral(lit)) => lit.value.to_string(),
+            Some(JSXAttributeValue::ExpressionContainer(container)) => {
+                match container
+                    .expression
+                    .as_expression()
+                    .map(|e| e.get_inner_expression())
                 {
-                    attrs.push(name.to_string());
+                    Some(Expression::StringLiteral(lit)) => lit.value.to_string(),
+                    Some(Expression::NumericLiteral(lit)) => lit.value.to_string(),
+                    Some(Expression::BooleanLiteral(lit)) if lit.value => String::new(),
+                    _ => continue,
                 }
             }
-        }
+            Some(_) => continue,
+        };
+
+        // React prop names that differ from their HTML attribute
+        let name = match name.as_str() {
+            "htmlFor" => "for".to_string(),
+            _ => name,
+        };
+
+        attributes.push((name, value));
     }
 
-    // Try to extract from destructuring pattern
-    if let Some(cap) = DESTRUCTURE_RE.captures(source) {
-        let content = cap.get(1).unwrap().as_str();
-        for part in content.split(',') {
-            let name = part.split(['=', ':']).next().unwrap_or("").trim();
-            if !name.is_empty()
-                && !attrs.contains(&name.to_string())
-                && name != "children"
-                && name != "className"
-                && name != "style"
-                && !name.starts_with("...")
-            {
-                attrs.push(name.to_string());
+    attributes
+}
+
+/// Props that never become observed attributes.
+const IGNORED_PROPS: &[&str] = &["children", "className", "class", "classList", "style"];
+
+/// Collect prop names destructured from the props object, e.g. the first
+/// parameter of a component or Svelte's `let { ... } = $props()`.
+pub(crate) fn destructured_props(pattern: &BindingPattern) -> Vec<String> {
+    match &object_pattern(pattern) {
+        Some(obj) => obj
+            .properties
+            .iter()
+            .filter_map(|prop| property_key_name(&prop.key))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Collect static defaults of destructured props, e.g. `{ variant = 'default' }`.
+pub(crate) fn destructured_defaults(pattern: &BindingPattern) -> Vec<(String, String)> {
+    let Some(obj) = object_pattern(pattern) else {
+        return Vec::new();
+    };
+
+    obj.properties
+        .iter()
+        .filter_map(|prop| {
+            let BindingPatternKind::AssignmentPattern(assign) = &prop.value.kind else {
+                return None;
+            };
+            let value = static_scalar(&assign.right).ok()?;
+            Some((property_key_name(&prop.key)?, value))
+        })
+        .collect()
+}
+
+/// Get the object pattern of a binding, looking through a default value.
+fn object_pattern<'b, 'a>(pattern: &'b BindingPattern<'a>) -> Option<&'b ObjectPattern<'a>> {
+    let kind = match &pattern.kind {
+        BindingPatternKind::AssignmentPattern(assign) => &assign.left.kind,
+        kind => kind,
+    };
+    match kind {
+        BindingPatternKind::ObjectPattern(obj) => Some(obj),
+        _ => None,
+    }
+}
+
+/// Compute observed attributes from the variant dimensions, declared props and
+/// the props the component reads, in that order.
+fn observed_attributes(
+    program: &Program,
+    destructured: &[String],
+    structure: &ComponentStructure,
+) -> Vec<String> {
+    let declared: Vec<String> = declared_props(program)
+        .into_iter()
+        .map(|prop| prop.name)
+        .collect();
+
+    // Every variant dimension is an attribute of the generated element
+    let mut attrs: Vec<String> = structure
+        .dimensions
+        .iter()
+        .map(|dimension| dimension.name.clone())
+        .collect();
+    for slot in &structure.slots {
+        for (dimension, _) in &slot.variants {
+            if !attrs.contains(dimension) {
+                attrs.push(dimension.clone());
             }
         }
     }
 
+    // State attributes the generated element understands when declared
+    for attr in ["disabled", "loading"] {
+        let declared_anywhere = declared.iter().chain(destructured).any(|name| name == attr);
+        if declared_anywhere && !attrs.iter().any(|a| a == attr) {
+            attrs.push(attr.to_string());
+        }
+    }
+
+    for name in declared.into_iter().chain(destructured.iter().cloned()) {
+        if !attrs.contains(&name) && !IGNORED_PROPS.contains(&name.as_str()) {
+            attrs.push(name);
+        }
+    }
+
     attrs
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::traits::TransformContext;
 
     #[test]
     fn extracts_variant_classes() {
@@ -404,6 +990,282 @@ export function Button({ variant, size, disabled, loading }: ButtonProps) {}
         assert!(result.attributes.contains(&"loading".to_string()));
     }
 
+    #[test]
+    fn extracts_arbitrary_dimensions() {
+        let source = r#"
+const toneClasses = { neutral: 'bg-muted', critical: 'bg-destructive' };
+const densityClasses = { compact: 'p-1', comfortable: 'p-4' };
+const baseClasses = 'rounded-md';
+
+export function Alert({ tone, density, title }) {}
+        "#;
+
+        let adapter = ReactAdapter::new();
+        let structure = adapter.extract_structure(source).unwrap();
+
+        let names: Vec<_> = structure.dimensions.iter().map(|d| &d.name).collect();
+        assert_eq!(names, vec!["tone", "density"]);
+        assert_eq!(structure.dimension("density").unwrap().default, "compact");
+        assert_eq!(structure.base_classes, "rounded-md");
+        assert_eq!(
+            structure.observed_attributes,
+            vec!["tone", "density", "title"]
+        );
+
+        let result = adapter
+            .transform(source, "alert-preview", &TransformContext::default())
+            .unwrap();
+        assert!(result.web_component.contains("'tone': {"));
+        assert!(result.classes_used.contains(&"p-4".to_string()));
+    }
+
+    #[test]
+    fn documents_props_with_defaults() {
+        let source = r#"
+import { cva } from 'class-variance-authority';
+
+const buttonVariants = cva('inline-flex', {
+  variants: { variant: { default: 'bg-primary', ghost: '' }, size: { sm: 'h-8', md: 'h-10' } },
+  defaultVariants: { size: 'md' },
+});
+
+interface ButtonProps {
+  /** Visual style. */
+  variant?: 'default' | 'ghost';
+  size?: 'sm' | 'md';
+  label: string;
+}
+
+export function Button({ variant = 'ghost', size, label }: ButtonProps) {}
+        "#;
+
+        let adapter = ReactAdapter::new();
+        let props = adapter.extract_structure(source).unwrap().props;
+
+        let defaults: Vec<_> = props
+            .iter()
+            .map(|p| (p.name.as_str(), p.default.as_deref()))
+            .collect();
+        assert_eq!(
+            defaults,
+            vec![
+                ("variant", Some("ghost")),
+                ("size", Some("md")),
+                ("label", None)
+            ]
+        );
+        assert_eq!(props[0].description.as_deref(), Some("Visual style."));
+        assert!(props[2].required);
+    }
+
+    #[test]
+    fn detects_root_element() {
+        let adapter = ReactAdapter::new();
+
+        let alert = r#"
+const variantClasses = { default: '' };
+export function Alert({ variant, ...props }) {
+  return <div role="alert" aria-live={'polite'} onClick={() => {}} className="p-4" {...props} />;
+}
+        "#;
+        let root = adapter.extract_structure(alert).unwrap().root;
+        assert_eq!(root.tag, "div");
+        assert_eq!(
+            root.attributes,
+            vec![
+                ("role".to_string(), "alert".to_string()),
+                ("aria-live".to_string(), "polite".to_string()),
+            ]
+        );
+
+        let input = r#"
+const variantClasses = { default: '' };
+export const Input = forwardRef((props, ref) => <input type="text" disabled ref={ref} />);
+        "#;
+        let root = adapter.extract_structure(input).unwrap().root;
+        assert_eq!(root.tag, "input");
+        assert!(root.is_void());
+        assert_eq!(root.attributes[1], ("disabled".to_string(), String::new()));
+
+        let link = r#"
+const variantClasses = { default: '' };
+export function Button({ asChild, ...props }) {
+  const Comp = asChild ? Slot : 'a';
+  return <Comp {...props} />;
+}
+        "#;
+        assert_eq!(adapter.extract_structure(link).unwrap().root.tag, "a");
+
+        let primitive = r#"
+const variantClasses = { default: '' };
+export const Button = forwardRef((props, ref) => <Primitive.button type="button" ref={ref} {...props} />);
+        "#;
+        let root = adapter.extract_structure(primitive).unwrap().root;
+        assert_eq!(root.tag, "button");
+        assert_eq!(
+            root.attributes,
+            vec![("type".to_string(), "button".to_string())]
+        );
+
+        let slot = r#"
+const variantClasses = { default: '' };
+export function Button(props) {
+  return <Slot {...props} />;
+}
+        "#;
+        assert_eq!(adapter.extract_structure(slot).unwrap().root.tag, "button");
+
+        let result = adapter
+            .transform(input, "input-preview", &TransformContext::default())
+            .unwrap();
+        assert!(result.web_component.contains("const rootTag = 'input';"));
+    }
+
+    #[test]
+    fn handles_real_world_formatting() {
+        let source = r#"
+import { forwardRef } from 'react';
+
+// Base styles { not a record }
+const baseClasses = `inline-flex items-center
+  justify-center`;
+
+export const variantClasses = {
+  /* the default look */
+  default: 'bg-primary text-primary-foreground',
+  'outline-primary': 'border border-primary ' + 'text-primary',
+} as const satisfies Record<string, string>;
+
+type ButtonProps = React.ButtonHTMLAttributes<HTMLButtonElement> & {
+  variant?: keyof typeof variantClasses;
+  icon?: { name: string };
+};
+
+export const Button = forwardRef<HTMLButtonElement, ButtonProps>(
+  ({ variant = 'default', icon, className, ...props }, ref) => (
+    <button ref={ref} className={`${baseClasses} ${variantClasses[variant]}`} {...props} />
+  ),
+);
+        "#;
+
+        let adapter = ReactAdapter::new();
+        let structure = adapter.extract_structure(source).unwrap();
+
+        assert_eq!(structure.name, "Button");
+        assert_eq!(
+            structure.base_classes,
+            "inline-flex items-center justify-center"
+        );
+        assert_eq!(
+            structure.dimension("variant").unwrap().lookup,
+            vec![
+                (
+                    "default".to_string(),
+                    "bg-primary text-primary-foreground".to_string()
+                ),
+                (
+                    "outline-primary".to_string(),
+                    "border border-primary text-primary".to_string()
+                ),
+            ]
+        );
+        assert_eq!(structure.observed_attributes, vec!["variant", "icon"]);
+    }
+
+    #[test]
+    fn reports_span_of_dynamic_values() {
+        let source = "const variantClasses = { default: cn('a', 'b') };";
+
+        let adapter = ReactAdapter::new();
+        let result = adapter.extract_structure(source);
+
+        let Err(TransformError::Unsupported { span, .. }) = result else {
+            panic!("expected unsupported error, got {result:?}");
+        };
+        assert_eq!(
+            &source[span.start as usize..span.end as usize],
+            "cn('a', 'b')"
+        );
+    }
+
+    #[test]
+    fn extracts_cva_definition() {
+        let source = r#"
+import { cva, type VariantProps } from 'class-variance-authority';
+
+const buttonVariants = cva('inline-flex items-center', {
+  variants: {
+    variant: { default: 'bg-primary', destructive: 'bg-destructive' },
+    size: { sm: 'h-8 px-3', md: 'h-10 px-4' },
+  },
+  compoundVariants: [{ variant: 'destructive', size: 'sm', class: 'font-bold' }],
+  defaultVariants: { variant: 'default', size: 'md' },
+});
+
+export interface ButtonProps extends VariantProps<typeof buttonVariants> {}
+
+export function Button({ variant, size, className }: ButtonProps) {
+  return <button className={buttonVariants({ variant, size, className })} />;
+}
+        "#;
+
+        let adapter = ReactAdapter::new();
+        let structure = adapter.extract_structure(source).unwrap();
+
+        assert_eq!(structure.base_classes, "inline-flex items-center");
+        assert_eq!(structure.dimensions.len(), 2);
+        assert_eq!(structure.dimension("size").unwrap().default, "md");
+        assert_eq!(structure.compound_variants.len(), 1);
+        assert_eq!(structure.compound_variants[0].classes, "font-bold");
+        assert_eq!(structure.observed_attributes, vec!["variant", "size"]);
+
+        let result = adapter
+            .transform(source, "button-preview", &TransformContext::default())
+            .unwrap();
+        assert!(result.classes_used.contains(&"font-bold".to_string()));
+        assert!(result.web_component.contains("compoundVariants"));
+    }
+
+    #[test]
+    fn extracts_tv_slots() {
+        let source = r#"
+import { tv } from 'tailwind-variants';
+
+const card = tv({
+  slots: {
+    base: 'rounded-lg border bg-card',
+    header: 'flex flex-col space-y-1.5 p-6',
+    body: 'p-6 pt-0',
+    footer: 'flex items-center p-6 pt-0',
+  },
+  variants: {
+    shadow: { none: '', md: { base: 'shadow-md' } },
+  },
+  defaultVariants: { shadow: 'md' },
+});
+
+export function Card({ shadow, children }) {
+  const { base, header, body, footer } = card({ shadow });
+  return <div className={base()}>{children}</div>;
+}
+        "#;
+
+        let adapter = ReactAdapter::new();
+        let structure = adapter.extract_structure(source).unwrap();
+
+        assert_eq!(structure.name, "Card");
+        assert_eq!(structure.slots.len(), 4);
+        assert_eq!(structure.dimensions.len(), 1);
+        assert_eq!(structure.dimension("shadow").unwrap().default, "md");
+        assert_eq!(structure.observed_attributes, vec!["shadow"]);
+
+        let result = adapter
+            .transform(source, "card-preview", &TransformContext::default())
+            .unwrap();
+        assert!(result.classes_used.contains(&"shadow-md".to_string()));
+        assert!(result.web_component.contains("name: 'footer',"));
+    }
+
     #[test]
     fn generates_valid_tag_name() {
         let source = r#"
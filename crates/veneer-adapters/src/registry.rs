@@ -3,7 +3,7 @@
 //! Scans a components directory, parses source files, and provides
 //! lookup by component name for generating Web Components.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -11,13 +11,33 @@ use walkdir::WalkDir;
 
 use crate::generator::generate_web_component;
 use crate::react::{ComponentStructure, ReactAdapter};
-use crate::traits::TransformedBlock;
+use crate::solid::SolidAdapter;
+use crate::svelte::SvelteAdapter;
+use crate::traits::{FrameworkAdapter, TransformedBlock};
+use crate::vue::VueAdapter;
+
+/// Get the adapter for a framework name, as configured per components directory.
+pub fn adapter_for(framework: &str) -> Option<Box<dyn FrameworkAdapter>> {
+    match framework {
+        "react" => Some(Box::new(ReactAdapter::new())),
+        "solid" => Some(Box::new(SolidAdapter::new())),
+        "vue" => Some(Box::new(VueAdapter::new())),
+        "svelte" => Some(Box::new(SvelteAdapter::new())),
+        _ => None,
+    }
+}
 
 /// A registry of component definitions.
-#[derive(Debug, Default)]
+///
+/// Holds a set of adapters and picks one per file by its extension, so a
+/// components directory mixing frameworks is scanned in one pass. When two
+/// adapters declare the same extension, the first one wins.
 pub struct ComponentRegistry {
     /// Cached component structures by name (lowercase)
     components: HashMap<String, CachedComponent>,
+
+    /// Adapters in order of preference
+    adapters: Vec<Box<dyn FrameworkAdapter>>,
 }
 
 /// A cached component with its source and structure.
@@ -29,6 +49,9 @@ pub struct CachedComponent {
     /// Source file path
     pub source_path: PathBuf,
 
+    /// Name of the adapter that extracted the structure (e.g., "react", "vue")
+    pub adapter: &'static str,
+
     /// Extracted structure
     pub structure: ComponentStructure,
 
@@ -36,13 +59,63 @@ pub struct CachedComponent {
     pub source: String,
 }
 
+impl Default for ComponentRegistry {
+    fn default() -> Self {
+        Self::with_adapters(vec![
+            Box::new(ReactAdapter::new()),
+            Box::new(VueAdapter::new()),
+            Box::new(SvelteAdapter::new()),
+        ])
+    }
+}
+
+impl std::fmt::Debug for ComponentRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let adapters: Vec<_> = self.adapters.iter().map(|a| a.name()).collect();
+        f.debug_struct("ComponentRegistry")
+            .field("components", &self.components)
+            .field("adapters", &adapters)
+            .finish()
+    }
+}
+
 impl ComponentRegistry {
-    /// Create a new empty registry.
+    /// Create an empty registry with the React, Vue and Svelte adapters.
     pub fn new() -> Self {
         Self::default()
     }
 
-    /// Scan a directory for component files and populate the registry.
+    /// Create an empty registry with the given adapters, in order of preference.
+    pub fn with_adapters(adapters: Vec<Box<dyn FrameworkAdapter>>) -> Self {
+        Self {
+            components: HashMap::new(),
+            adapters,
+        }
+    }
+
+    /// Put `adapter` ahead of the others, so it handles the extensions it
+    /// shares with them (e.g., Solid rather than React for `.tsx`).
+    pub fn prefer(&mut self, adapter: Box<dyn FrameworkAdapter>) {
+        self.adapters.retain(|a| a.name() != adapter.name());
+        self.adapters.insert(0, adapter);
+    }
+
+    /// Get the adapter for a file extension (without the dot).
+    pub fn adapter_for_extension(&self, ext: &str) -> Option<&dyn FrameworkAdapter> {
+        self.adapters
+            .iter()
+            .find(|adapter| adapter.extensions().contains(&ext))
+            .map(|adapter| adapter.as_ref())
+    }
+
+    /// Get the adapter for a component file.
+    pub fn adapter_for_path(&self, path: &Path) -> Option<&dyn FrameworkAdapter> {
+        let ext = path.extension().and_then(|e| e.to_str())?;
+        self.adapter_for_extension(ext)
+    }
+
+    /// Scan a directory of components and populate the registry, extracting
+    /// each file with the adapter for its extension.
     pub fn scan(&mut self, components_dir: &Path) -> Result<usize, RegistryError> {
         if !components_dir.exists() {
             return Err(RegistryError::DirectoryNotFound(
@@ -50,7 +123,6 @@ impl ComponentRegistry {
             ));
         }
 
-        let adapter = ReactAdapter::new();
         let mut count = 0;
 
         for entry in WalkDir::new(components_dir)
@@ -60,19 +132,17 @@ impl ComponentRegistry {
         {
             let path = entry.path();
 
-            // Only process .tsx and .jsx files
-            let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-            if ext != "tsx" && ext != "jsx" {
+            // Only process files an adapter handles
+            let Some(adapter) = self.adapter_for_path(path) else {
                 continue;
-            }
+            };
 
             // Skip test files, stories, and index files
             let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
             if filename.contains(".test.")
                 || filename.contains(".spec.")
                 || filename.contains(".stories.")
-                || filename == "index.tsx"
-                || filename == "index.jsx"
+                || path.file_stem().is_some_and(|stem| stem == "index")
             {
                 continue;
             }
@@ -86,7 +156,7 @@ impl ComponentRegistry {
             // Try to extract structure
             let structure = match adapter.extract_structure(&source) {
                 Ok(s) => s,
-                Err(_) => continue, // Skip files without variantClasses
+                Err(_) => continue, // Skip files without a variant definition
             };
 
             // Use the extracted component name, or derive from filename
@@ -102,6 +172,7 @@ impl ComponentRegistry {
             let cached = CachedComponent {
                 name: name.clone(),
                 source_path: path.to_path_buf(),
+                adapter: adapter.name(),
                 structure,
                 source,
             };
@@ -139,35 +210,7 @@ impl ComponentRegistry {
             .get(component_name)
             .ok_or_else(|| RegistryError::ComponentNotFound(component_name.to_string()))?;
 
-        // Collect all classes used (using HashSet for O(1) deduplication)
-        let mut classes_set: HashSet<String> = HashSet::new();
-
-        // Add base classes
-        for class in cached.structure.base_classes.split_whitespace() {
-            classes_set.insert(class.to_string());
-        }
-
-        // Add variant classes
-        for (_, classes) in &cached.structure.variant_lookup {
-            for class in classes.split_whitespace() {
-                classes_set.insert(class.to_string());
-            }
-        }
-
-        // Add size classes
-        for (_, classes) in &cached.structure.size_lookup {
-            for class in classes.split_whitespace() {
-                classes_set.insert(class.to_string());
-            }
-        }
-
-        // Add disabled classes
-        for class in cached.structure.disabled_classes.split_whitespace() {
-            classes_set.insert(class.to_string());
-        }
-
-        // Convert to Vec for the output
-        let classes_used: Vec<String> = classes_set.into_iter().collect();
+        let classes_used = cached.structure.all_classes();
 
         let web_component = generate_web_component(tag_name, &cached.structure);
 
@@ -257,6 +300,51 @@ export function Button() {}
         assert!(result.web_component.contains("bg-blue-500"));
     }
 
+    #[test]
+    fn scans_mixed_framework_components() {
+        let temp = tempdir().unwrap();
+        let comp_dir = temp.path().join("components");
+        fs::create_dir_all(&comp_dir).unwrap();
+
+        fs::write(
+            comp_dir.join("Badge.svelte"),
+            r#"
+<script>
+  export let tone = 'neutral';
+</script>
+
+<span class:bg-muted={tone === 'neutral'} class:bg-red-600={tone === 'critical'}><slot /></span>
+            "#,
+        )
+        .unwrap();
+        fs::write(
+            comp_dir.join("button.tsx"),
+            "const variantClasses = { a: 'b' };",
+        )
+        .unwrap();
+
+        let mut registry = ComponentRegistry::new();
+        let count = registry.scan(&comp_dir).unwrap();
+
+        assert_eq!(count, 2);
+        assert_eq!(registry.get("Badge").unwrap().adapter, "svelte");
+        assert_eq!(registry.get("button").unwrap().adapter, "react");
+        let result = registry
+            .generate_web_component("Badge", "badge-preview")
+            .unwrap();
+        assert!(result.web_component.contains("bg-red-600"));
+    }
+
+    #[test]
+    fn preferred_adapter_handles_shared_extensions() {
+        let mut registry = ComponentRegistry::new();
+        registry.prefer(adapter_for("solid").unwrap());
+
+        let adapter = registry.adapter_for_path(Path::new("button.tsx")).unwrap();
+        assert_eq!(adapter.name(), "solid");
+        assert!(registry.adapter_for_extension("css").is_none());
+    }
+
     #[test]
     fn skips_test_and_story_files() {
         let temp = tempdir().unwrap();
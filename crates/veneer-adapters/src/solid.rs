@@ -0,0 +1,233 @@
+//! Solid.js adapter for transforming components to Web Components.
+//!
+//! Solid components share JSX and variant definitions with React, but read
+//! their props differently:
+//!
+//! ```tsx
+//! export function Button(props: ButtonProps) {
+//!   const merged = mergeProps({ variant: 'default', size: 'md' }, props);
+//!   const [local, others] = splitProps(merged, ['variant', 'size', 'disabled']);
+//!   return (
+//!     <button
+//!       class="inline-flex items-center"
+//!       classList={{ 'opacity-50': local.disabled, 'bg-primary': local.variant === 'default' }}
+//!       {...others}
+//!     />
+//!   );
+//! }
+//! ```
+//!
+//! `mergeProps` supplies defaults, `splitProps` names the props the component
+//! reads, and `classList` entries become variant lookups.
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{
+    Expression, FunctionBody, JSXAttributeItem, JSXAttributeName, JSXAttributeValue, JSXElement,
+    Statement,
+};
+use oxc_span::SourceType;
+
+use crate::ast::{
+    normalize_classes, object_properties, parse_program, static_scalar, static_string,
+};
+use crate::react::{build_structure, find_component, root_jsx, ComponentHints, ComponentStructure};
+use crate::traits::{FrameworkAdapter, TransformError};
+
+/// Solid.js to Web Component adapter.
+#[derive(Debug, Default)]
+pub struct SolidAdapter;
+
+impl SolidAdapter {
+    /// Create a new Solid adapter.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl FrameworkAdapter for SolidAdapter {
+    fn name(&self) -> &'static str {
+        "solid"
+    }
+
+    fn extensions(&self) -> &[&'static str] {
+        &["tsx", "jsx"]
+    }
+
+    /// Parses the source as TSX and reads the same variant definitions as the
+    /// React adapter, plus `mergeProps` defaults, `splitProps` keys and the
+    /// root element's `class` and `classList` bindings.
+    fn extract_structure(&self, source: &str) -> Result<ComponentStructure, TransformError> {
+        let allocator = Allocator::default();
+        let program = parse_program(&allocator, source, SourceType::tsx())?;
+
+        let component = find_component(&program);
+
+        let mut hints = ComponentHints::default();
+        if let Some(body) = component.as_ref().and_then(|c| c.body) {
+            read_prop_helpers(body, &mut hints);
+            if let Some((element, _)) = root_jsx(body) {
+                read_root_classes(element, &mut hints)?;
+            }
+        }
+
+        build_structure(&program, component.as_ref(), hints)
+    }
+}
+
+/// Read `mergeProps` and `splitProps` calls from the component body.
+fn read_prop_helpers(body: &FunctionBody, hints: &mut ComponentHints) {
+    for stmt in &body.statements {
+        let Statement::VariableDeclaration(var) = stmt else {
+            continue;
+        };
+        for init in var.declarations.iter().filter_map(|d| d.init.as_ref()) {
+            read_props_call(init, hints);
+        }
+    }
+}
+
+fn read_props_call(expr: &Expression, hints: &mut ComponentHints) {
+    let Expression::CallExpression(call) = expr.get_inner_expression() else {
+        return;
+    };
+    let args = call.arguments.iter().filter_map(|arg| arg.as_expression());
+
+    if call.callee.is_specific_id("splitProps") {
+        // splitProps(props, ['variant', 'size'], ['onClick'])
+        for arg in args {
+            match arg.get_inner_expression() {
+                Expression::ArrayExpression(keys) => {
+                    for key in keys.elements.iter().filter_map(|k| k.as_expression()) {
+                        if let Ok(name) = static_string(key) {
+                            add_prop(hints, name);
+                        }
+                    }
+                }
+                other => read_props_call(other, hints),
+            }
+        }
+    } else if call.callee.is_specific_id("mergeProps") {
+        // mergeProps({ variant: 'default' }, props); non-literal defaults are skipped
+        for arg in args {
+            match object_properties(arg) {
+                Ok(entries) => {
+                    for (name, value) in entries {
+                        if let Ok(value) = static_scalar(value) {
+                            hints.defaults.push((name.clone(), value));
+                            add_prop(hints, name);
+                        }
+                    }
+                }
+                Err(_) => read_props_call(arg, hints),
+            }
+        }
+    }
+}
+
+fn add_prop(hints: &mut ComponentHints, name: String) {
+    if !hints.props.contains(&name) {
+        hints.props.push(name);
+    }
+}
+
+/// Read the static `class` and the `classList` of the root element.
+fn read_root_classes(
+    element: &JSXElement,
+    hints: &mut ComponentHints,
+) -> Result<(), TransformError> {
+    for item in &element.opening_element.attributes {
+        let JSXAttributeItem::Attribute(attr) = item else {
+            continue;
+        };
+        let JSXAttributeName::Identifier(name) = &attr.name else {
+            continue;
+        };
+
+        let value = match &attr.value {
+            Some(JSXAttributeValue::StringLiteral(lit)) => {
+                if name.name == "class" {
+                    hints.base_classes = Some(normalize_classes(&lit.value));
+                }
+                continue;
+            }
+            Some(JSXAttributeValue::ExpressionContainer(container)) => {
+                match container.expression.as_expression() {
+                    Some(expr) => expr,
+                    None => continue,
+                }
+            }
+            _ => continue,
+        };
+
+        match name.name.as_str() {
+            "class" => hints.base_classes = static_string(value).ok(),
+            "classList" => {
+                for (classes, condition) in object_properties(value)? {
+                    hints.add_conditional(&classes, condition);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::TransformContext;
+
+    #[test]
+    fn extracts_solid_component() {
+        let source = r#"
+import { mergeProps, splitProps } from 'solid-js';
+
+export function Badge(props: BadgeProps) {
+  const merged = mergeProps({ tone: 'neutral', onClick: () => {} }, props);
+  const [local, others] = splitProps(merged, ['tone', 'disabled', 'class']);
+
+  return (
+    <span
+      class="inline-flex rounded-full"
+      classList={{
+        'bg-muted': local.tone === 'neutral',
+        'bg-destructive text-white': local.tone === 'critical',
+        'opacity-50': local.disabled,
+        ring: props.selected,
+      }}
+      {...others}
+    />
+  );
+}
+        "#;
+
+        let adapter = SolidAdapter::new();
+        let structure = adapter.extract_structure(source).unwrap();
+
+        assert_eq!(structure.name, "Badge");
+        assert_eq!(structure.root.tag, "span");
+        assert_eq!(structure.base_classes, "inline-flex rounded-full");
+        assert_eq!(structure.disabled_classes, "opacity-50");
+
+        let tone = structure.dimension("tone").unwrap();
+        assert_eq!(tone.default, "neutral");
+        assert_eq!(
+            tone.lookup[1],
+            (
+                "critical".to_string(),
+                "bg-destructive text-white".to_string()
+            )
+        );
+        assert_eq!(structure.dimension("selected").unwrap().default, "false");
+        assert_eq!(
+            structure.observed_attributes,
+            vec!["tone", "selected", "disabled"]
+        );
+
+        let result = adapter
+            .transform(source, "badge-preview", &TransformContext::default())
+            .unwrap();
+        assert!(result.classes_used.contains(&"ring".to_string()));
+    }
+}
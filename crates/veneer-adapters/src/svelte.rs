@@ -0,0 +1,304 @@
+//! Svelte component adapter.
+//!
+//! Reads props from the instance `<script>` block, both Svelte 4
+//! `export let` declarations and Svelte 5 `$props()` destructuring, and the
+//! classes of the first element of the markup:
+//!
+//! ```svelte
+//! <script lang="ts">
+//!   let { variant = 'default', disabled = false }: ButtonProps = $props();
+//! </script>
+//!
+//! <button
+//!   class="inline-flex items-center"
+//!   class:bg-primary={variant === 'default'}
+//!   class:opacity-50={disabled}
+//! >
+//!   {@render children?.()}
+//! </button>
+//! ```
+//!
+//! `class:` directives become variant lookups, as `classList` does for Solid.
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{BindingPatternKind, Declaration, Expression, Program, Statement};
+use oxc_span::SourceType;
+
+use crate::ast::{normalize_classes, parse_expression, parse_program, static_scalar};
+use crate::markup::{mask_except, script_source, tags, Tag};
+use crate::props::type_props;
+use crate::react::{
+    build_structure, destructured_defaults, destructured_props, ComponentHints, ComponentStructure,
+    RootElement,
+};
+use crate::traits::{FrameworkAdapter, TransformError};
+
+/// Svelte to Web Component adapter.
+#[derive(Debug, Default)]
+pub struct SvelteAdapter;
+
+impl SvelteAdapter {
+    /// Create a new Svelte adapter.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl FrameworkAdapter for SvelteAdapter {
+    fn name(&self) -> &'static str {
+        "svelte"
+    }
+
+    fn extensions(&self) -> &[&'static str] {
+        &["svelte"]
+    }
+
+    /// Parses the script blocks for `cva(...)` / `tv(...)` definitions or
+    /// `*Classes` Records, `export let` and `$props()` declarations, and
+    /// reads the root element's `class` attribute and `class:` directives.
+    fn extract_structure(&self, source: &str) -> Result<ComponentStructure, TransformError> {
+        // Blank everything outside the scripts so spans match the .svelte file
+        let (script, source_type) = script_source(source);
+
+        let allocator = Allocator::default();
+        let program = parse_program(&allocator, &script, source_type)?;
+
+        let mut hints = ComponentHints::default();
+        read_props(&program, &mut hints);
+
+        // Svelte markup sits next to the scripts; the first element is the root
+        let root = tags(source, 0).find(|tag| {
+            !matches!(tag.name.as_str(), "script" | "style") && !tag.name.starts_with("svelte:")
+        });
+        if let Some(root) = root {
+            read_root(source, &root, &mut hints)?;
+        }
+
+        build_structure(&program, None, hints)
+    }
+}
+
+/// Read props from `export let` declarations and `$props()` destructuring.
+fn read_props(program: &Program, hints: &mut ComponentHints) {
+    for stmt in &program.body {
+        match stmt {
+            // export let variant: Variant = 'default';
+            Statement::ExportNamedDeclaration(export) => {
+                let Some(Declaration::VariableDeclaration(var)) = &export.declaration else {
+                    continue;
+                };
+                for decl in &var.declarations {
+                    let BindingPatternKind::BindingIdentifier(id) = &decl.id.kind else {
+                        continue;
+                    };
+                    let name = id.name.to_string();
+                    if let Some(value) = decl.init.as_ref().and_then(|v| static_scalar(v).ok()) {
+                        hints.defaults.push((name.clone(), value));
+                    }
+                    hints.props.push(name);
+                }
+            }
+            // let { variant = 'default', ...rest }: ButtonProps = $props();
+            Statement::VariableDeclaration(var) => {
+                for decl in &var.declarations {
+                    let is_props = decl.init.as_ref().is_some_and(|init| {
+                        matches!(
+                            init.get_inner_expression(),
+                            Expression::CallExpression(call) if call.callee.is_specific_id("$props")
+                        )
+                    });
+                    if !is_props {
+                        continue;
+                    }
+
+                    hints.props.extend(destructured_props(&decl.id));
+                    hints.defaults.extend(destructured_defaults(&decl.id));
+                    if let Some(annotation) = &decl.id.type_annotation {
+                        hints.add_prop_docs(type_props(program, &annotation.type_annotation));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Root attributes that never become static attributes.
+const IGNORED_ATTRIBUTES: &[&str] = &["class", "style"];
+
+/// Read the tag, static attributes, `class` and `class:` directives of the
+/// root element.
+fn read_root(source: &str, root: &Tag, hints: &mut ComponentHints) -> Result<(), TransformError> {
+    // Components such as <Button> or <Tooltip.Root> are not rendered
+    let intrinsic =
+        root.name.starts_with(|c: char| c.is_ascii_lowercase()) && !root.name.contains(['.', ':']);
+    if intrinsic {
+        let attributes = root
+            .attributes
+            .iter()
+            .filter(|attr| {
+                // Directives (`on:click`, `bind:value`), shorthands and
+                // interpolated values depend on component state
+                !attr.name.contains(':')
+                    && !attr.name.starts_with('{')
+                    && !attr.expression
+                    && !attr.value.as_deref().is_some_and(|v| v.contains('{'))
+                    && !IGNORED_ATTRIBUTES.contains(&attr.name.as_str())
+            })
+            .map(|attr| (attr.name.clone(), attr.value.clone().unwrap_or_default()))
+            .collect();
+        hints.root = Some(RootElement {
+            tag: root.name.clone(),
+            attributes,
+        });
+    }
+
+    for attr in &root.attributes {
+        if attr.name == "class" {
+            match &attr.value {
+                // class={['btn', { active }]} (Svelte 5.16+)
+                Some(_) if attr.expression => {
+                    let binding = mask_except(source, &[attr.value_range()]);
+                    let allocator = Allocator::default();
+                    let expr = parse_expression(&allocator, &binding, SourceType::ts())?;
+                    hints.add_class_binding(&expr)?;
+                }
+                // class="btn {extra}": only the static parts are known
+                Some(value) => {
+                    let classes = normalize_classes(&strip_interpolations(value));
+                    let base = hints.base_classes.get_or_insert_with(String::new);
+                    *base = normalize_classes(&format!("{base} {classes}"));
+                }
+                None => {}
+            }
+        } else if let Some(classes) = attr.name.strip_prefix("class:") {
+            // class:active={isActive}, or the shorthand class:active
+            let range = match attr.value {
+                Some(_) => attr.value_range(),
+                None => attr.value_offset - classes.len()..attr.value_offset,
+            };
+            let condition = mask_except(source, &[range]);
+            let allocator = Allocator::default();
+            let expr = parse_expression(&allocator, &condition, SourceType::ts())?;
+            hints.add_conditional(classes, &expr);
+        }
+    }
+
+    Ok(())
+}
+
+/// Remove `{...}` interpolations from an attribute value.
+fn strip_interpolations(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut depth = 0usize;
+
+    for c in value.chars() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            c if depth == 0 => out.push(c),
+            _ => {}
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::TransformContext;
+
+    #[test]
+    fn extracts_export_let_component() {
+        let source = r#"
+<script context="module">
+  const toneClasses = {
+    neutral: 'bg-muted',
+    critical: 'bg-red-600 text-white',
+  };
+</script>
+
+<script>
+  export let tone = 'neutral';
+  export let disabled = false;
+  export let selected;
+</script>
+
+<span
+  role="status"
+  class="inline-flex rounded-full {toneClasses[tone]}"
+  class:opacity-50={disabled}
+  class:ring={selected}
+  on:click
+>
+  <slot />
+</span>
+
+<style>
+  span { cursor: default; }
+</style>
+        "#;
+
+        let adapter = SvelteAdapter::new();
+        let structure = adapter.extract_structure(source).unwrap();
+
+        assert_eq!(structure.root.tag, "span");
+        assert_eq!(
+            structure.root.attributes,
+            vec![("role".to_string(), "status".to_string())]
+        );
+        assert_eq!(structure.base_classes, "inline-flex rounded-full");
+        assert_eq!(structure.disabled_classes, "opacity-50");
+        assert_eq!(structure.dimension("tone").unwrap().default, "neutral");
+        assert_eq!(structure.dimension("selected").unwrap().default, "false");
+        assert_eq!(
+            structure.observed_attributes,
+            vec!["tone", "selected", "disabled"]
+        );
+
+        let result = adapter
+            .transform(source, "badge-preview", &TransformContext::default())
+            .unwrap();
+        assert!(result.classes_used.contains(&"ring".to_string()));
+    }
+
+    #[test]
+    fn extracts_runes_component() {
+        let source = r#"
+<svelte:options runes />
+
+<script lang="ts">
+  import { cva } from 'class-variance-authority';
+
+  interface ButtonProps {
+    size?: 'sm' | 'lg';
+    loading?: boolean;
+  }
+
+  const buttonVariants = cva('inline-flex', {
+    variants: { size: { sm: 'h-8', lg: 'h-12' } },
+  });
+
+  let { size = 'lg', loading, ...rest }: ButtonProps = $props();
+</script>
+
+<button class={[buttonVariants({ size }), { 'animate-pulse': loading }]} {...rest}>
+  {@render rest.children?.()}
+</button>
+        "#;
+
+        let adapter = SvelteAdapter::new();
+        let structure = adapter.extract_structure(source).unwrap();
+
+        assert_eq!(structure.root.tag, "button");
+        assert_eq!(structure.dimension("size").unwrap().default, "lg");
+        assert_eq!(
+            structure.dimension("loading").unwrap().lookup[1],
+            ("true".to_string(), "animate-pulse".to_string())
+        );
+        assert!(structure
+            .observed_attributes
+            .contains(&"loading".to_string()));
+    }
+}
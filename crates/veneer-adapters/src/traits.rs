@@ -1,6 +1,10 @@
 //! Trait definitions for framework adapters.
 
 use std::collections::HashMap;
+use std::fmt;
+
+use crate::generator::generate_web_component;
+use crate::react::ComponentStructure;
 
 /// Context for transforming a component.
 #[derive(Debug, Clone, Default)]
@@ -25,16 +29,54 @@ pub struct TransformedBlock {
     pub attributes: Vec<String>,
 }
 
+/// Byte range in a component source file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceSpan {
+    /// Start offset (inclusive)
+    pub start: u32,
+    /// End offset (exclusive)
+    pub end: u32,
+}
+
+impl SourceSpan {
+    /// Create a new span.
+    pub fn new(start: u32, end: u32) -> Self {
+        Self { start, end }
+    }
+
+    /// Get the 1-indexed line and column of the span start in `source`.
+    pub fn line_col(&self, source: &str) -> (usize, usize) {
+        let before = &source[..(self.start as usize).min(source.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+        (line, column)
+    }
+}
+
+impl fmt::Display for SourceSpan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
 /// Errors that can occur during transformation.
 #[derive(Debug, thiserror::Error)]
 pub enum TransformError {
     #[error("Parse error: {0}")]
     ParseError(String),
 
+    #[error("Syntax error at {span}: {message}")]
+    Syntax { message: String, span: SourceSpan },
+
+    #[error("Unsupported syntax at {span}: {message}")]
+    Unsupported { message: String, span: SourceSpan },
+
     #[error("Transform error: {0}")]
     TransformError(String),
 
-    #[error("Missing variant classes: component must define variantClasses Record")]
+    #[error(
+        "Missing variant classes: component must define a `*Classes` Record or a cva()/tv() call"
+    )]
     MissingVariants,
 
     #[error("Invalid component structure: {0}")]
@@ -43,12 +85,15 @@ pub enum TransformError {
 
 /// Trait for framework-specific adapters.
 pub trait FrameworkAdapter: Send + Sync {
-    /// Framework identifier (e.g., "react", "solid")
+    /// Framework identifier ("react", "solid", "vue" or "svelte")
     fn name(&self) -> &'static str;
 
     /// File extensions this adapter handles
     fn extensions(&self) -> &[&'static str];
 
+    /// Extract the variant classes, props and root element of a component.
+    fn extract_structure(&self, source: &str) -> Result<ComponentStructure, TransformError>;
+
     /// Transform source component into a Web Component.
     ///
     /// # Arguments
@@ -59,6 +104,20 @@ pub trait FrameworkAdapter: Send + Sync {
         &self,
         source: &str,
         tag_name: &str,
-        ctx: &TransformContext,
-    ) -> Result<TransformedBlock, TransformError>;
+        _ctx: &TransformContext,
+    ) -> Result<TransformedBlock, TransformError> {
+        let structure = self.extract_structure(source)?;
+
+        let classes_used = structure.all_classes();
+
+        // Generate the Web Component
+        let web_component = generate_web_component(tag_name, &structure);
+
+        Ok(TransformedBlock {
+            web_component,
+            tag_name: tag_name.to_string(),
+            classes_used,
+            attributes: structure.observed_attributes,
+        })
+    }
 }
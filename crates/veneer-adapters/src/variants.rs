@@ -0,0 +1,425 @@
+//! Variant definitions from class-variance-authority (`cva`) and
+//! tailwind-variants (`tv`).
+//!
+//! Reads calls such as:
+//!
+//! ```ts
+//! const buttonVariants = cva('inline-flex items-center', {
+//!   variants: { variant: { default: '...', outline: '...' }, size: { sm: '...' } },
+//!   compoundVariants: [{ variant: 'outline', size: 'sm', class: '...' }],
+//!   defaultVariants: { variant: 'default', size: 'sm' },
+//! });
+//!
+//! const card = tv({
+//!   slots: { base: '...', header: '...', body: '...' },
+//!   variants: { shadow: { sm: { base: 'shadow-sm', header: 'pb-2' } } },
+//! });
+//! ```
+//!
+//! Both are normalized into a list of [`ComponentSlot`]s. A `cva` call and a
+//! `tv` call without `slots` produce a single `base` slot.
+
+use oxc_ast::ast::{ArrayExpressionElement, Declaration, Expression, Program};
+use oxc_span::GetSpan;
+
+use crate::ast::{
+    argument_expression, class_list, object_properties, static_scalar, top_level_declarations,
+    unsupported,
+};
+use crate::react::{ComponentSlot, CompoundVariant};
+use crate::traits::TransformError;
+
+/// Name of the slot that receives plain string values.
+const BASE_SLOT: &str = "base";
+
+/// A variant definition read from a `cva(...)` or `tv(...)` call.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct VariantConfig {
+    /// Slots in declaration order; the first one is the root element
+    pub slots: Vec<ComponentSlot>,
+
+    /// Values from `defaultVariants`
+    pub defaults: Vec<(String, String)>,
+
+    /// Whether the definition declared `slots` explicitly
+    pub slotted: bool,
+}
+
+impl VariantConfig {
+    /// Get the root slot.
+    pub fn root(&self) -> Option<&ComponentSlot> {
+        self.slots.first()
+    }
+
+    /// Get the root slot's lookup for a variant dimension.
+    pub fn lookup(&self, dimension: &str) -> Option<&[(String, String)]> {
+        self.root()?.lookup(dimension)
+    }
+
+    /// Get the declared default for a variant dimension.
+    pub fn default_for(&self, dimension: &str) -> Option<&str> {
+        self.defaults
+            .iter()
+            .find(|(name, _)| name == dimension)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Classes for one variant value, split by slot.
+type SlotClasses = Vec<(String, String)>;
+
+/// A compound rule before being split into slots.
+type RawCompound = (Vec<(String, Vec<String>)>, SlotClasses);
+
+/// Options shared by `cva` and `tv`, before being split into slots.
+#[derive(Default)]
+struct RawOptions {
+    base: String,
+    slots: Vec<(String, String)>,
+    variants: Vec<(String, Vec<(String, SlotClasses)>)>,
+    compound_variants: Vec<RawCompound>,
+    defaults: Vec<(String, String)>,
+}
+
+/// Find the first top-level `cva(...)` or `tv(...)` call and read its definition.
+pub(crate) fn find_variant_config(
+    program: &Program,
+) -> Result<Option<VariantConfig>, TransformError> {
+    for expr in top_level_initializers(program) {
+        let Expression::CallExpression(call) = expr.get_inner_expression() else {
+            continue;
+        };
+
+        let mut raw = RawOptions::default();
+
+        if call.callee.is_specific_id("cva") {
+            // cva(base, options)
+            if let Some(base) = call.arguments.first() {
+                let base = argument_expression(base)?;
+                if !is_nullish(base) {
+                    raw.base = class_list(base)?;
+                }
+            }
+            if let Some(options) = call.arguments.get(1) {
+                read_options(argument_expression(options)?, &mut raw)?;
+            }
+        } else if call.callee.is_specific_id("tv") {
+            // tv(options, config)
+            if let Some(options) = call.arguments.first() {
+                read_options(argument_expression(options)?, &mut raw)?;
+            }
+        } else {
+            continue;
+        }
+
+        return Ok(Some(into_config(raw)));
+    }
+
+    Ok(None)
+}
+
+/// Read the `base`, `slots`, `variants`, `compoundVariants` and `defaultVariants` options.
+fn read_options(options: &Expression, raw: &mut RawOptions) -> Result<(), TransformError> {
+    for (key, value) in object_properties(options)? {
+        match key.as_str() {
+            "base" => raw.base = class_list(value)?,
+            "slots" => {
+                for (slot, classes) in object_properties(value)? {
+                    raw.slots.push((slot, class_list(classes)?));
+                }
+            }
+            "variants" => {
+                for (dimension, lookup) in object_properties(value)? {
+                    let entries = object_properties(lookup)?
+                        .into_iter()
+                        .map(|(name, classes)| Ok((name, slot_classes(classes)?)))
+                        .collect::<Result<Vec<_>, TransformError>>()?;
+                    raw.variants.push((dimension, entries));
+                }
+            }
+            "defaultVariants" => {
+                for (dimension, default) in object_properties(value)? {
+                    raw.defaults.push((dimension, static_scalar(default)?));
+                }
+            }
+            "compoundVariants" => {
+                raw.compound_variants = compound_variants(value)?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Read classes that are either a class value for the base slot or an
+/// object of per-slot class values.
+fn slot_classes(expr: &Expression) -> Result<SlotClasses, TransformError> {
+    if is_nullish(expr) {
+        return Ok(Vec::new());
+    }
+
+    match expr.get_inner_expression() {
+        Expression::ObjectExpression(_) => object_properties(expr)?
+            .into_iter()
+            .map(|(slot, classes)| Ok((slot, class_list(classes)?)))
+            .collect(),
+        _ => Ok(vec![(BASE_SLOT.to_string(), class_list(expr)?)]),
+    }
+}
+
+/// Read a `compoundVariants` array.
+fn compound_variants(expr: &Expression) -> Result<Vec<RawCompound>, TransformError> {
+    let Expression::ArrayExpression(arr) = expr.get_inner_expression() else {
+        return Err(unsupported(
+            expr.span(),
+            "expected an array of compound variants",
+        ));
+    };
+
+    let mut rules = Vec::with_capacity(arr.elements.len());
+
+    for element in &arr.elements {
+        let element = match element {
+            ArrayExpressionElement::SpreadElement(spread) => {
+                return Err(unsupported(
+                    spread.span,
+                    "spread elements are not supported",
+                ));
+            }
+            ArrayExpressionElement::Elision(_) => continue,
+            element => element.to_expression(),
+        };
+
+        let mut conditions = Vec::new();
+        let mut classes = Vec::new();
+
+        for (key, value) in object_properties(element)? {
+            match key.as_str() {
+                "class" | "className" => classes = slot_classes(value)?,
+                _ => conditions.push((key, condition_values(value)?)),
+            }
+        }
+
+        rules.push((conditions, classes));
+    }
+
+    Ok(rules)
+}
+
+/// Read a compound condition: a single value or an array of accepted values.
+fn condition_values(expr: &Expression) -> Result<Vec<String>, TransformError> {
+    match expr.get_inner_expression() {
+        Expression::ArrayExpression(arr) => arr
+            .elements
+            .iter()
+            .map(|element| match element {
+                ArrayExpressionElement::SpreadElement(spread) => Err(unsupported(
+                    spread.span,
+                    "spread elements are not supported",
+                )),
+                ArrayExpressionElement::Elision(elision) => Err(unsupported(
+                    elision.span,
+                    "empty array slots are not supported",
+                )),
+                element => static_scalar(element.to_expression()),
+            })
+            .collect(),
+        _ => Ok(vec![static_scalar(expr)?]),
+    }
+}
+
+/// Split raw options into per-slot structures.
+fn into_config(raw: RawOptions) -> VariantConfig {
+    let slotted = !raw.slots.is_empty();
+
+    // The top-level `base` option belongs to the base slot
+    let mut slots: Vec<ComponentSlot> = Vec::new();
+    if !slotted || !raw.base.is_empty() || raw.slots.iter().any(|(name, _)| name == BASE_SLOT) {
+        slots.push(ComponentSlot {
+            name: BASE_SLOT.to_string(),
+            base_classes: raw.base,
+            ..Default::default()
+        });
+    }
+    for (name, classes) in raw.slots {
+        match slots.iter_mut().find(|slot| slot.name == name) {
+            Some(slot) => slot.base_classes = join_classes(&slot.base_classes, &classes),
+            None => slots.push(ComponentSlot {
+                name,
+                base_classes: classes,
+                ..Default::default()
+            }),
+        }
+    }
+
+    for slot in &mut slots {
+        for (dimension, values) in &raw.variants {
+            let lookup: Vec<(String, String)> = values
+                .iter()
+                .map(|(value, classes)| (value.clone(), classes_for(classes, &slot.name)))
+                .collect();
+            slot.variants.push((dimension.clone(), lookup));
+        }
+
+        for (conditions, classes) in &raw.compound_variants {
+            let classes = classes_for(classes, &slot.name);
+            if !classes.is_empty() {
+                slot.compound_variants.push(CompoundVariant {
+                    conditions: conditions.clone(),
+                    classes,
+                });
+            }
+        }
+    }
+
+    VariantConfig {
+        slots,
+        defaults: raw.defaults,
+        slotted,
+    }
+}
+
+/// Get the classes a per-slot value assigns to one slot.
+fn classes_for(classes: &SlotClasses, slot: &str) -> String {
+    classes
+        .iter()
+        .filter(|(name, _)| name == slot)
+        .map(|(_, classes)| classes.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn join_classes(a: &str, b: &str) -> String {
+    format!("{a} {b}").trim().to_string()
+}
+
+/// Iterate over the initializers of top-level variable declarations.
+fn top_level_initializers<'b, 'a>(
+    program: &'b Program<'a>,
+) -> impl Iterator<Item = &'b Expression<'a>> {
+    top_level_declarations(program)
+        .filter_map(|decl| match decl {
+            Declaration::VariableDeclaration(var) => Some(var),
+            _ => None,
+        })
+        .flat_map(|var| var.declarations.iter().filter_map(|d| d.init.as_ref()))
+}
+
+fn is_nullish(expr: &Expression) -> bool {
+    expr.get_inner_expression().is_null_or_undefined()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::parse_program;
+    use oxc_allocator::Allocator;
+    use oxc_span::SourceType;
+
+    fn read(source: &str) -> VariantConfig {
+        let allocator = Allocator::default();
+        let program = parse_program(&allocator, source, SourceType::tsx()).unwrap();
+        find_variant_config(&program).unwrap().unwrap()
+    }
+
+    #[test]
+    fn reads_cva_definition() {
+        let config = read(
+            r#"
+import { cva } from 'class-variance-authority';
+
+export const buttonVariants = cva(['inline-flex', 'items-center'], {
+  variants: {
+    variant: { default: 'bg-primary', outline: ['border', 'bg-transparent'] },
+    size: { sm: 'h-8', lg: 'h-12' },
+    disabled: { true: 'opacity-50', false: null },
+  },
+  compoundVariants: [
+    { variant: ['default', 'outline'], size: 'lg', class: 'uppercase' },
+    { disabled: true, className: 'cursor-not-allowed' },
+  ],
+  defaultVariants: { variant: 'outline', size: 'sm' },
+});
+            "#,
+        );
+
+        let root = config.root().unwrap();
+        assert!(!config.slotted);
+        assert_eq!(root.base_classes, "inline-flex items-center");
+        assert_eq!(
+            config.lookup("variant").unwrap(),
+            &[
+                ("default".to_string(), "bg-primary".to_string()),
+                ("outline".to_string(), "border bg-transparent".to_string()),
+            ]
+        );
+        assert_eq!(config.default_for("variant"), Some("outline"));
+        assert_eq!(root.compound_variants.len(), 2);
+        assert_eq!(
+            root.compound_variants[0].conditions,
+            vec![
+                (
+                    "variant".to_string(),
+                    vec!["default".to_string(), "outline".to_string()]
+                ),
+                ("size".to_string(), vec!["lg".to_string()]),
+            ]
+        );
+        assert_eq!(root.compound_variants[1].classes, "cursor-not-allowed");
+    }
+
+    #[test]
+    fn reads_tv_slots() {
+        let config = read(
+            r#"
+import { tv } from 'tailwind-variants';
+
+const card = tv({
+  slots: {
+    base: 'rounded-lg border',
+    header: 'flex flex-col p-6',
+    body: 'p-6 pt-0',
+    footer: 'flex items-center p-6 pt-0',
+  },
+  variants: {
+    shadow: {
+      none: '',
+      md: { base: 'shadow-md', header: 'border-b' },
+    },
+  },
+  compoundVariants: [{ shadow: 'md', class: { footer: 'border-t' } }],
+  defaultVariants: { shadow: 'none' },
+});
+            "#,
+        );
+
+        assert!(config.slotted);
+        let names: Vec<_> = config.slots.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["base", "header", "body", "footer"]);
+
+        let header = &config.slots[1];
+        assert_eq!(header.base_classes, "flex flex-col p-6");
+        assert_eq!(
+            header.lookup("shadow").unwrap(),
+            &[
+                ("none".to_string(), String::new()),
+                ("md".to_string(), "border-b".to_string()),
+            ]
+        );
+
+        let footer = &config.slots[3];
+        assert_eq!(footer.compound_variants.len(), 1);
+        assert_eq!(footer.compound_variants[0].classes, "border-t");
+        assert!(config.slots[0].compound_variants.is_empty());
+    }
+
+    #[test]
+    fn ignores_files_without_variant_calls() {
+        let allocator = Allocator::default();
+        let program =
+            parse_program(&allocator, "const x = other('a');", SourceType::tsx()).unwrap();
+
+        assert!(find_variant_config(&program).unwrap().is_none());
+    }
+}
@@ -0,0 +1,319 @@
+//! Vue single-file component adapter.
+//!
+//! Reads the `<script setup>` (and plain `<script>`) blocks of a `.vue` file
+//! for variant definitions and props, and the root element of `<template>`
+//! for its tag, static attributes and class bindings:
+//!
+//! ```vue
+//! <script setup lang="ts">
+//! const props = withDefaults(defineProps<{ variant?: string; disabled?: boolean }>(), {
+//!   variant: 'default',
+//! });
+//! </script>
+//!
+//! <template>
+//!   <button class="inline-flex" :class="{ 'bg-primary': variant === 'default', 'opacity-50': disabled }">
+//!     <slot />
+//!   </button>
+//! </template>
+//! ```
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{Expression, Program, Statement};
+use oxc_span::SourceType;
+
+use crate::ast::{
+    normalize_classes, object_properties, parse_expression, parse_program, static_scalar,
+    static_string,
+};
+use crate::markup::{mask_except, script_source, tags, template_block, Tag};
+use crate::props::type_props;
+use crate::react::{build_structure, ComponentHints, ComponentStructure, RootElement};
+use crate::traits::{FrameworkAdapter, TransformError};
+
+/// Vue single-file component to Web Component adapter.
+#[derive(Debug, Default)]
+pub struct VueAdapter;
+
+impl VueAdapter {
+    /// Create a new Vue adapter.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl FrameworkAdapter for VueAdapter {
+    fn name(&self) -> &'static str {
+        "vue"
+    }
+
+    fn extensions(&self) -> &[&'static str] {
+        &["vue"]
+    }
+
+    /// Parses the script blocks for `cva(...)` / `tv(...)` definitions or
+    /// `*Classes` Records, `defineProps` and `withDefaults`, and reads the
+    /// template root's `class` and `:class` bindings.
+    fn extract_structure(&self, source: &str) -> Result<ComponentStructure, TransformError> {
+        // Blank everything outside the scripts so spans match the .vue file
+        let (script, source_type) = script_source(source);
+
+        let allocator = Allocator::default();
+        let program = parse_program(&allocator, &script, source_type)?;
+
+        let mut hints = ComponentHints::default();
+        read_define_props(&program, &mut hints);
+
+        if let Some((_, content)) = template_block(source) {
+            let root = tags(source, content.start)
+                .next()
+                .filter(|tag| tag.end <= content.end);
+            if let Some(root) = root {
+                read_root(source, &root, &mut hints)?;
+            }
+        }
+
+        let mut structure = build_structure(&program, None, hints)?;
+        if let Some(name) = component_name(&program) {
+            structure.name = name;
+        }
+
+        Ok(structure)
+    }
+}
+
+/// Read props and defaults from `defineProps` and `withDefaults` calls.
+fn read_define_props(program: &Program, hints: &mut ComponentHints) {
+    for stmt in &program.body {
+        match stmt {
+            Statement::ExpressionStatement(expr) => {
+                read_props_macro(program, &expr.expression, hints)
+            }
+            Statement::VariableDeclaration(var) => {
+                for init in var.declarations.iter().filter_map(|d| d.init.as_ref()) {
+                    read_props_macro(program, init, hints);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn read_props_macro(program: &Program, expr: &Expression, hints: &mut ComponentHints) {
+    let Expression::CallExpression(call) = expr.get_inner_expression() else {
+        return;
+    };
+    let mut args = call.arguments.iter().filter_map(|arg| arg.as_expression());
+
+    if call.callee.is_specific_id("withDefaults") {
+        // withDefaults(defineProps<Props>(), { variant: 'default' })
+        if let Some(props) = args.next() {
+            read_props_macro(program, props, hints);
+        }
+        if let Some(Ok(defaults)) = args.next().map(object_properties) {
+            for (name, value) in defaults {
+                if let Ok(value) = static_scalar(value) {
+                    hints.defaults.push((name, value));
+                }
+            }
+        }
+    } else if call.callee.is_specific_id("defineProps") {
+        // defineProps<{ variant?: string }>() or defineProps<ButtonProps>()
+        for ty in call.type_arguments.iter().flat_map(|types| &types.params) {
+            hints.add_prop_docs(type_props(program, ty));
+        }
+
+        // defineProps(['variant']) or defineProps({ variant: { type: String, default: 'x' } })
+        match args.next().map(|arg| arg.get_inner_expression()) {
+            Some(Expression::ArrayExpression(names)) => {
+                for name in names.elements.iter().filter_map(|n| n.as_expression()) {
+                    if let Ok(name) = static_string(name) {
+                        hints.props.push(name);
+                    }
+                }
+            }
+            Some(options @ Expression::ObjectExpression(_)) => {
+                for (name, option) in object_properties(options).unwrap_or_default() {
+                    let default = object_properties(option)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .find(|(key, _)| key == "default")
+                        .and_then(|(_, value)| static_scalar(value).ok());
+                    if let Some(default) = default {
+                        hints.defaults.push((name.clone(), default));
+                    }
+                    hints.props.push(name);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Get the component name from `defineOptions({ name: 'Button' })` or an
+/// options object `export default { name: 'Button' }`.
+fn component_name(program: &Program) -> Option<String> {
+    program.body.iter().find_map(|stmt| {
+        let options = match stmt {
+            Statement::ExpressionStatement(expr) => match expr.expression.get_inner_expression() {
+                Expression::CallExpression(call) if call.callee.is_specific_id("defineOptions") => {
+                    call.arguments.first()?.as_expression()?
+                }
+                _ => return None,
+            },
+            Statement::ExportDefaultDeclaration(export) => export.declaration.as_expression()?,
+            _ => return None,
+        };
+
+        object_properties(options)
+            .ok()?
+            .into_iter()
+            .find(|(key, _)| key == "name")
+            .and_then(|(_, value)| static_string(value).ok())
+    })
+}
+
+/// Template attributes that never become static attributes of the root element.
+const IGNORED_ATTRIBUTES: &[&str] = &["class", "style", "key", "ref"];
+
+/// Read the tag, static attributes and class bindings of the template root.
+fn read_root(source: &str, root: &Tag, hints: &mut ComponentHints) -> Result<(), TransformError> {
+    // Components such as <RouterLink> or <component :is> are not rendered
+    let intrinsic = root
+        .name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+    if intrinsic {
+        let attributes = root
+            .attributes
+            .iter()
+            .filter(|attr| {
+                !attr.name.starts_with([':', '@', '#', '.'])
+                    && !attr.name.starts_with("v-")
+                    && !IGNORED_ATTRIBUTES.contains(&attr.name.as_str())
+            })
+            .map(|attr| (attr.name.clone(), attr.value.clone().unwrap_or_default()))
+            .collect();
+        hints.root = Some(RootElement {
+            tag: root.name.clone(),
+            attributes,
+        });
+    }
+
+    for attr in &root.attributes {
+        match attr.name.as_str() {
+            "class" => {
+                if let Some(classes) = &attr.value {
+                    hints.base_classes = Some(normalize_classes(classes));
+                }
+            }
+            ":class" | "v-bind:class" => {
+                let binding = mask_except(source, &[attr.value_range()]);
+                let allocator = Allocator::default();
+                let expr = parse_expression(&allocator, &binding, SourceType::ts())?;
+                hints.add_class_binding(&expr)?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::TransformContext;
+
+    #[test]
+    fn extracts_script_setup_component() {
+        let source = r#"
+<script setup lang="ts">
+import { cva } from 'class-variance-authority';
+
+defineOptions({ name: 'Button' });
+
+const buttonVariants = cva('inline-flex items-center', {
+  variants: {
+    variant: { default: 'bg-primary', outline: 'border' },
+    size: { sm: 'h-8', md: 'h-10' },
+  },
+});
+
+const props = withDefaults(
+  defineProps<{ variant?: 'default' | 'outline'; size?: 'sm' | 'md'; loading?: boolean }>(),
+  { size: 'md' },
+);
+</script>
+
+<template>
+  <!-- root -->
+  <button type="submit" :class="[buttonVariants({ variant, size }), { 'opacity-50': disabled }]" @click="emit('click')">
+    <slot />
+  </button>
+</template>
+        "#;
+
+        let adapter = VueAdapter::new();
+        let structure = adapter.extract_structure(source).unwrap();
+
+        assert_eq!(structure.name, "Button");
+        assert_eq!(structure.root.tag, "button");
+        assert_eq!(
+            structure.root.attributes,
+            vec![("type".to_string(), "submit".to_string())]
+        );
+        assert_eq!(structure.base_classes, "inline-flex items-center");
+        assert_eq!(structure.disabled_classes, "opacity-50");
+        assert_eq!(structure.dimension("size").unwrap().default, "md");
+        assert_eq!(
+            structure.observed_attributes,
+            vec!["variant", "size", "loading"]
+        );
+
+        let result = adapter
+            .transform(source, "button-preview", &TransformContext::default())
+            .unwrap();
+        assert!(result.classes_used.contains(&"h-10".to_string()));
+    }
+
+    #[test]
+    fn reads_runtime_props_and_class_objects() {
+        let source = r#"
+<template>
+  <span class="rounded-full px-2" :class="{ 'bg-muted': tone === 'neutral', 'bg-red-600': props.tone === 'critical' }">
+    <slot />
+  </span>
+</template>
+
+<script setup>
+const props = defineProps({
+  tone: { type: String, default: 'critical' },
+  label: String,
+});
+</script>
+        "#;
+
+        let adapter = VueAdapter::new();
+        let structure = adapter.extract_structure(source).unwrap();
+
+        assert_eq!(structure.name, "Component");
+        assert_eq!(structure.root.tag, "span");
+        assert_eq!(structure.base_classes, "rounded-full px-2");
+        let tone = structure.dimension("tone").unwrap();
+        assert_eq!(tone.default, "critical");
+        assert_eq!(tone.lookup.len(), 2);
+        assert_eq!(structure.observed_attributes, vec!["tone", "label"]);
+    }
+
+    #[test]
+    fn reports_binding_errors_at_file_offsets() {
+        let source = "<template><div :class=\"{ a: \"></div></template>\n<script>const variantClasses = { a: 'b' };</script>";
+
+        let adapter = VueAdapter::new();
+        let Err(TransformError::Syntax { span, .. }) = adapter.extract_structure(source) else {
+            panic!("expected a syntax error");
+        };
+        assert!(span.start as usize > source.find(":class").unwrap());
+    }
+}
@@ -0,0 +1,79 @@
+//! Admonitions: callout containers such as notes and warnings.
+//!
+//! Admonitions are [directives](crate::directive) with a known name. The
+//! label, or the text after the name, is the title:
+//!
+//! ```md
+//! :::warning Breaking change
+//! `size="xs"` was removed in 2.0.
+//! :::
+//! ```
+
+use crate::ast::{Node, Span};
+
+/// Kind of an admonition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdmonitionKind {
+    Note,
+    Tip,
+    Warning,
+    Danger,
+}
+
+impl AdmonitionKind {
+    /// Get the kind for a directive name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "note" => Some(Self::Note),
+            "tip" => Some(Self::Tip),
+            "warning" => Some(Self::Warning),
+            "danger" => Some(Self::Danger),
+            _ => None,
+        }
+    }
+
+    /// Directive name, also used in CSS classes.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Note => "note",
+            Self::Tip => "tip",
+            Self::Warning => "warning",
+            Self::Danger => "danger",
+        }
+    }
+
+    /// Title shown when the admonition has none.
+    pub fn default_title(&self) -> &'static str {
+        match self {
+            Self::Note => "Note",
+            Self::Tip => "Tip",
+            Self::Warning => "Warning",
+            Self::Danger => "Danger",
+        }
+    }
+}
+
+/// A `:::note`, `:::tip`, `:::warning` or `:::danger` container.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Admonition {
+    /// Kind of callout
+    pub kind: AdmonitionKind,
+
+    /// Custom title
+    pub title: Option<String>,
+
+    /// Content of the container
+    pub children: Vec<Node>,
+
+    /// Source span, from the opening to the closing fence
+    pub span: Span,
+}
+
+impl Admonition {
+    /// Custom title, or the default title of the kind.
+    pub fn title(&self) -> &str {
+        self.title
+            .as_deref()
+            .unwrap_or_else(|| self.kind.default_title())
+    }
+}
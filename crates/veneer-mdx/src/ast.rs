@@ -0,0 +1,342 @@
+//! Typed document tree for MDX.
+//!
+//! [`parse_mdx`](crate::parse_mdx) turns a document into block [`Node`]s
+//! holding [`Inline`] content. Blocks carry the [`Span`] of their source, so
+//! later stages can report positions and rewrite the tree instead of
+//! patching the Markdown text.
+
+use std::ops::Range;
+
+use crate::admonition::Admonition;
+use crate::codeblock::CodeBlock;
+use crate::codegroup::CodeGroup;
+use crate::directive::Directive;
+use crate::esm::EsmBlock;
+use crate::jsx::JsxElement;
+
+/// Location of a node in the source file, frontmatter included.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Span {
+    /// Byte offset of the first byte
+    pub start: usize,
+
+    /// Byte offset just past the last byte
+    pub end: usize,
+
+    /// Line of the first byte (1-indexed)
+    pub line: usize,
+
+    /// Column of the first byte, in characters (1-indexed)
+    pub column: usize,
+}
+
+impl Span {
+    /// Locate a byte range of `source`.
+    pub fn locate(source: &str, range: Range<usize>) -> Self {
+        let before = &source[..range.start];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Self {
+            start: range.start,
+            end: range.end,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+
+    /// Byte range of the span.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+/// A block-level node.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    /// `# Title`
+    Heading {
+        level: u8,
+        /// Anchor ID, as used by the table of contents
+        id: String,
+        children: Vec<Inline>,
+        span: Span,
+    },
+
+    /// A paragraph of prose
+    Paragraph { children: Vec<Inline>, span: Span },
+
+    /// A fenced or indented code block
+    Code(CodeBlock),
+
+    /// Code blocks shown as tabs
+    CodeGroup(CodeGroup),
+
+    /// A JSX component element on its own lines
+    Jsx(JsxElement),
+
+    /// A top-level `import` / `export` block
+    Esm(EsmBlock),
+
+    /// A `:::name` container directive
+    Directive(Directive),
+
+    /// A `:::note`, `:::tip`, `:::warning` or `:::danger` callout
+    Admonition(Admonition),
+
+    /// Raw HTML, passed through as is
+    Html { html: String, span: Span },
+
+    /// `> quoted`
+    BlockQuote { children: Vec<Node>, span: Span },
+
+    /// A bullet list, or a numbered list when `start` is set
+    List {
+        start: Option<u64>,
+        /// Items hold their text directly rather than in paragraphs
+        tight: bool,
+        items: Vec<ListItem>,
+        span: Span,
+    },
+
+    /// A GFM table
+    Table {
+        alignments: Vec<Alignment>,
+        head: Vec<Vec<Inline>>,
+        rows: Vec<Vec<Vec<Inline>>>,
+        span: Span,
+    },
+
+    /// `[^label]: text`
+    FootnoteDefinition {
+        label: String,
+        children: Vec<Node>,
+        span: Span,
+    },
+
+    /// `---`
+    ThematicBreak { span: Span },
+}
+
+/// An item of a [`Node::List`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListItem {
+    pub children: Vec<Node>,
+    pub span: Span,
+}
+
+/// Alignment of a table column.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Alignment {
+    #[default]
+    None,
+    Left,
+    Center,
+    Right,
+}
+
+/// Inline content of paragraphs, headings and table cells.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Inline {
+    Text(String),
+    /// `` `code` ``
+    Code(String),
+    /// Raw inline HTML
+    Html(String),
+    /// A JSX component element inside prose
+    Jsx(JsxElement),
+    Emphasis(Vec<Inline>),
+    Strong(Vec<Inline>),
+    Strikethrough(Vec<Inline>),
+    Link {
+        url: String,
+        title: String,
+        children: Vec<Inline>,
+    },
+    Image {
+        url: String,
+        title: String,
+        /// Alt text
+        children: Vec<Inline>,
+    },
+    /// `[^label]`
+    FootnoteReference(String),
+    /// `[x]` at the start of a task list item
+    TaskMarker(bool),
+    SoftBreak,
+    HardBreak,
+}
+
+impl Node {
+    /// Source span of the node.
+    pub fn span(&self) -> Span {
+        match self {
+            Node::Heading { span, .. }
+            | Node::Paragraph { span, .. }
+            | Node::Html { span, .. }
+            | Node::BlockQuote { span, .. }
+            | Node::List { span, .. }
+            | Node::Table { span, .. }
+            | Node::FootnoteDefinition { span, .. }
+            | Node::ThematicBreak { span } => *span,
+            Node::Code(block) => block.span,
+            Node::CodeGroup(group) => group.span,
+            Node::Jsx(element) => element.span,
+            Node::Esm(esm) => esm.span,
+            Node::Directive(directive) => directive.span,
+            Node::Admonition(admonition) => admonition.span,
+        }
+    }
+
+    /// Child block lists of container nodes.
+    fn child_lists(&self) -> Vec<&Vec<Node>> {
+        match self {
+            Node::BlockQuote { children, .. } | Node::FootnoteDefinition { children, .. } => {
+                vec![children]
+            }
+            Node::List { items, .. } => items.iter().map(|item| &item.children).collect(),
+            Node::Jsx(element) => vec![&element.children],
+            Node::Directive(directive) => vec![&directive.children],
+            Node::Admonition(admonition) => vec![&admonition.children],
+            Node::CodeGroup(group) => vec![&group.children],
+            _ => Vec::new(),
+        }
+    }
+
+    pub(crate) fn child_lists_mut(&mut self) -> Vec<&mut Vec<Node>> {
+        match self {
+            Node::BlockQuote { children, .. } | Node::FootnoteDefinition { children, .. } => {
+                vec![children]
+            }
+            Node::List { items, .. } => items.iter_mut().map(|item| &mut item.children).collect(),
+            Node::Jsx(element) => vec![&mut element.children],
+            Node::Directive(directive) => vec![&mut directive.children],
+            Node::Admonition(admonition) => vec![&mut admonition.children],
+            Node::CodeGroup(group) => vec![&mut group.children],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Inline lists held directly by the node.
+    fn inline_lists(&self) -> Vec<&Vec<Inline>> {
+        match self {
+            Node::Heading { children, .. } | Node::Paragraph { children, .. } => vec![children],
+            Node::Table { head, rows, .. } => head.iter().chain(rows.iter().flatten()).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn inline_lists_mut(&mut self) -> Vec<&mut Vec<Inline>> {
+        match self {
+            Node::Heading { children, .. } | Node::Paragraph { children, .. } => vec![children],
+            Node::Table { head, rows, .. } => {
+                head.iter_mut().chain(rows.iter_mut().flatten()).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Inline {
+    fn children(&self) -> Option<&Vec<Inline>> {
+        match self {
+            Inline::Emphasis(children)
+            | Inline::Strong(children)
+            | Inline::Strikethrough(children)
+            | Inline::Link { children, .. }
+            | Inline::Image { children, .. } => Some(children),
+            _ => None,
+        }
+    }
+
+    fn children_mut(&mut self) -> Option<&mut Vec<Inline>> {
+        match self {
+            Inline::Emphasis(children)
+            | Inline::Strong(children)
+            | Inline::Strikethrough(children)
+            | Inline::Link { children, .. }
+            | Inline::Image { children, .. } => Some(children),
+            _ => None,
+        }
+    }
+}
+
+/// Call `f` on every block node, parents before their children.
+pub fn walk<'a>(nodes: &'a [Node], f: &mut impl FnMut(&'a Node)) {
+    for node in nodes {
+        f(node);
+        for children in node.child_lists() {
+            walk(children, f);
+        }
+    }
+}
+
+/// Call `f` on every block node, parents before their children. Nodes may
+/// be replaced; the children of the replacement are visited.
+pub fn walk_mut(nodes: &mut [Node], f: &mut impl FnMut(&mut Node)) {
+    for node in nodes {
+        f(node);
+        for children in node.child_lists_mut() {
+            walk_mut(children, f);
+        }
+    }
+}
+
+/// Call `f` on every inline node in the tree, outer before inner.
+pub fn walk_inlines<'a>(nodes: &'a [Node], f: &mut impl FnMut(&'a Inline)) {
+    fn visit<'a>(inlines: &'a [Inline], f: &mut impl FnMut(&'a Inline)) {
+        for inline in inlines {
+            f(inline);
+            if let Some(children) = inline.children() {
+                visit(children, f);
+            }
+        }
+    }
+
+    for node in nodes {
+        for inlines in node.inline_lists() {
+            visit(inlines, f);
+        }
+        for children in node.child_lists() {
+            walk_inlines(children, f);
+        }
+    }
+}
+
+/// Call `f` on every inline node in the tree, outer before inner. Inlines
+/// may be replaced; the children of the replacement are visited.
+pub fn walk_inlines_mut(nodes: &mut [Node], f: &mut impl FnMut(&mut Inline)) {
+    fn visit(inlines: &mut [Inline], f: &mut impl FnMut(&mut Inline)) {
+        for inline in inlines {
+            f(inline);
+            if let Some(children) = inline.children_mut() {
+                visit(children, f);
+            }
+        }
+    }
+
+    for node in nodes {
+        for inlines in node.inline_lists_mut() {
+            visit(inlines, f);
+        }
+        for children in node.child_lists_mut() {
+            walk_inlines_mut(children, f);
+        }
+    }
+}
+
+/// Plain text of inline content, without markup, HTML or JSX.
+pub fn plain_text(inlines: &[Inline]) -> String {
+    let mut text = String::new();
+    for inline in inlines {
+        match inline {
+            Inline::Text(t) | Inline::Code(t) => text.push_str(t),
+            Inline::SoftBreak | Inline::HardBreak => text.push(' '),
+            Inline::Emphasis(children)
+            | Inline::Strong(children)
+            | Inline::Strikethrough(children)
+            | Inline::Link { children, .. }
+            | Inline::Image { children, .. } => text.push_str(&plain_text(children)),
+            _ => {}
+        }
+    }
+    text
+}
@@ -1,4 +1,19 @@
 //! Code block extraction and parsing.
+//!
+//! The fence info string holds the language, then meta: mode flags,
+//! `{1,3-5}` line ranges to highlight, `title="..."`, `filename="..."` and
+//! `showLineNumbers`, and `group="..."` for [tabbed groups](crate::codegroup):
+//!
+//! ````md
+//! ```tsx preview title="Saving" {2} showLineNumbers
+//! ````
+//!
+//! Lines ending in a `// [!code ++]` or `// [!code --]` comment are marked as
+//! added or removed, and the comment is dropped from the source.
+
+use std::ops::RangeInclusive;
+
+use crate::ast::Span;
 
 /// Programming language of a code block.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -13,6 +28,10 @@ pub enum Language {
     Css,
     Json,
     Bash,
+    Rust,
+    Toml,
+    Yaml,
+    Diff,
     #[default]
     Unknown,
 }
@@ -20,8 +39,7 @@ pub enum Language {
 impl Language {
     /// Parse language from code fence info string.
     pub fn from_info(info: &str) -> Self {
-        let lang = info.split_whitespace().next().unwrap_or("");
-        match lang.to_lowercase().as_str() {
+        match info_language(info).to_lowercase().as_str() {
             "tsx" => Self::Tsx,
             "jsx" => Self::Jsx,
             "ts" | "typescript" => Self::TypeScript,
@@ -32,13 +50,17 @@ impl Language {
             "css" => Self::Css,
             "json" => Self::Json,
             "bash" | "sh" | "shell" => Self::Bash,
+            "rs" | "rust" => Self::Rust,
+            "toml" => Self::Toml,
+            "yaml" | "yml" => Self::Yaml,
+            "diff" | "patch" => Self::Diff,
             _ => Self::Unknown,
         }
     }
 
     /// Check if this language can be transformed to a Web Component.
     pub fn is_transformable(&self) -> bool {
-        matches!(self, Self::Tsx | Self::Jsx)
+        matches!(self, Self::Tsx | Self::Jsx | Self::Vue | Self::Svelte)
     }
 }
 
@@ -59,12 +81,13 @@ pub enum BlockMode {
 impl BlockMode {
     /// Parse mode from code fence info string.
     pub fn from_info(info: &str) -> Self {
-        let lower = info.to_lowercase();
-        if lower.contains("live") {
+        let words = meta_words(info);
+        let has = |flag: &str| words.iter().any(|w| w.eq_ignore_ascii_case(flag));
+        if has("live") {
             Self::Live
-        } else if lower.contains("editable") {
+        } else if has("editable") {
             Self::Editable
-        } else if lower.contains("preview") {
+        } else if has("preview") {
             Self::Preview
         } else {
             Self::Source
@@ -72,6 +95,15 @@ impl BlockMode {
     }
 }
 
+/// A diff marker on a line of a code block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineDiff {
+    /// `// [!code ++]`
+    Added,
+    /// `// [!code --]`
+    Removed,
+}
+
 /// A parsed code block from MDX.
 #[derive(Debug, Clone, PartialEq)]
 pub struct CodeBlock {
@@ -92,6 +124,27 @@ pub struct CodeBlock {
 
     /// Optional filename hint from info string
     pub filename: Option<String>,
+
+    /// Title from `title="..."`
+    pub title: Option<String>,
+
+    /// Lines to highlight, from `{1,3-5}` (1-indexed)
+    pub highlighted_lines: Vec<RangeInclusive<usize>>,
+
+    /// Whether to number lines, from `showLineNumbers`
+    pub line_numbers: bool,
+
+    /// Code group name, from `group="..."`
+    pub group: Option<String>,
+
+    /// Lines marked as added or removed (1-indexed), in order
+    pub diff_lines: Vec<(usize, LineDiff)>,
+
+    /// Fence info string as written (e.g., "tsx live")
+    pub info: String,
+
+    /// Source span, fences included
+    pub span: Span,
 }
 
 impl CodeBlock {
@@ -104,9 +157,76 @@ impl CodeBlock {
             source,
             line_number,
             filename: None,
+            title: None,
+            highlighted_lines: Vec::new(),
+            line_numbers: false,
+            group: None,
+            diff_lines: Vec::new(),
+            info: String::new(),
+            span: Span::default(),
         }
     }
 
+    /// Create a code block from a fence info string and content, reading the
+    /// fence meta and diff markers.
+    pub fn from_fence(info: String, content: &str, line_number: usize) -> Self {
+        let mut source = String::with_capacity(content.len());
+        let mut diff_lines = Vec::new();
+        for (i, line) in content.split_inclusive('\n').enumerate() {
+            let (text, newline) = match line.strip_suffix('\n') {
+                Some(text) => (text, "\n"),
+                None => (line, ""),
+            };
+            match strip_diff_marker(text) {
+                Some((text, diff)) => {
+                    diff_lines.push((i + 1, diff));
+                    source.push_str(text);
+                }
+                None => source.push_str(text),
+            }
+            source.push_str(newline);
+        }
+
+        let mut block = Self::new(
+            Language::from_info(&info),
+            BlockMode::from_info(&info),
+            source,
+            line_number,
+        );
+        block.filename = extract_filename(&info);
+        block.title = meta_value(&info, "title");
+        block.highlighted_lines = meta_words(&info)
+            .iter()
+            .filter_map(|word| word.strip_prefix('{')?.strip_suffix('}'))
+            .flat_map(parse_line_ranges)
+            .collect();
+        block.line_numbers = meta_words(&info).contains(&"showLineNumbers");
+        block.group = meta_value(&info, "group");
+        block.diff_lines = diff_lines;
+        block.info = info;
+        block
+    }
+
+    /// Language name as written in the info string, if any.
+    pub fn lang(&self) -> Option<&str> {
+        Some(info_language(&self.info)).filter(|lang| !lang.is_empty())
+    }
+
+    /// Check if a line (1-indexed) is highlighted.
+    pub fn is_highlighted(&self, line: usize) -> bool {
+        self.highlighted_lines
+            .iter()
+            .any(|range| range.contains(&line))
+    }
+
+    /// Get the diff marker of a line (1-indexed).
+    pub fn line_diff(&self, line: usize) -> Option<LineDiff> {
+        self.diff_lines
+            .iter()
+            .find(|(l, _)| *l == line)
+            .map(|(_, diff)| *diff)
+    }
+
     /// Check if this block should be rendered as a live preview.
     pub fn is_live(&self) -> bool {
         self.mode == BlockMode::Live && self.language.is_transformable()
@@ -119,25 +239,95 @@ impl CodeBlock {
 /// - `tsx filename="Button.tsx"`
 /// - `tsx file=Button.tsx`
 pub fn extract_filename(info: &str) -> Option<String> {
-    // Try filename="..." format
-    if let Some(start) = info.find("filename=\"") {
-        let rest = &info[start + 10..];
-        if let Some(end) = rest.find('"') {
-            return Some(rest[..end].to_string());
-        }
-    }
+    meta_value(info, "filename").or_else(|| meta_value(info, "file"))
+}
 
-    // Try file=... format (without quotes)
-    if let Some(start) = info.find("file=") {
-        let rest = &info[start + 5..];
-        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
-        let filename = rest[..end].trim_matches('"');
-        if !filename.is_empty() {
-            return Some(filename.to_string());
+/// Get the language of an info string: its first word, up to any `{`.
+fn info_language(info: &str) -> &str {
+    info.split(|c: char| c.is_whitespace() || c == '{')
+        .next()
+        .unwrap_or("")
+}
+
+/// Split the meta of an info string into words, keeping quoted values and
+/// `{...}` groups whole. The language is not included.
+fn meta_words(info: &str) -> Vec<&str> {
+    let meta = &info[info_language(info).len()..];
+    let mut words = Vec::new();
+    let mut start = None;
+    let mut quoted = false;
+    let mut braces = false;
+
+    for (i, c) in meta.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '{' if !quoted => braces = true,
+            '}' if !quoted => braces = false,
+            c if c.is_whitespace() && !quoted && !braces => {
+                if let Some(s) = start.take() {
+                    words.push(&meta[s..i]);
+                }
+                continue;
+            }
+            _ => {}
         }
+        start.get_or_insert(i);
+    }
+    if let Some(s) = start {
+        words.push(&meta[s..]);
     }
 
-    None
+    words
+}
+
+/// Get a `key="value"` or `key=value` meta value.
+fn meta_value(info: &str, key: &str) -> Option<String> {
+    meta_words(info).into_iter().find_map(|word| {
+        let value = word.strip_prefix(key)?.strip_prefix('=')?;
+        let value = value.trim_matches('"');
+        (!value.is_empty()).then(|| value.to_string())
+    })
+}
+
+/// Parse `1,3-5` line ranges, skipping invalid entries.
+fn parse_line_ranges(list: &str) -> Vec<RangeInclusive<usize>> {
+    list.split(',')
+        .filter_map(|part| {
+            let part = part.trim();
+            let (start, end) = part.split_once('-').unwrap_or((part, part));
+            let start = start.trim().parse().ok()?;
+            let end = end.trim().parse().ok()?;
+            (start <= end).then_some(start..=end)
+        })
+        .collect()
+}
+
+/// Remove a trailing `[!code ++]` or `[!code --]` comment from a line.
+/// `//`, `#`, `<!-- -->`, `/* */` and `{/* */}` comments are recognized.
+fn strip_diff_marker(line: &str) -> Option<(&str, LineDiff)> {
+    let (start, end, diff) = [
+        ("[!code ++]", LineDiff::Added),
+        ("[!code --]", LineDiff::Removed),
+    ]
+    .into_iter()
+    .find_map(|(marker, diff)| {
+        line.find(marker)
+            .map(|start| (start, start + marker.len(), diff))
+    })?;
+
+    let closer = line[end..].trim();
+    let opener = match closer {
+        "" => ["//", "#"].as_slice(),
+        "-->" => &["<!--"],
+        "*/" => &["/*"],
+        "*/}" => &["{/*"],
+        _ => return None,
+    };
+    let before = line[..start].trim_end();
+    let code = opener
+        .iter()
+        .find_map(|opener| before.strip_suffix(opener))?;
+    Some((code.trim_end(), diff))
 }
 
 #[cfg(test)]
@@ -150,6 +340,8 @@ mod tests {
         assert_eq!(Language::from_info("jsx"), Language::Jsx);
         assert_eq!(Language::from_info("typescript"), Language::TypeScript);
         assert_eq!(Language::from_info("js"), Language::JavaScript);
+        assert_eq!(Language::from_info("rs"), Language::Rust);
+        assert_eq!(Language::from_info("yml"), Language::Yaml);
         assert_eq!(Language::from_info("unknown"), Language::Unknown);
     }
 
@@ -174,6 +366,57 @@ mod tests {
         assert_eq!(extract_filename("tsx live"), None);
     }
 
+    #[test]
+    fn parses_fence_meta() {
+        let block = CodeBlock::from_fence(
+            "tsx{1,3-4} preview title=\"Live {demo}\" showLineNumbers file=App.tsx group=ui"
+                .to_string(),
+            "a\nb\n",
+            7,
+        );
+        assert_eq!(block.lang(), Some("tsx"));
+        assert_eq!(block.language, Language::Tsx);
+        assert_eq!(block.mode, BlockMode::Preview);
+        assert_eq!(block.title.as_deref(), Some("Live {demo}"));
+        assert_eq!(block.filename.as_deref(), Some("App.tsx"));
+        assert_eq!(block.highlighted_lines, vec![1..=1, 3..=4]);
+        assert!(block.is_highlighted(4) && !block.is_highlighted(2));
+        assert!(block.line_numbers);
+        assert_eq!(block.group.as_deref(), Some("ui"));
+
+        let plain =
+            CodeBlock::from_fence("ts title=\"Editable live demo\" {x,5-2}".to_string(), "", 1);
+        assert_eq!(plain.mode, BlockMode::Source);
+        assert!(plain.highlighted_lines.is_empty());
+        assert!(!plain.line_numbers);
+    }
+
+    #[test]
+    fn strips_diff_markers() {
+        let block = CodeBlock::from_fence(
+            "tsx".to_string(),
+            "keep\nconst a = 1; // [!code --]\nconst a = 2; // [!code ++]\n<p /> {/* [!code ++] */}\n\"[!code ++]\"\n",
+            1,
+        );
+        assert_eq!(
+            block.source,
+            "keep\nconst a = 1;\nconst a = 2;\n<p />\n\"[!code ++]\"\n"
+        );
+        assert_eq!(
+            block.diff_lines,
+            vec![
+                (2, LineDiff::Removed),
+                (3, LineDiff::Added),
+                (4, LineDiff::Added)
+            ]
+        );
+        assert_eq!(block.line_diff(3), Some(LineDiff::Added));
+        assert_eq!(block.line_diff(1), None);
+
+        let shell = CodeBlock::from_fence("sh".to_string(), "npm i # [!code ++]", 1);
+        assert_eq!(shell.source, "npm i");
+    }
+
     #[test]
     fn code_block_is_live() {
         let live_tsx = CodeBlock::new(Language::Tsx, BlockMode::Live, "".to_string(), 1);
@@ -184,5 +427,8 @@ mod tests {
 
         let live_html = CodeBlock::new(Language::Html, BlockMode::Live, "".to_string(), 1);
         assert!(!live_html.is_live());
+
+        let live_vue = CodeBlock::new(Language::Vue, BlockMode::Live, "".to_string(), 1);
+        assert!(live_vue.is_live());
     }
 }
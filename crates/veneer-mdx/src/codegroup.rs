@@ -0,0 +1,199 @@
+//! Tabbed groups of code blocks.
+//!
+//! Consecutive code blocks with the same `group` meta form a group, one tab
+//! per block:
+//!
+//! ````md
+//! ```sh group="install" title="npm"
+//! npm install veneer
+//! ```
+//! ```sh group="install" title="pnpm"
+//! pnpm add veneer
+//! ```
+//! ````
+//!
+//! Code blocks wrapped in a `<CodeGroup>` element are grouped too, named by
+//! its `group` attribute if it has one.
+
+use crate::ast::{Node, Span};
+use crate::codeblock::CodeBlock;
+use crate::directive::parse_attributes;
+use crate::jsx::JsxElement;
+
+/// A group of code blocks shown as tabs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodeGroup {
+    /// Group name. Groups with the same name switch tabs together.
+    pub name: Option<String>,
+
+    /// Tab label of each child
+    pub labels: Vec<String>,
+
+    /// One code block per tab. Renderers may replace them, as with live
+    /// previews.
+    pub children: Vec<Node>,
+
+    /// Source span, from the first block to the last
+    pub span: Span,
+}
+
+impl CodeGroup {
+    fn new(name: Option<String>, blocks: Vec<Node>, span: Span) -> Self {
+        let labels = blocks
+            .iter()
+            .filter_map(|node| match node {
+                Node::Code(block) => Some(tab_label(block)),
+                _ => None,
+            })
+            .collect();
+        Self {
+            name,
+            labels,
+            children: blocks,
+            span,
+        }
+    }
+}
+
+/// Tab label of a code block: its title, filename or language.
+fn tab_label(block: &CodeBlock) -> String {
+    block
+        .title
+        .as_deref()
+        .or(block.filename.as_deref())
+        .or(block.lang())
+        .unwrap_or("Code")
+        .to_string()
+}
+
+/// Replace `<CodeGroup>` elements and runs of code blocks sharing a `group`
+/// with [`CodeGroup`] nodes, throughout the tree.
+pub(crate) fn group_code_blocks(nodes: &mut Vec<Node>) {
+    let mut i = 0;
+    while i < nodes.len() {
+        if let Node::Jsx(element) = &nodes[i] {
+            if let Some(group) = element_group(element) {
+                nodes[i] = Node::CodeGroup(group);
+            }
+        }
+
+        let name = match &nodes[i] {
+            Node::Code(block) => block.group.clone(),
+            _ => None,
+        };
+        if let Some(name) = name {
+            let len = nodes[i..]
+                .iter()
+                .take_while(
+                    |node| matches!(node, Node::Code(block) if block.group.as_ref() == Some(&name)),
+                )
+                .count();
+            let blocks: Vec<Node> = nodes.drain(i..i + len).collect();
+            let span = group_span(&blocks);
+            nodes.insert(i, Node::CodeGroup(CodeGroup::new(Some(name), blocks, span)));
+        }
+
+        if !matches!(nodes[i], Node::CodeGroup(_)) {
+            for children in nodes[i].child_lists_mut() {
+                group_code_blocks(children);
+            }
+        }
+        i += 1;
+    }
+}
+
+/// Get the group of a `<CodeGroup>` element holding only code blocks.
+fn element_group(element: &JsxElement) -> Option<CodeGroup> {
+    let only_code = element
+        .children
+        .iter()
+        .all(|node| matches!(node, Node::Code(_)));
+    if element.name != "CodeGroup" || element.children.is_empty() || !only_code {
+        return None;
+    }
+
+    let attributes = element
+        .opening_tag()
+        .strip_prefix("<CodeGroup")?
+        .trim_end_matches('>')
+        .trim_end_matches('/');
+    let name = parse_attributes(attributes)
+        .into_iter()
+        .find(|(key, _)| key == "group")
+        .map(|(_, value)| value)
+        .or_else(|| match &element.children[0] {
+            Node::Code(block) => block.group.clone(),
+            _ => None,
+        });
+
+    Some(CodeGroup::new(name, element.children.clone(), element.span))
+}
+
+/// Span covering a run of blocks.
+fn group_span(blocks: &[Node]) -> Span {
+    let first = blocks[0].span();
+    let last = blocks[blocks.len() - 1].span();
+    Span {
+        end: last.end,
+        ..first
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse_mdx;
+    use crate::Node;
+
+    #[test]
+    fn groups_code_blocks() {
+        let doc = parse_mdx(
+            r#"```sh group="install" title="npm"
+npm i veneer
+```
+```sh group="install" title="pnpm"
+pnpm add veneer
+```
+
+```sh group="other"
+yarn
+```
+
+<CodeGroup group="lang">
+
+```tsx title="Button.tsx"
+<Button />
+```
+
+```jsx
+<Button />
+```
+
+</CodeGroup>
+"#,
+        )
+        .unwrap();
+
+        let groups: Vec<_> = doc
+            .nodes
+            .iter()
+            .filter_map(|node| match node {
+                Node::CodeGroup(group) => Some(group),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(groups.len(), 3);
+
+        assert_eq!(groups[0].name.as_deref(), Some("install"));
+        assert_eq!(groups[0].labels, vec!["npm", "pnpm"]);
+        assert_eq!(groups[0].children.len(), 2);
+        assert_eq!(groups[0].span.line, 1);
+
+        assert_eq!(groups[1].labels, vec!["sh"]);
+
+        assert_eq!(groups[2].name.as_deref(), Some("lang"));
+        assert_eq!(groups[2].labels, vec!["Button.tsx", "jsx"]);
+
+        // Grouped blocks are still found for live previews
+        assert_eq!(doc.code_blocks.len(), 5);
+    }
+}
@@ -0,0 +1,153 @@
+//! Diagnostics with code frames.
+//!
+//! A [`Diagnostic`] points at a span of a source file and renders as a code
+//! frame, for the terminal or the dev server error overlay:
+//!
+//! ```text
+//! Unclosed element <Button>
+//!  --> docs/button.mdx:7:1
+//!   |
+//! 6 |
+//! 7 | <Button variant="outline">
+//!   | ^^^^^^^^^^^^^^^^^^^^^^^^^^
+//! 8 |   Outline
+//!   |
+//!   = help: close it with </Button>, or end the tag with />
+//! ```
+
+use std::fmt;
+
+use crate::ast::Span;
+use crate::render::escape_html;
+
+/// A problem at a position in a source file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// What went wrong
+    pub message: String,
+
+    /// Where it went wrong
+    pub span: Span,
+
+    /// How to fix it
+    pub help: Option<String>,
+}
+
+impl Diagnostic {
+    /// Create a diagnostic without help text.
+    pub fn new(message: impl Into<String>, span: Span) -> Self {
+        Self {
+            message: message.into(),
+            span,
+            help: None,
+        }
+    }
+
+    /// Add help text.
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Render the diagnostic as a code frame of `source`, the file at `path`.
+    pub fn render(&self, path: &str, source: &str) -> String {
+        let lines: Vec<&str> = source
+            .split('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .collect();
+        let line = self.span.line.clamp(1, lines.len());
+        let first = line.saturating_sub(1).max(1);
+        let last = (line + 1).min(lines.len());
+        let width = last.to_string().len();
+        let gutter = " ".repeat(width);
+
+        let mut out = format!("{}\n", self.message);
+        out.push_str(&format!(
+            "{gutter}--> {path}:{}:{}\n",
+            self.span.line, self.span.column
+        ));
+        out.push_str(&format!("{gutter} |\n"));
+
+        for number in first..=last {
+            let text = lines[number - 1];
+            out.push_str(format!("{number:>width$} | {text}").trim_end());
+            out.push('\n');
+
+            if number == line {
+                let indent: String = text
+                    .chars()
+                    .take(self.span.column.saturating_sub(1))
+                    .map(|c| if c == '\t' { '\t' } else { ' ' })
+                    .collect();
+                let rest = text.chars().count().saturating_sub(indent.chars().count());
+                let marked = source
+                    .get(self.span.start..self.span.end)
+                    .map_or(0, |text| text.chars().take_while(|&c| c != '\n').count());
+                let carets = "^".repeat(marked.min(rest).max(1));
+                out.push_str(&format!("{gutter} | {indent}{carets}\n"));
+            }
+        }
+
+        if let Some(help) = &self.help {
+            out.push_str(&format!("{gutter} |\n{gutter} = help: {help}\n"));
+        }
+
+        out
+    }
+
+    /// Render the code frame as HTML, for the dev server error overlay.
+    pub fn render_html(&self, path: &str, source: &str) -> String {
+        format!(
+            r#"<pre class="veneer-diagnostic">{}</pre>"#,
+            escape_html(&self.render(path, source))
+        )
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message, self.span.line, self.span.column
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_code_frames() {
+        let source = "# Title\n\n<Button variant=\"outline\">\n  Outline\n";
+        let start = source.find("<Button").unwrap();
+        let end = start + source[start..].find('>').unwrap() + 1;
+        let diagnostic = Diagnostic::new(
+            "Unclosed element <Button>",
+            Span::locate(source, start..end),
+        )
+        .with_help("close it with </Button>, or end the tag with />");
+
+        assert_eq!(
+            diagnostic.render("docs/button.mdx", source),
+            r#"Unclosed element <Button>
+ --> docs/button.mdx:3:1
+  |
+2 |
+3 | <Button variant="outline">
+  | ^^^^^^^^^^^^^^^^^^^^^^^^^^
+4 |   Outline
+  |
+  = help: close it with </Button>, or end the tag with />
+"#
+        );
+        assert_eq!(
+            diagnostic.to_string(),
+            "Unclosed element <Button> at line 3, column 1"
+        );
+        assert!(diagnostic
+            .render_html("docs/button.mdx", source)
+            .contains("3 | &lt;Button variant=&quot;outline&quot;&gt;"));
+    }
+}
@@ -0,0 +1,237 @@
+//! Container directives.
+//!
+//! A directive wraps Markdown in a named container, with an optional label
+//! and attributes:
+//!
+//! ```md
+//! :::note[Heads up]{#install .wide}
+//! Run `veneer init` first.
+//! :::
+//! ```
+//!
+//! Text after the name also works as the label (`:::warning Breaking
+//! change`). Directives nest by using longer fences for the outer container,
+//! and [admonitions](crate::admonition) are directives with a known name.
+
+use std::ops::Range;
+
+use crate::ast::{Node, Span};
+
+/// A `:::name` container directive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Directive {
+    /// Directive name (e.g., "note")
+    pub name: String,
+
+    /// Text in brackets, or plain text, after the name
+    pub label: Option<String>,
+
+    /// Attributes in braces after the name, in source order. `#id` and
+    /// `.class` shorthands are stored as `id` and `class`.
+    pub attributes: Vec<(String, String)>,
+
+    /// Content of the container
+    pub children: Vec<Node>,
+
+    /// Source span, from the opening to the closing fence
+    pub span: Span,
+}
+
+impl Directive {
+    /// Get an attribute value by name.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// An opening directive fence.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Fence {
+    pub colons: usize,
+    pub name: String,
+    pub label: Option<String>,
+    pub attributes: Vec<(String, String)>,
+}
+
+/// Parse an opening fence such as `:::note[Title]{.wide}`.
+pub(crate) fn directive_fence(line: &str) -> Option<Fence> {
+    let line = line.trim_end();
+    let colons = line.len() - line.trim_start_matches(':').len();
+    if colons < 3 {
+        return None;
+    }
+
+    let rest = &line[colons..];
+    let name_end = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+        .unwrap_or(rest.len());
+    let name = &rest[..name_end];
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let mut rest = &rest[name_end..];
+    let mut label = None;
+    if let Some(inner) = rest.strip_prefix('[') {
+        let end = inner.find(']')?;
+        label = Some(inner[..end].trim().to_string());
+        rest = &inner[end + 1..];
+    } else if rest.starts_with(char::is_whitespace) {
+        // Text after the name, as in `:::warning Breaking change`
+        let end = rest.find('{').unwrap_or(rest.len());
+        label = Some(rest[..end].trim().to_string()).filter(|l| !l.is_empty());
+        rest = &rest[end..];
+    }
+
+    let mut attributes = Vec::new();
+    if let Some(inner) = rest.strip_prefix('{') {
+        let end = inner.rfind('}')?;
+        attributes = parse_attributes(&inner[..end]);
+        rest = &inner[end + 1..];
+    }
+
+    if !rest.trim().is_empty() {
+        return None;
+    }
+
+    Some(Fence {
+        colons,
+        name: name.to_string(),
+        label,
+        attributes,
+    })
+}
+
+/// Find the content and end (exclusive) of a directive whose opening fence
+/// line ends at `content_start`. Fenced code is skipped, and an unclosed
+/// directive runs to `limit`.
+pub(crate) fn directive_end(
+    source: &str,
+    content_start: usize,
+    colons: usize,
+    limit: usize,
+) -> (Range<usize>, usize) {
+    let mut depth = 0;
+    let mut code_fence: Option<&str> = None;
+    let mut pos = content_start;
+
+    while pos < limit {
+        let line_end = source[pos..limit].find('\n').map_or(limit, |i| pos + i + 1);
+        let line = source[pos..line_end].trim();
+
+        if let Some(fence) = code_fence {
+            if line.starts_with(fence) {
+                code_fence = None;
+            }
+        } else if line.starts_with("```") || line.starts_with("~~~") {
+            code_fence = Some(&line[..3]);
+        } else if !line.is_empty() && line.chars().all(|c| c == ':') && line.len() >= colons {
+            if depth == 0 {
+                return (content_start..pos, line_end);
+            }
+            depth -= 1;
+        } else if directive_fence(line).is_some_and(|fence| fence.colons == colons) {
+            depth += 1;
+        }
+
+        pos = line_end;
+    }
+
+    (content_start..limit, limit)
+}
+
+/// Parse `#id .class key="value" key=value flag` attributes.
+pub(crate) fn parse_attributes(source: &str) -> Vec<(String, String)> {
+    let mut attributes: Vec<(String, String)> = Vec::new();
+    let mut rest = source.trim_start();
+
+    while !rest.is_empty() {
+        let token_end = rest
+            .find(|c: char| c.is_whitespace() || c == '=')
+            .unwrap_or(rest.len());
+        let key = &rest[..token_end];
+        rest = &rest[token_end..];
+
+        let value = if let Some(after) = rest.strip_prefix('=') {
+            if let Some(quoted) = after.strip_prefix('"') {
+                let end = quoted.find('"').unwrap_or(quoted.len());
+                rest = quoted.get(end + 1..).unwrap_or("");
+                quoted[..end].to_string()
+            } else {
+                let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                rest = &after[end..];
+                after[..end].to_string()
+            }
+        } else {
+            String::new()
+        };
+
+        if let Some(id) = key.strip_prefix('#') {
+            attributes.push(("id".to_string(), id.to_string()));
+        } else if let Some(class) = key.strip_prefix('.') {
+            match attributes.iter_mut().find(|(k, _)| k == "class") {
+                Some((_, classes)) => {
+                    classes.push(' ');
+                    classes.push_str(class);
+                }
+                None => attributes.push(("class".to_string(), class.to_string())),
+            }
+        } else if !key.is_empty() {
+            attributes.push((key.to_string(), value));
+        }
+
+        rest = rest.trim_start();
+    }
+
+    attributes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fences() {
+        let fence = directive_fence(":::note[Heads up]{#install .a .b open kind=\"x y\"}").unwrap();
+        assert_eq!(fence.colons, 3);
+        assert_eq!(fence.name, "note");
+        assert_eq!(fence.label.as_deref(), Some("Heads up"));
+        assert_eq!(
+            fence.attributes,
+            vec![
+                ("id".to_string(), "install".to_string()),
+                ("class".to_string(), "a b".to_string()),
+                ("open".to_string(), String::new()),
+                ("kind".to_string(), "x y".to_string()),
+            ]
+        );
+
+        assert_eq!(directive_fence("::::tip").unwrap().colons, 4);
+        assert!(directive_fence(":::").is_none());
+        assert!(directive_fence("::note").is_none());
+        assert_eq!(
+            directive_fence(":::warning Breaking change {.wide}")
+                .unwrap()
+                .label
+                .as_deref(),
+            Some("Breaking change")
+        );
+        assert!(directive_fence(":::note[unclosed").is_none());
+    }
+
+    #[test]
+    fn finds_directive_ends() {
+        let source = ":::note\nA\n```md\n:::\n```\n:::tip\nB\n:::\n:::\nafter";
+        let start = source.find('\n').unwrap() + 1;
+        let (content, end) = directive_end(source, start, 3, source.len());
+        assert_eq!(&source[content], "A\n```md\n:::\n```\n:::tip\nB\n:::\n");
+        assert_eq!(&source[end..], "after");
+
+        let (content, end) = directive_end(source, start, 4, source.len());
+        assert_eq!(content.end, source.len());
+        assert_eq!(end, source.len());
+    }
+}
@@ -0,0 +1,221 @@
+//! ESM `import` and `export` blocks in MDX.
+
+use std::ops::Range;
+
+use oxc_allocator::Allocator;
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+use crate::ast::Span;
+
+/// Kind of an ESM block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EsmKind {
+    /// `import { Button } from './button'`
+    Import,
+    /// `export const meta = { ... }`
+    Export,
+}
+
+/// A top-level ESM block. It is code for the page, not prose, so it is not
+/// rendered.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EsmBlock {
+    /// Import or export
+    pub kind: EsmKind,
+
+    /// Block source, possibly spanning several statements
+    pub source: String,
+
+    /// Source span
+    pub span: Span,
+}
+
+impl EsmBlock {
+    /// Names bound by the import statements of the block, e.g. `Card` and
+    /// `Btn` for `import Card, { Button as Btn } from '...'`.
+    pub fn imported_names(&self) -> Vec<String> {
+        let mut names = Vec::new();
+
+        for statement in import_statements(&self.source) {
+            let Some(clause) = import_clause(statement) else {
+                continue;
+            };
+
+            let clause = clause.replace(['{', '}'], ",");
+            for part in clause.split(',') {
+                let part = part.trim().trim_start_matches("type ");
+                let name = match part.rsplit_once(" as ") {
+                    Some((_, alias)) => alias.trim(),
+                    None => part,
+                };
+                if !name.is_empty() && name != "*" && name != "type" {
+                    names.push(name.to_string());
+                }
+            }
+        }
+
+        names
+    }
+}
+
+/// Import statements of an ESM source, after their `import` keyword. A
+/// statement starts with the keyword at the start of a line or after a `;`.
+fn import_statements(source: &str) -> Vec<&str> {
+    let starts: Vec<usize> = source
+        .match_indices("import")
+        .map(|(i, _)| i)
+        .filter(|&i| {
+            let before = source[..i].trim_end_matches([' ', '\t']);
+            let at_boundary = before.is_empty() || before.ends_with(['\n', ';']);
+            at_boundary && !source[i + 6..].starts_with(is_identifier_char)
+        })
+        .collect();
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(n, &start)| &source[start + 6..starts.get(n + 1).copied().unwrap_or(source.len())])
+        .collect()
+}
+
+/// The bindings of an import statement, before its `from` keyword. Side-effect
+/// imports (`import './styles.css'`) have none.
+fn import_clause(statement: &str) -> Option<&str> {
+    statement
+        .match_indices("from")
+        .find(|(i, _)| {
+            let keyword = !statement[..*i].ends_with(is_identifier_char)
+                && !statement[i + 4..].starts_with(is_identifier_char);
+            keyword && statement[i + 4..].trim_start().starts_with(['\'', '"'])
+        })
+        .map(|(i, _)| &statement[..i])
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '$')
+}
+
+/// Get the kind of ESM block a paragraph starts, if it has the shape of
+/// one: `import ... from '...'`, `import '...'`, or `export` followed by a
+/// declaration, `default` or a `{ ... }` list. Prose such as "import settings
+/// are described below" stays a paragraph.
+pub(crate) fn esm_kind(paragraph: &str) -> Option<EsmKind> {
+    let after = |keyword: &str| {
+        paragraph
+            .strip_prefix(keyword)
+            .filter(|rest| {
+                rest.starts_with(|c: char| c.is_whitespace() || matches!(c, '{' | '*' | '\'' | '"'))
+            })
+            .map(str::trim_start)
+    };
+
+    if let Some(rest) = after("import") {
+        let side_effect = rest.starts_with(['\'', '"']);
+        return (side_effect || import_clause(rest).is_some()).then_some(EsmKind::Import);
+    }
+
+    let rest = after("export")?;
+    let word: String = rest
+        .chars()
+        .take_while(|c| is_identifier_char(*c))
+        .collect();
+    let declaration = matches!(
+        word.as_str(),
+        "const"
+            | "let"
+            | "var"
+            | "function"
+            | "async"
+            | "class"
+            | "default"
+            | "type"
+            | "interface"
+            | "enum"
+    );
+    (declaration || rest.starts_with(['{', '*'])).then_some(EsmKind::Export)
+}
+
+/// End of an ESM block starting at `start` whose first paragraph ends at
+/// `end`. Blocks with unclosed brackets, such as an object with a blank line
+/// in it, take in the following paragraphs until they are closed or `limit`
+/// is reached.
+pub(crate) fn esm_end(source: &str, start: usize, mut end: usize, limit: usize) -> usize {
+    while end < limit && open_brackets(&source[start..end]) > 0 {
+        let rest = &source[end..limit];
+        let content = end + rest.len() - rest.trim_start().len();
+        end = source[content..limit]
+            .find("\n\n")
+            .map_or(limit, |i| content + i + 1);
+    }
+    end
+}
+
+/// Brackets opened and not closed in `code`, outside string literals.
+fn open_brackets(code: &str) -> i32 {
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for c in code.chars() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' | '`' => quote = Some(c),
+                '{' | '(' | '[' => depth += 1,
+                '}' | ')' | ']' => depth -= 1,
+                _ => {}
+            },
+        }
+    }
+    depth
+}
+
+/// Check that an ESM block parses as a module. Returns the first error and
+/// its byte range in `source`.
+pub(crate) fn check_module(source: &str) -> Result<(), (String, Range<usize>)> {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source, SourceType::tsx()).parse();
+
+    match ret.errors.first() {
+        Some(error) => {
+            let range = error
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.first())
+                .map_or(0..source.len(), |l| l.offset()..l.offset() + l.len());
+            Err((error.message.to_string(), range))
+        }
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_imported_names() {
+        let block = EsmBlock {
+            kind: EsmKind::Import,
+            source: "import Card, { Button as Btn, type Size } from './ui';\nimport * as Icons from 'icons';\nimport './styles.css';".to_string(),
+            span: Span::default(),
+        };
+
+        assert_eq!(block.imported_names(), vec!["Card", "Btn", "Size", "Icons"]);
+
+        let block = EsmBlock {
+            kind: EsmKind::Import,
+            source: "import { importantThing } from \"./important\"\nimport fromage, {\n  Reimport as R,\n} from './cheese';\nimport type { Props } from './types'".to_string(),
+            span: Span::default(),
+        };
+        assert_eq!(
+            block.imported_names(),
+            vec!["importantThing", "fromage", "R", "Props"]
+        );
+        assert_eq!(esm_kind("export const meta = {}"), Some(EsmKind::Export));
+        assert_eq!(esm_kind("important things"), None);
+    }
+}
@@ -1,6 +1,14 @@
 //! Frontmatter extraction and parsing.
 
-use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
+
+use serde::de::{Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize};
+
+use crate::ast::Span;
+use crate::diagnostic::Diagnostic;
 
 /// Parsed frontmatter from an MDX file.
 #[derive(Debug, Clone, Deserialize, PartialEq)]
@@ -27,12 +35,315 @@ pub struct Frontmatter {
     /// Custom slug override
     #[serde(default)]
     pub slug: Option<String>,
+
+    /// Named template to render the page with, such as `full-width` or a
+    /// layout the project registers (default `doc`)
+    #[serde(default)]
+    pub layout: Option<String>,
+
+    /// Table of contents override: `false` to hide it, or the heading
+    /// levels to include (`{ min: 2, max: 3 }`)
+    #[serde(default)]
+    pub toc: Option<TocSettings>,
+
+    /// Work in progress, left out of builds unless drafts are included
+    #[serde(default)]
+    pub draft: bool,
+
+    /// Day the page goes live (`2024-05-01`); until then it is left out
+    /// like a draft
+    #[serde(default, deserialize_with = "deserialize_date")]
+    pub publish_date: Option<String>,
+
+    /// Other fields, such as `tags` or `status`, for templates and the
+    /// project's frontmatter schema
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, FrontmatterValue>,
+}
+
+/// Value of a frontmatter field.
+///
+/// TOML dates and times are read as strings, as in YAML and JSON.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum FrontmatterValue {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    List(Vec<FrontmatterValue>),
+    Map(BTreeMap<String, FrontmatterValue>),
+}
+
+impl FrontmatterValue {
+    /// Name of the value's type, as used by schemas.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Bool(_) => "boolean",
+            Self::Integer(_) => "integer",
+            Self::Float(_) => "number",
+            Self::String(_) => "string",
+            Self::List(_) => "list",
+            Self::Map(_) => "map",
+        }
+    }
+
+    /// The value as a string, if it is a scalar.
+    pub fn as_scalar(&self) -> Option<String> {
+        match self {
+            Self::Bool(b) => Some(b.to_string()),
+            Self::Integer(n) => Some(n.to_string()),
+            Self::Float(n) => Some(n.to_string()),
+            Self::String(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// Key of the map the `toml` crate reads dates and times into
+const TOML_DATETIME: &str = "$__toml_private_datetime";
+
+impl<'de> Deserialize<'de> for FrontmatterValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = FrontmatterValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a frontmatter value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(FrontmatterValue::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(FrontmatterValue::Integer(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(i64::try_from(v).map_or(FrontmatterValue::Float(v as f64), FrontmatterValue::Integer))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(FrontmatterValue::Float(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(FrontmatterValue::String(v.to_string()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+        Ok(FrontmatterValue::String(v))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(FrontmatterValue::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(FrontmatterValue::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        FrontmatterValue::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(FrontmatterValue::List(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut entries = BTreeMap::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if key == TOML_DATETIME {
+                return Ok(FrontmatterValue::String(map.next_value()?));
+            }
+            entries.insert(key, map.next_value()?);
+        }
+        Ok(FrontmatterValue::Map(entries))
+    }
+}
+
+impl Frontmatter {
+    /// Whether the page is live on `today` (`YYYY-MM-DD`): not a draft, and
+    /// past its publish date.
+    pub fn is_published(&self, today: &str) -> bool {
+        !self.draft
+            && self
+                .publish_date
+                .as_deref()
+                .is_none_or(|date| date.get(..10).unwrap_or(date) <= today)
+    }
+
+    /// Publish date of a page that is not a draft but is not live on
+    /// `today` yet.
+    pub fn scheduled(&self, today: &str) -> Option<&str> {
+        self.publish_date
+            .as_deref()
+            .filter(|_| !self.draft && !self.is_published(today))
+    }
+
+    /// Get a field by name, built-in or extra.
+    pub fn field(&self, name: &str) -> Option<FrontmatterValue> {
+        let string = |s: &Option<String>| s.clone().map(FrontmatterValue::String);
+        match name {
+            "title" => Some(FrontmatterValue::String(self.title.clone())),
+            "description" => string(&self.description),
+            "component" => string(&self.component),
+            "slug" => string(&self.slug),
+            "layout" => string(&self.layout),
+            "order" => self.order.map(|n| FrontmatterValue::Integer(n.into())),
+            "nav" => Some(FrontmatterValue::Bool(self.nav)),
+            "draft" => Some(FrontmatterValue::Bool(self.draft)),
+            "publish_date" => string(&self.publish_date),
+            _ => self.extra.get(name).cloned(),
+        }
+    }
+}
+
+/// Table of contents settings of a page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TocSettings {
+    /// `toc: false` hides the table of contents, `toc: true` shows it
+    Enabled(bool),
+
+    /// Heading levels to include
+    Depth(TocDepth),
+}
+
+/// Heading levels of a page's table of contents; missing bounds use the
+/// site default.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TocDepth {
+    /// Shallowest heading level to include (1-6)
+    #[serde(default)]
+    pub min: Option<u8>,
+
+    /// Deepest heading level to include (1-6)
+    #[serde(default)]
+    pub max: Option<u8>,
+}
+
+impl<'de> Deserialize<'de> for TocSettings {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(TocVisitor)
+    }
+}
+
+struct TocVisitor;
+
+impl<'de> Visitor<'de> for TocVisitor {
+    type Value = TocSettings;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a boolean or heading levels such as { min: 2, max: 3 }")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(TocSettings::Enabled(v))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        // Read the map as `TocDepth` so misspelled bounds are reported
+        TocDepth::deserialize(serde::de::value::MapAccessDeserializer::new(map))
+            .map(TocSettings::Depth)
+    }
+}
+
+impl TocDepth {
+    /// Why these levels cannot select any heading, if they cannot.
+    fn problem(&self) -> Option<String> {
+        if let Some(level) = [self.min, self.max]
+            .into_iter()
+            .flatten()
+            .find(|level| !(1..=6).contains(level))
+        {
+            return Some(format!("toc level {level} is not a heading level (1-6)"));
+        }
+        match (self.min, self.max) {
+            (Some(min), Some(max)) if min > max => {
+                Some(format!("toc min {min} is greater than max {max}"))
+            }
+            _ => None,
+        }
+    }
 }
 
 fn default_true() -> bool {
     true
 }
 
+/// Read a `YYYY-MM-DD` date, optionally followed by a time, from a string
+/// or a TOML date.
+fn deserialize_date<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    deserializer.deserialize_any(DateVisitor)
+}
+
+struct DateVisitor;
+
+impl<'de> Visitor<'de> for DateVisitor {
+    type Value = Option<String>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a date such as 2024-05-01")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        if is_date(v) {
+            Ok(Some(v.to_string()))
+        } else {
+            Err(E::invalid_value(serde::de::Unexpected::Str(v), &self))
+        }
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        match map.next_key::<String>()? {
+            Some(key) if key == TOML_DATETIME => self.visit_str(&map.next_value::<String>()?),
+            _ => Err(serde::de::Error::invalid_type(
+                serde::de::Unexpected::Map,
+                &self,
+            )),
+        }
+    }
+}
+
+fn is_date(date: &str) -> bool {
+    let bytes = date.as_bytes();
+    bytes.len() >= 10
+        && bytes[..10].iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        })
+        && (bytes.len() == 10 || matches!(bytes[10], b'T' | b't' | b' '))
+}
+
 impl Default for Frontmatter {
     fn default() -> Self {
         Self {
@@ -42,43 +353,282 @@ impl Default for Frontmatter {
             order: None,
             nav: true,
             slug: None,
+            layout: None,
+            toc: None,
+            draft: false,
+            publish_date: None,
+            extra: BTreeMap::new(),
+        }
+    }
+}
+
+/// Today's date (UTC) as `YYYY-MM-DD`, for [`Frontmatter::is_published`].
+pub fn today() -> String {
+    let seconds = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    civil_date(seconds / 86_400)
+}
+
+/// Date of a day counted from 1970-01-01, as `YYYY-MM-DD`.
+fn civil_date(days: u64) -> String {
+    // Howard Hinnant's days-to-civil algorithm, for days after the epoch
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Format of a frontmatter block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrontmatterFormat {
+    /// YAML between `---` lines
+    Yaml,
+    /// TOML between `+++` lines
+    Toml,
+    /// A JSON object at the start of the file
+    Json,
+}
+
+impl FrontmatterFormat {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Yaml => "YAML",
+            Self::Toml => "TOML",
+            Self::Json => "JSON",
         }
     }
 }
 
 /// Extract frontmatter from MDX content.
 ///
-/// Returns the parsed frontmatter and the remaining content after the frontmatter block.
+/// Frontmatter is YAML between `---` lines, TOML between `+++` lines, or a
+/// JSON object. Returns the parsed frontmatter and the remaining content
+/// after the frontmatter block.
 pub fn extract_frontmatter(source: &str) -> Result<(Option<Frontmatter>, &str), FrontmatterError> {
     let trimmed = source.trim_start();
+    let open = source.len() - trimmed.len();
 
-    if !trimmed.starts_with("---") {
-        return Ok((None, source));
+    if trimmed.starts_with("---") {
+        fenced_frontmatter(source, open, "---", FrontmatterFormat::Yaml)
+    } else if trimmed.starts_with("+++") {
+        fenced_frontmatter(source, open, "+++", FrontmatterFormat::Toml)
+    } else if is_json_object(trimmed) {
+        json_frontmatter(source, open)
+    } else {
+        Ok((None, source))
     }
+}
+
+/// Whether the content starts with a JSON object, rather than a JSX
+/// expression such as `{/* comment */}`.
+fn is_json_object(content: &str) -> bool {
+    content
+        .strip_prefix('{')
+        .is_some_and(|rest| rest.trim_start().starts_with(['"', '}']))
+}
 
-    // Find the closing ---
-    let after_open = &trimmed[3..];
-    let Some(close_pos) = after_open.find("\n---") else {
-        return Err(FrontmatterError::Unclosed);
+/// Parse YAML or TOML frontmatter whose opening `fence` starts at `open`.
+fn fenced_frontmatter<'s>(
+    source: &'s str,
+    open: usize,
+    fence: &'static str,
+    format: FrontmatterFormat,
+) -> Result<(Option<Frontmatter>, &'s str), FrontmatterError> {
+    // Find the closing fence
+    let after_open = &source[open + 3..];
+    let Some(close_pos) = after_open.find(&format!("\n{fence}")) else {
+        return Err(FrontmatterError::Unclosed {
+            fence,
+            span: Span::locate(source, open..open + 3),
+        });
     };
 
-    let yaml_content = &after_open[..close_pos].trim();
+    let raw = &after_open[..close_pos];
+    let content = raw.trim();
+    let content_start = open + 3 + raw.len() - raw.trim_start().len();
     let remaining = &after_open[close_pos + 4..];
 
-    let frontmatter: Frontmatter = serde_yaml::from_str(yaml_content)
-        .map_err(|e| FrontmatterError::InvalidYaml(e.to_string()))?;
+    let invalid = |message: String, range: Option<Range<usize>>| {
+        // Point at the error, or at the whole block when it has no location
+        let range = match range {
+            // Parsers may point at or past the end of an unterminated value
+            Some(range) => {
+                let start = range.start.min(content.len());
+                let end = range.end.max(start + 1).min(content.len().max(start + 1));
+                content_start + start..content_start + end
+            }
+            None => content_start..content_start + content.len(),
+        };
+        FrontmatterError::Invalid {
+            format,
+            message,
+            span: Span::locate(source, range),
+        }
+    };
+
+    let frontmatter: Frontmatter = match format {
+        FrontmatterFormat::Yaml => serde_yaml::from_str(content).map_err(|e| {
+            let range = e.location().map(|l| l.index()..l.index() + 1);
+            invalid(strip_location(e.to_string()), range)
+        })?,
+        _ => toml::from_str(content)
+            .map_err(|e| invalid(e.message().trim_end().to_string(), e.span()))?,
+    };
+    check_toc(&frontmatter, source, format)?;
 
     Ok((Some(frontmatter), remaining.trim_start()))
 }
 
+/// Parse a JSON object frontmatter starting at `open`.
+fn json_frontmatter(
+    source: &str,
+    open: usize,
+) -> Result<(Option<Frontmatter>, &str), FrontmatterError> {
+    let mut stream = serde_json::Deserializer::from_str(&source[open..]).into_iter();
+    match stream.next() {
+        Some(Ok(frontmatter)) => {
+            check_toc(&frontmatter, source, FrontmatterFormat::Json)?;
+            let end = open + stream.byte_offset();
+            Ok((Some(frontmatter), source[end..].trim_start()))
+        }
+        Some(Err(e)) => {
+            let start = open + offset_of(&source[open..], e.line(), e.column());
+            let start = start.min(source.len().saturating_sub(1));
+            Err(FrontmatterError::Invalid {
+                format: FrontmatterFormat::Json,
+                message: strip_location(e.to_string()),
+                span: Span::locate(source, start..(start + 1).min(source.len())),
+            })
+        }
+        None => Ok((None, source)),
+    }
+}
+
+/// Reject a `toc` override whose heading levels cannot select any heading,
+/// pointing at the `toc` key.
+fn check_toc(
+    frontmatter: &Frontmatter,
+    source: &str,
+    format: FrontmatterFormat,
+) -> Result<(), FrontmatterError> {
+    match frontmatter.toc {
+        Some(TocSettings::Depth(depth)) => match depth.problem() {
+            Some(message) => Err(FrontmatterError::Invalid {
+                format,
+                message,
+                span: field_span(source, "toc"),
+            }),
+            None => Ok(()),
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Remove the ` at line 3 column 8` suffix of a parser message.
+fn strip_location(message: String) -> String {
+    match message.rsplit_once(" at line ") {
+        Some((message, _)) => message.to_string(),
+        None => message,
+    }
+}
+
+/// Byte offset of a 1-indexed line and column, in characters, of `text`.
+fn offset_of(text: &str, line: usize, column: usize) -> usize {
+    let line_start: usize = text
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum();
+    // serde_json points just past the offending character
+    text[line_start..]
+        .char_indices()
+        .nth(column.saturating_sub(1))
+        .map_or(text.len(), |(i, _)| line_start + i)
+}
+
 /// Errors that can occur when parsing frontmatter.
 #[derive(Debug, thiserror::Error)]
 pub enum FrontmatterError {
-    #[error("Unclosed frontmatter block - missing closing ---")]
-    Unclosed,
+    #[error("Unclosed frontmatter block - missing closing {fence} (line {})", .span.line)]
+    Unclosed { fence: &'static str, span: Span },
+
+    #[error("Invalid {} in frontmatter at line {}, column {}: {message}", .format.name(), .span.line, .span.column)]
+    Invalid {
+        format: FrontmatterFormat,
+        message: String,
+        span: Span,
+    },
+}
 
-    #[error("Invalid YAML in frontmatter: {0}")]
-    InvalidYaml(String),
+impl FrontmatterError {
+    /// The error as a diagnostic for code frames.
+    pub fn diagnostic(&self) -> Diagnostic {
+        match self {
+            Self::Unclosed { fence, span } => Diagnostic::new("Unclosed frontmatter block", *span)
+                .with_help(format!(
+                    "end the frontmatter with a line containing only {fence}"
+                )),
+            Self::Invalid {
+                format,
+                message,
+                span,
+            } => Diagnostic::new(
+                format!("Invalid {} in frontmatter: {message}", format.name()),
+                *span,
+            ),
+        }
+    }
+}
+
+/// Span of the opening frontmatter fence, or of the start of the document.
+pub fn frontmatter_span(source: &str) -> Span {
+    let start = source.len() - source.trim_start().len();
+    let rest = &source[start..];
+    let end = if rest.starts_with("---") || rest.starts_with("+++") {
+        start + 3
+    } else if rest.starts_with('{') {
+        start + 1
+    } else {
+        start
+    };
+    Span::locate(source, start..end)
+}
+
+/// Span of the key `name` in the frontmatter, written as `name:`,
+/// `name =`, `"name":` or a `[name]` table, or of the opening fence when it
+/// cannot be found.
+pub fn field_span(source: &str, name: &str) -> Span {
+    let mut offset = 0;
+    for (i, line) in source.split_inclusive('\n').enumerate() {
+        let text = line.trim_end();
+        if i > 0 && matches!(text, "---" | "+++" | "}") {
+            break;
+        }
+
+        let key = text.trim_start();
+        let quoted = key.starts_with(['"', '[']);
+        let rest = if quoted {
+            key[1..]
+                .strip_prefix(name)
+                .and_then(|rest| rest.strip_prefix(if key.starts_with('"') { '"' } else { ']' }))
+        } else {
+            key.strip_prefix(name)
+        };
+        let table = key.starts_with('[') && rest.is_some_and(|rest| rest.trim().is_empty());
+        if table || rest.is_some_and(|rest| rest.trim_start().starts_with([':', '='])) {
+            let start = offset + text.len() - key.len() + usize::from(quoted);
+            return Span::locate(source, start..start + name.len());
+        }
+        offset += line.len();
+    }
+    frontmatter_span(source)
 }
 
 #[cfg(test)]
@@ -106,6 +656,90 @@ order: 1
         );
         assert_eq!(fm.order, Some(1));
         assert!(content.starts_with("# Button Component"));
+        assert_eq!(fm.toc, None);
+        assert!(fm.extra.is_empty());
+    }
+
+    #[test]
+    fn keeps_extra_fields() {
+        let source = "---\ntitle: Button\nstatus: beta\nsince: 2.3\ntags: [input, form]\nlegacy: null\n---\n";
+
+        let (fm, _) = extract_frontmatter(source).unwrap();
+        let fm = fm.unwrap();
+
+        assert_eq!(
+            fm.extra.get("status"),
+            Some(&FrontmatterValue::String("beta".to_string()))
+        );
+        assert_eq!(fm.extra.get("since"), Some(&FrontmatterValue::Float(2.3)));
+        assert_eq!(
+            fm.field("tags"),
+            Some(FrontmatterValue::List(vec![
+                FrontmatterValue::String("input".to_string()),
+                FrontmatterValue::String("form".to_string()),
+            ]))
+        );
+        assert_eq!(fm.extra.get("legacy"), Some(&FrontmatterValue::Null));
+        assert_eq!(
+            fm.field("title"),
+            Some(FrontmatterValue::String("Button".to_string()))
+        );
+        assert!(!fm.extra.contains_key("title"));
+    }
+
+    #[test]
+    fn parses_toc_settings() {
+        let (fm, _) = extract_frontmatter("---\ntitle: A\ntoc: false\n---\n").unwrap();
+        assert_eq!(fm.unwrap().toc, Some(TocSettings::Enabled(false)));
+
+        let (fm, _) = extract_frontmatter("---\ntitle: A\ntoc: { min: 2, max: 3 }\n---\n").unwrap();
+        assert_eq!(
+            fm.unwrap().toc,
+            Some(TocSettings::Depth(TocDepth {
+                min: Some(2),
+                max: Some(3)
+            }))
+        );
+
+        let (fm, _) = extract_frontmatter("---\ntitle: A\ntoc:\n  max: 2\n---\n").unwrap();
+        assert_eq!(
+            fm.unwrap().toc,
+            Some(TocSettings::Depth(TocDepth {
+                min: None,
+                max: Some(2)
+            }))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_toc_settings() {
+        let source = "---\ntitle: A\ntoc: { mni: 2 }\n---\n";
+        let Err(FrontmatterError::Invalid { message, span, .. }) = extract_frontmatter(source)
+        else {
+            panic!("expected an unknown toc field to be rejected");
+        };
+        assert_eq!(message, "toc: unknown field `mni`, expected `min` or `max`");
+        assert_eq!(span.line, 3);
+
+        let source = "---\ntitle: A\ntoc: { min: 3, max: 2 }\n---\n";
+        let Err(FrontmatterError::Invalid { message, span, .. }) = extract_frontmatter(source)
+        else {
+            panic!("expected min > max to be rejected");
+        };
+        assert_eq!(message, "toc min 3 is greater than max 2");
+        assert_eq!((span.line, span.column), (3, 1));
+        assert_eq!(&source[span.range()], "toc");
+
+        let source = "+++\ntitle = \"A\"\n\n[toc]\nmax = 7\n+++\n";
+        let Err(FrontmatterError::Invalid { message, span, .. }) = extract_frontmatter(source)
+        else {
+            panic!("expected an out-of-range level to be rejected");
+        };
+        assert_eq!(message, "toc level 7 is not a heading level (1-6)");
+        assert_eq!(&source[span.range()], "toc");
+
+        let source = "{ \"title\": \"A\", \"toc\": { \"min\": 0 } }\n# A\n";
+        assert!(extract_frontmatter(source).is_err());
     }
 
     #[test]
@@ -124,7 +758,17 @@ order: 1
 
         let result = extract_frontmatter(source);
 
-        assert!(matches!(result, Err(FrontmatterError::Unclosed)));
+        assert!(matches!(
+            result,
+            Err(FrontmatterError::Unclosed { fence: "---", span }) if span.line == 1
+        ));
+
+        let result = extract_frontmatter("\n+++\ntitle = \"Test\"\n---\n");
+
+        assert!(matches!(
+            result,
+            Err(FrontmatterError::Unclosed { fence: "+++", span }) if span.line == 2
+        ));
     }
 
     #[test]
@@ -133,6 +777,171 @@ order: 1
 
         let result = extract_frontmatter(source);
 
-        assert!(matches!(result, Err(FrontmatterError::InvalidYaml(_))));
+        assert!(matches!(
+            result,
+            Err(FrontmatterError::Invalid {
+                format: FrontmatterFormat::Yaml,
+                ..
+            })
+        ));
+
+        let source = "---\ntitle: Test\norder: first\n---\n";
+
+        let Err(FrontmatterError::Invalid { message, span, .. }) = extract_frontmatter(source)
+        else {
+            panic!("expected a YAML error");
+        };
+
+        assert!(message.starts_with("order: invalid type"), "{message}");
+        assert_eq!((span.line, span.column), (3, 8));
+    }
+
+    #[test]
+    fn parses_drafts_and_publish_dates() {
+        let (fm, _) = extract_frontmatter("---\ntitle: A\ndraft: true\n---\n").unwrap();
+        let fm = fm.unwrap();
+        assert!(fm.draft);
+        assert!(!fm.is_published("2024-05-01"));
+
+        let (fm, _) =
+            extract_frontmatter("---\ntitle: A\npublish_date: 2024-05-01\n---\n").unwrap();
+        let fm = fm.unwrap();
+        assert_eq!(fm.publish_date.as_deref(), Some("2024-05-01"));
+        assert!(!fm.is_published("2024-04-30"));
+        assert!(fm.is_published("2024-05-01"));
+
+        let (fm, _) =
+            extract_frontmatter("+++\ntitle = \"A\"\npublish_date = 2024-05-01T09:00:00Z\n+++\n")
+                .unwrap();
+        assert_eq!(
+            fm.unwrap().publish_date.as_deref(),
+            Some("2024-05-01T09:00:00Z")
+        );
+
+        let Err(FrontmatterError::Invalid { message, span, .. }) =
+            extract_frontmatter("---\ntitle: A\npublish_date: soon\n---\n")
+        else {
+            panic!("expected an invalid date");
+        };
+        assert!(
+            message.contains("expected a date such as 2024-05-01"),
+            "{message}"
+        );
+        assert_eq!(span.line, 3);
+    }
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(civil_date(0), "1970-01-01");
+        assert_eq!(civil_date(11_016), "2000-02-29");
+        assert_eq!(civil_date(19_844), "2024-05-01");
+        assert_eq!(today().len(), 10);
+    }
+
+    #[test]
+    fn extracts_toml_frontmatter() {
+        let source = "+++\ntitle = \"Button\"\norder = 2\nstatus = \"beta\"\nupdated = 2024-05-01\n\n[toc]\nmax = 3\n+++\n\n# Button\n";
+
+        let (fm, content) = extract_frontmatter(source).unwrap();
+        let fm = fm.unwrap();
+
+        assert_eq!(fm.title, "Button");
+        assert_eq!(fm.order, Some(2));
+        assert_eq!(
+            fm.toc,
+            Some(TocSettings::Depth(TocDepth {
+                min: None,
+                max: Some(3)
+            }))
+        );
+        assert_eq!(
+            fm.extra.get("status"),
+            Some(&FrontmatterValue::String("beta".to_string()))
+        );
+        assert_eq!(
+            fm.extra.get("updated"),
+            Some(&FrontmatterValue::String("2024-05-01".to_string()))
+        );
+        assert_eq!(content, "# Button\n");
+    }
+
+    #[test]
+    fn extracts_json_frontmatter() {
+        let source = "{\n  \"title\": \"Button\",\n  \"nav\": false,\n  \"tags\": [\"form\"]\n}\n\n# Button\n";
+
+        let (fm, content) = extract_frontmatter(source).unwrap();
+        let fm = fm.unwrap();
+
+        assert_eq!(fm.title, "Button");
+        assert!(!fm.nav);
+        assert_eq!(
+            fm.extra.get("tags"),
+            Some(&FrontmatterValue::List(vec![FrontmatterValue::String(
+                "form".to_string()
+            )]))
+        );
+        assert_eq!(content, "# Button\n");
+
+        // JSX expressions are content
+        let source = "{/* draft */}\n# Button\n";
+        let (fm, content) = extract_frontmatter(source).unwrap();
+        assert!(fm.is_none());
+        assert_eq!(content, source);
+    }
+
+    #[test]
+    fn errors_on_invalid_toml_and_json() {
+        let source = "+++\ntitle = \"Test\"\norder = \"first\"\n+++\n";
+
+        let Err(FrontmatterError::Invalid {
+            format: FrontmatterFormat::Toml,
+            message,
+            span,
+        }) = extract_frontmatter(source)
+        else {
+            panic!("expected a TOML error");
+        };
+
+        assert!(message.starts_with("invalid type"), "{message}");
+        assert_eq!((span.line, span.column), (3, 9));
+
+        let source = "{\n  \"title\": \"Test\",\n  \"order\": \"first\"\n}\n";
+
+        let Err(FrontmatterError::Invalid {
+            format: FrontmatterFormat::Json,
+            message,
+            span,
+        }) = extract_frontmatter(source)
+        else {
+            panic!("expected a JSON error");
+        };
+
+        assert!(message.starts_with("invalid type"), "{message}");
+        assert_eq!(span.line, 3);
+
+        let source = "{\n  \"title\": \"Test\",\n\n# Content\n";
+        let error = extract_frontmatter(source).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .starts_with("Invalid JSON in frontmatter at line 4"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn reports_unterminated_values() {
+        for source in [
+            "---\ntitle: \"abc\n---\n",
+            "+++\ntitle = \n+++\n",
+            "+++\ntitle = \"x\n+++\n",
+        ] {
+            match extract_frontmatter(source) {
+                Err(FrontmatterError::Invalid { span, .. }) => {
+                    assert_eq!(span.line, 2, "{source:?}");
+                }
+                other => panic!("expected an error for {source:?}, got {other:?}"),
+            }
+        }
     }
 }
@@ -0,0 +1,279 @@
+//! Content includes.
+//!
+//! An `<Include>` element on its own lines is replaced by the content of
+//! another file, resolved relative to the including file:
+//!
+//! ```md
+//! <Include src="../_partials/install.mdx" />
+//! <Include src="../_partials/props.mdx" lines="3-10" />
+//! ```
+//!
+//! Without `lines`, the partial's frontmatter is skipped. Partials may
+//! include others, but not themselves. Included nodes keep the spans of the
+//! partial they come from.
+
+use std::fs;
+use std::ops::{Range, RangeInclusive};
+use std::path::{Path, PathBuf};
+
+use crate::ast::Node;
+use crate::diagnostic::Diagnostic;
+use crate::directive::parse_attributes;
+use crate::frontmatter::extract_frontmatter;
+use crate::jsx::JsxElement;
+use crate::parser::{parse_region, ParseError};
+
+/// Replace `<Include>` elements with the content of the files they name,
+/// throughout the tree. `stack` holds the including files, innermost last,
+/// and every included file is added to `includes`.
+pub(crate) fn resolve_includes(
+    nodes: &mut Vec<Node>,
+    stack: &mut Vec<PathBuf>,
+    includes: &mut Vec<PathBuf>,
+) -> Result<(), ParseError> {
+    let mut i = 0;
+    while i < nodes.len() {
+        if let Node::Jsx(element) = &nodes[i] {
+            if element.name == "Include" {
+                let included = include(element, stack, includes)?;
+                let len = included.len();
+                nodes.splice(i..=i, included);
+                i += len;
+                continue;
+            }
+        }
+
+        for children in nodes[i].child_lists_mut() {
+            resolve_includes(children, stack, includes)?;
+        }
+        i += 1;
+    }
+    Ok(())
+}
+
+/// Parse the content an `<Include>` element stands for.
+fn include(
+    element: &JsxElement,
+    stack: &mut Vec<PathBuf>,
+    includes: &mut Vec<PathBuf>,
+) -> Result<Vec<Node>, ParseError> {
+    let error = |message: String| ParseError::Parse(Diagnostic::new(message, element.span));
+
+    let attributes = element
+        .opening_tag()
+        .strip_prefix("<Include")
+        .unwrap_or_default()
+        .trim_end_matches('>')
+        .trim_end_matches('/');
+    let attributes = parse_attributes(attributes);
+    let attribute = |name: &str| {
+        attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    };
+
+    let Some(src) = attribute("src").filter(|src| !src.is_empty()) else {
+        return Err(ParseError::Parse(
+            Diagnostic::new("<Include> is missing its src", element.span).with_help(
+                "name the file to include, as in <Include src=\"_partials/install.mdx\" />",
+            ),
+        ));
+    };
+
+    let including = stack.last().expect("including file");
+    let path = including.parent().unwrap_or(Path::new("")).join(src);
+    let path = fs::canonicalize(&path).unwrap_or(path);
+
+    if let Some(start) = stack.iter().position(|file| *file == path) {
+        let cycle: Vec<_> = stack[start..]
+            .iter()
+            .chain([&path])
+            .map(|file| file.display().to_string())
+            .collect();
+        return Err(ParseError::Parse(
+            Diagnostic::new(format!("{src} includes itself"), element.span)
+                .with_help(format!("include cycle: {}", cycle.join(" -> "))),
+        ));
+    }
+
+    let source =
+        fs::read_to_string(&path).map_err(|e| error(format!("Failed to include {src}: {e}")))?;
+
+    let region = match attribute("lines") {
+        Some(lines) => {
+            let Some(lines) = parse_line_range(lines) else {
+                return Err(ParseError::Parse(
+                    Diagnostic::new(format!("Invalid line range \"{lines}\""), element.span)
+                        .with_help("use lines=\"3-10\", lines=\"3-\" or lines=\"3\""),
+                ));
+            };
+            line_region(&source, lines.clone()).ok_or_else(|| {
+                error(format!(
+                    "{src} has no line {} ({} lines)",
+                    lines.start(),
+                    source.lines().count()
+                ))
+            })?
+        }
+        None => {
+            let (_, content) = extract_frontmatter(&source).map_err(|e| {
+                let diagnostic = e.diagnostic();
+                error(format!(
+                    "In {src}:{}:{}: {}",
+                    diagnostic.span.line, diagnostic.span.column, diagnostic.message
+                ))
+            })?;
+            source.len() - content.len()..source.len()
+        }
+    };
+
+    if !includes.contains(&path) {
+        includes.push(path.clone());
+    }
+    stack.push(path);
+    let nodes = parse_region(&source, region).and_then(|mut nodes| {
+        resolve_includes(&mut nodes, stack, includes)?;
+        Ok(nodes)
+    });
+    stack.pop();
+
+    nodes.map_err(|e| match e {
+        // Errors of nested includes already point at an element of the partial
+        ParseError::Parse(diagnostic) => ParseError::Parse(Diagnostic {
+            message: format!(
+                "In {src}:{}:{}: {}",
+                diagnostic.span.line, diagnostic.span.column, diagnostic.message
+            ),
+            span: element.span,
+            help: diagnostic.help,
+        }),
+        e => e,
+    })
+}
+
+/// Parse a 1-indexed `3-10`, `3-` or `3` line range.
+fn parse_line_range(lines: &str) -> Option<RangeInclusive<usize>> {
+    let (start, end) = match lines.split_once('-') {
+        Some((start, "")) => (start.trim().parse().ok()?, usize::MAX),
+        Some((start, end)) => (start.trim().parse().ok()?, end.trim().parse().ok()?),
+        None => {
+            let line = lines.trim().parse().ok()?;
+            (line, line)
+        }
+    };
+    (start >= 1 && start <= end).then_some(start..=end)
+}
+
+/// Byte range of `lines` of `source`, cut short at its end.
+fn line_region(source: &str, lines: RangeInclusive<usize>) -> Option<Range<usize>> {
+    let mut starts = std::iter::once(0).chain(source.match_indices('\n').map(|(i, _)| i + 1));
+    let start = starts
+        .nth(lines.start() - 1)
+        .filter(|&s| s < source.len())?;
+    let end = starts
+        .nth(lines.end() - lines.start())
+        .unwrap_or(source.len());
+    Some(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::{parse_mdx_file, ParseError};
+
+    #[test]
+    fn includes_partials() {
+        let temp = tempdir().unwrap();
+        let partials = temp.path().join("_partials");
+        fs::create_dir_all(&partials).unwrap();
+        fs::write(
+            partials.join("install.mdx"),
+            "---\ntitle: Install\n---\n## Install\n\n<Include src=\"command.mdx\" />\n",
+        )
+        .unwrap();
+        fs::write(
+            partials.join("command.mdx"),
+            "one\n\n```sh\nnpm i veneer\n```\n\nthree\n",
+        )
+        .unwrap();
+
+        let page = temp.path().join("index.mdx");
+        let doc = parse_mdx_file(
+            "# Home\n\n:::note\n<Include src=\"_partials/install.mdx\" />\n:::\n\n<Include src=\"_partials/command.mdx\" lines=\"3-5\" />\n",
+            &page,
+        )
+        .unwrap();
+
+        assert_eq!(doc.toc[0].children[0].id, "install");
+        assert_eq!(doc.code_blocks.len(), 2);
+        assert!(matches!(doc.nodes.last(), Some(Node::Code(_))));
+        let Node::Admonition(note) = &doc.nodes[1] else {
+            panic!("expected an admonition");
+        };
+        assert_eq!(note.children.len(), 4);
+
+        let names: Vec<_> = doc
+            .includes
+            .iter()
+            .map(|path| path.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["install.mdx", "command.mdx"]);
+    }
+
+    #[test]
+    fn reports_include_errors() {
+        let temp = tempdir().unwrap();
+        fs::write(temp.path().join("a.mdx"), "<Include src=\"b.mdx\" />\n").unwrap();
+        fs::write(
+            temp.path().join("b.mdx"),
+            "Text\n\n<Include src=\"a.mdx\" />\n",
+        )
+        .unwrap();
+        let page = temp.path().join("index.mdx");
+
+        let error = |source: &str| match parse_mdx_file(source, &page) {
+            Err(ParseError::Parse(diagnostic)) => diagnostic,
+            other => panic!("expected an error, got {other:?}"),
+        };
+
+        let cycle = error("# Home\n\n<Include src=\"a.mdx\" />\n");
+        assert_eq!(cycle.span.line, 3);
+        assert!(cycle
+            .message
+            .starts_with("In a.mdx:1:1: In b.mdx:3:1: a.mdx includes itself"));
+        let help = cycle.help.unwrap();
+        assert!(help.starts_with("include cycle: "), "{help}");
+        assert_eq!(help.matches(" -> ").count(), 2);
+
+        assert!(error("<Include src=\"missing.mdx\" />")
+            .message
+            .starts_with("Failed to include missing.mdx"));
+        assert!(error("<Include />").message.contains("missing its src"));
+        assert!(error("<Include src=\"a.mdx\" lines=\"3-1\" />")
+            .message
+            .contains("Invalid line range"));
+        assert!(error("<Include src=\"b.mdx\" lines=\"9\" />")
+            .message
+            .contains("has no line 9"));
+    }
+
+    #[test]
+    fn selects_line_ranges() {
+        assert_eq!(parse_line_range("3-10"), Some(3..=10));
+        assert_eq!(parse_line_range("4"), Some(4..=4));
+        assert_eq!(parse_line_range("2-"), Some(2..=usize::MAX));
+        assert_eq!(parse_line_range("0-2"), None);
+        assert_eq!(parse_line_range("x"), None);
+
+        let source = "a\nb\nc\n";
+        assert_eq!(line_region(source, 2..=2), Some(2..4));
+        assert_eq!(line_region(source, 2..=usize::MAX), Some(2..6));
+        assert_eq!(line_region(source, 3..=9), Some(4..6));
+        assert_eq!(line_region(source, 4..=4), None);
+    }
+}
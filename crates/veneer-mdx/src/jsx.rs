@@ -0,0 +1,173 @@
+//! JSX elements written directly in MDX prose.
+
+use std::ops::Range;
+
+use crate::ast::{Node, Span};
+
+/// A JSX component element in the document, such as `<Button>Save</Button>`.
+///
+/// Only capitalized tags are components; lowercase tags stay plain HTML.
+/// Elements written on their own lines whose content starts on a new line
+/// wrap Markdown, which is parsed into `children`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsxElement {
+    /// Component name (e.g., "Button", "Tabs.Item")
+    pub name: String,
+
+    /// Element source, from the opening `<` to the end of the closing tag
+    pub source: String,
+
+    /// Source span
+    pub span: Span,
+
+    /// Whether the element sits inside a paragraph rather than on its own lines
+    pub inline: bool,
+
+    /// Markdown content of a wrapping element
+    pub children: Vec<Node>,
+}
+
+impl JsxElement {
+    /// Opening tag source, e.g. `<Tabs defaultValue="npm">`.
+    pub fn opening_tag(&self) -> &str {
+        match tag_end(&self.source, 0) {
+            Some((end, _)) => &self.source[..end],
+            None => &self.source,
+        }
+    }
+
+    /// Closing tag source, empty for self-closing and unclosed elements.
+    pub fn closing_tag(&self) -> &str {
+        match (tag_end(&self.source, 0), self.source.rfind("</")) {
+            (Some((end, false)), Some(close)) if close >= end => &self.source[close..],
+            _ => "",
+        }
+    }
+}
+
+/// Get the component name of a tag starting at `source[0]`, if it is one.
+pub(crate) fn component_name(source: &str) -> Option<&str> {
+    let rest = source.strip_prefix('<')?;
+    if !rest.starts_with(|c: char| c.is_ascii_uppercase()) {
+        return None;
+    }
+    let end = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '.' || c == '_'))
+        .unwrap_or(rest.len());
+    Some(&rest[..end])
+}
+
+/// Find the end (exclusive) of the element whose opening tag starts at
+/// `start`, including nested elements of the same name.
+pub(crate) fn element_end(source: &str, start: usize) -> Option<usize> {
+    let name = component_name(&source[start..])?;
+    let (mut pos, self_closing) = tag_end(source, start)?;
+    if self_closing {
+        return Some(pos);
+    }
+
+    let open = format!("<{name}");
+    let close = format!("</{name}");
+    let mut depth = 1;
+
+    while depth > 0 {
+        let next_open = find_tag(source, pos, &open);
+        let next_close = find_tag(source, pos, &close)?;
+
+        match next_open {
+            Some(o) if o < next_close => {
+                let (end, self_closing) = tag_end(source, o)?;
+                if !self_closing {
+                    depth += 1;
+                }
+                pos = end;
+            }
+            _ => {
+                depth -= 1;
+                pos = next_close + source[next_close..].find('>')? + 1;
+            }
+        }
+    }
+
+    Some(pos)
+}
+
+/// Find the Markdown content of the element spanning `range`: the text
+/// between its tags, when that starts on a new line.
+pub(crate) fn element_content(source: &str, range: Range<usize>) -> Option<Range<usize>> {
+    let (open_end, self_closing) = tag_end(source, range.start)?;
+    if self_closing {
+        return None;
+    }
+    let close = open_end + source[open_end..range.end].rfind("</")?;
+    source[open_end..close]
+        .starts_with(['\n', '\r'])
+        .then_some(open_end..close)
+}
+
+/// Find `<Name` or `</Name` at or after `from`, followed by the end of the name.
+fn find_tag(source: &str, from: usize, prefix: &str) -> Option<usize> {
+    let mut pos = from;
+    loop {
+        let found = pos + source[pos..].find(prefix)?;
+        let after = source[found + prefix.len()..].chars().next();
+        if after.is_some_and(|c| c.is_whitespace() || c == '>' || c == '/') {
+            return Some(found);
+        }
+        pos = found + prefix.len();
+    }
+}
+
+/// Find the end (exclusive) of the tag starting at `start`, skipping `>`
+/// inside quoted values and `{...}` expressions. Also reports whether the
+/// tag is self-closing.
+pub(crate) fn tag_end(source: &str, start: usize) -> Option<(usize, bool)> {
+    let mut quote: Option<char> = None;
+    let mut depth = 0usize;
+    let mut prev = '\0';
+
+    for (offset, c) in source[start..].char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                '>' if depth == 0 => return Some((start + offset + 1, prev == '/')),
+                _ => {}
+            },
+        }
+        if !c.is_whitespace() {
+            prev = c;
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_element_ends() {
+        let source = r#"<Button onClick={() => a > b}>Save</Button> rest"#;
+        assert_eq!(element_end(source, 0), Some(source.find(" rest").unwrap()));
+
+        let nested = "<Card><Card title=\"x\" /><Card>in</Card></Card>!";
+        assert_eq!(element_end(nested, 0), Some(nested.len() - 1));
+
+        assert_eq!(component_name("<Tabs.Item value=\"a\">"), Some("Tabs.Item"));
+        assert_eq!(component_name("<div>"), None);
+        assert_eq!(element_end("<Button>unclosed", 0), None);
+
+        let wrapper = "<Tabs>\n\n```sh\nnpm i\n```\n\n</Tabs>";
+        let content = element_content(wrapper, 0..wrapper.len()).unwrap();
+        assert_eq!(&wrapper[content], "\n\n```sh\nnpm i\n```\n\n");
+        assert_eq!(
+            element_content(source, 0..source.find(" rest").unwrap()),
+            None
+        );
+    }
+}
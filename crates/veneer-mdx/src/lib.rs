@@ -1,12 +1,37 @@
 //! MDX parser with frontmatter and code block extraction.
 //!
-//! This crate provides functionality to parse MDX files, extract YAML frontmatter,
-//! and identify code blocks marked for live preview rendering.
+//! This crate provides functionality to parse MDX files into a typed document
+//! tree, extract YAML, TOML or JSON frontmatter, ESM import/export blocks,
+//! JSX elements and directives, resolve included partials, identify code
+//! blocks marked for live preview rendering, and render the tree to HTML.
+//! Errors carry source positions and render as code frames.
 
+pub mod admonition;
+pub mod ast;
 pub mod codeblock;
+pub mod codegroup;
+pub mod diagnostic;
+pub mod directive;
+pub mod esm;
 pub mod frontmatter;
+pub mod include;
+pub mod jsx;
 pub mod parser;
+pub mod render;
+pub mod schema;
 
-pub use codeblock::{BlockMode, CodeBlock, Language};
-pub use frontmatter::Frontmatter;
-pub use parser::{parse_mdx, ParseError, ParsedDoc};
+pub use admonition::{Admonition, AdmonitionKind};
+pub use ast::{
+    plain_text, walk, walk_inlines, walk_inlines_mut, walk_mut, Alignment, Inline, ListItem, Node,
+    Span,
+};
+pub use codeblock::{BlockMode, CodeBlock, Language, LineDiff};
+pub use codegroup::CodeGroup;
+pub use diagnostic::Diagnostic;
+pub use directive::Directive;
+pub use esm::{EsmBlock, EsmKind};
+pub use frontmatter::{Frontmatter, FrontmatterFormat, FrontmatterValue, TocDepth, TocSettings};
+pub use jsx::JsxElement;
+pub use parser::{parse_mdx, parse_mdx_file, ParseError, ParsedDoc, TocEntry};
+pub use render::{escape_html, render_html};
+pub use schema::{FieldSchema, FieldType, FrontmatterSchema};
@@ -1,9 +1,24 @@
 //! MDX document parser.
 
-use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+use std::collections::HashSet;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
-use crate::codeblock::{extract_filename, BlockMode, CodeBlock, Language};
+use pulldown_cmark::{CodeBlockKind, Event, LinkType, Options, Parser, Tag};
+
+use crate::admonition::{Admonition, AdmonitionKind};
+use crate::ast::{
+    plain_text, walk, walk_inlines, walk_mut, Alignment, Inline, ListItem, Node, Span,
+};
+use crate::codeblock::CodeBlock;
+use crate::codegroup::group_code_blocks;
+use crate::diagnostic::Diagnostic;
+use crate::directive::{directive_end, directive_fence, Directive};
+use crate::esm::{check_module, esm_end, esm_kind, EsmBlock};
 use crate::frontmatter::{extract_frontmatter, Frontmatter, FrontmatterError};
+use crate::include::resolve_includes;
+use crate::jsx::{component_name, element_content, element_end, tag_end, JsxElement};
 
 /// A parsed MDX document.
 #[derive(Debug, Clone)]
@@ -14,11 +29,45 @@ pub struct ParsedDoc {
     /// Markdown content (without frontmatter)
     pub content: String,
 
-    /// Extracted code blocks
+    /// Document tree
+    pub nodes: Vec<Node>,
+
+    /// Code blocks of the tree, in document order
     pub code_blocks: Vec<CodeBlock>,
 
-    /// Table of contents entries
+    /// Top-level `import` / `export` blocks
+    pub esm: Vec<EsmBlock>,
+
+    /// JSX component elements, flow and inline, in document order
+    pub jsx: Vec<JsxElement>,
+
+    /// Table of contents, with deeper headings nested under the heading
+    /// before them
     pub toc: Vec<TocEntry>,
+
+    /// Files pulled in with `<Include>`, directly or through other partials
+    pub includes: Vec<PathBuf>,
+}
+
+impl ParsedDoc {
+    /// Table of contents limited to heading levels `min..=max`, nested
+    /// again after the excluded levels are dropped.
+    pub fn toc_between(&self, min: u8, max: u8) -> Vec<TocEntry> {
+        fn flatten(entries: &[TocEntry], flat: &mut Vec<TocEntry>) {
+            for entry in entries {
+                flat.push(TocEntry {
+                    children: Vec::new(),
+                    ..entry.clone()
+                });
+                flatten(&entry.children, flat);
+            }
+        }
+
+        let mut flat = Vec::new();
+        flatten(&self.toc, &mut flat);
+        flat.retain(|entry| (min..=max).contains(&entry.level));
+        nest_toc(flat)
+    }
 }
 
 /// A table of contents entry.
@@ -30,6 +79,26 @@ pub struct TocEntry {
     pub id: String,
     /// Heading level (1-6)
     pub level: u8,
+    /// Deeper headings up to the next heading of this level or higher
+    pub children: Vec<TocEntry>,
+}
+
+/// Nest a flat list of entries: each entry takes the deeper entries after
+/// it as children.
+fn nest_toc(flat: Vec<TocEntry>) -> Vec<TocEntry> {
+    let mut toc = Vec::new();
+    let mut entries = flat.into_iter().peekable();
+    while let Some(entry) = entries.next() {
+        let mut children = Vec::new();
+        while let Some(child) = entries.next_if(|next| next.level > entry.level) {
+            children.push(child);
+        }
+        toc.push(TocEntry {
+            children: nest_toc(children),
+            ..entry
+        });
+    }
+    toc
 }
 
 /// Errors that can occur when parsing MDX.
@@ -38,93 +107,708 @@ pub enum ParseError {
     #[error("Frontmatter error: {0}")]
     Frontmatter(#[from] FrontmatterError),
 
-    #[error("Parse error at line {line}: {message}")]
-    Parse { line: usize, message: String },
+    #[error("Parse error: {0}")]
+    Parse(Diagnostic),
+}
+
+impl ParseError {
+    /// The error as a diagnostic for code frames.
+    pub fn diagnostic(&self) -> Diagnostic {
+        match self {
+            Self::Frontmatter(e) => e.diagnostic(),
+            Self::Parse(diagnostic) => diagnostic.clone(),
+        }
+    }
 }
 
 /// Parse an MDX document.
 ///
-/// Extracts frontmatter, code blocks, and generates a table of contents.
+/// Extracts frontmatter, builds the document tree and generates a table of
+/// contents. `<Include>` elements are left as they are; use
+/// [`parse_mdx_file`] to resolve them.
 pub fn parse_mdx(source: &str) -> Result<ParsedDoc, ParseError> {
+    parse_document(source, None)
+}
+
+/// Parse the MDX file at `path`, whose content is `source`, replacing
+/// `<Include>` elements with the partials they name.
+pub fn parse_mdx_file(source: &str, path: &Path) -> Result<ParsedDoc, ParseError> {
+    parse_document(source, Some(path))
+}
+
+fn parse_document(source: &str, path: Option<&Path>) -> Result<ParsedDoc, ParseError> {
     // Extract frontmatter first
     let (frontmatter, content) = extract_frontmatter(source)?;
 
-    // Parse markdown to extract code blocks and headings
-    let mut code_blocks = Vec::new();
+    let mut nodes = parse_region(source, source.len() - content.len()..source.len())?;
+    let mut includes = Vec::new();
+    if let Some(path) = path {
+        let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        resolve_includes(&mut nodes, &mut vec![path], &mut includes)?;
+    }
+    group_code_blocks(&mut nodes);
+    assign_heading_ids(&mut nodes);
+    assign_block_ids(&mut nodes);
+
     let mut toc = Vec::new();
+    walk(&nodes, &mut |node| {
+        if let Node::Heading {
+            level,
+            id,
+            children,
+            ..
+        } = node
+        {
+            toc.push(TocEntry {
+                title: plain_text(children).trim().to_string(),
+                id: id.clone(),
+                level: *level,
+                children: Vec::new(),
+            });
+        }
+    });
 
-    let options = Options::ENABLE_TABLES
-        | Options::ENABLE_FOOTNOTES
-        | Options::ENABLE_STRIKETHROUGH
-        | Options::ENABLE_TASKLISTS;
+    let mut code_blocks = Vec::new();
+    let mut jsx = Vec::new();
+    walk(&nodes, &mut |node| match node {
+        Node::Code(block) => code_blocks.push(block.clone()),
+        Node::Jsx(element) => jsx.push(element.clone()),
+        _ => {}
+    });
+    walk_inlines(&nodes, &mut |inline| {
+        if let Inline::Jsx(element) = inline {
+            jsx.push(element.clone());
+        }
+    });
+    jsx.sort_by_key(|element| element.span.start);
+    let esm = nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Esm(esm) => Some(esm.clone()),
+            _ => None,
+        })
+        .collect();
 
-    let parser = Parser::new_ext(content, options);
+    Ok(ParsedDoc {
+        frontmatter,
+        content: content.to_string(),
+        nodes,
+        code_blocks,
+        esm,
+        jsx,
+        toc: nest_toc(toc),
+        includes,
+    })
+}
 
-    let mut current_code_block: Option<(String, usize)> = None; // (info, line)
-    let mut current_heading: Option<(u8, String)> = None; // (level, text)
-    let mut line_number = 1;
+/// Build the tree of the Markdown in `region` of `source`.
+pub(crate) fn parse_region(source: &str, region: Range<usize>) -> Result<Vec<Node>, ParseError> {
+    TreeBuilder::new(source).parse(region, true)
+}
 
-    // Count lines in frontmatter to offset line numbers
-    let frontmatter_lines = source.len() - content.len();
-    let frontmatter_line_offset = source[..frontmatter_lines].lines().count();
+/// Builds the document tree from Markdown events.
+struct TreeBuilder<'s> {
+    source: &'s str,
+    /// Byte offsets at which lines start
+    line_starts: Vec<usize>,
+}
 
-    for event in parser {
-        match event {
-            Event::Start(Tag::CodeBlock(kind)) => {
-                let info = match &kind {
-                    CodeBlockKind::Fenced(info) => info.to_string(),
-                    CodeBlockKind::Indented => String::new(),
-                };
-                current_code_block = Some((info, line_number + frontmatter_line_offset));
+/// A node whose end event has not been read yet.
+struct Frame {
+    kind: FrameKind,
+    range: Range<usize>,
+    blocks: Vec<Node>,
+    inlines: Vec<Inline>,
+}
+
+enum FrameKind {
+    Root,
+    Paragraph,
+    /// Level and `{#custom-id}`
+    Heading(u8, Option<String>),
+    BlockQuote,
+    List {
+        start: Option<u64>,
+        tight: bool,
+        items: Vec<ListItem>,
+    },
+    Item {
+        tight: bool,
+    },
+    FootnoteDefinition(String),
+    Table {
+        alignments: Vec<Alignment>,
+        head: Vec<Vec<Inline>>,
+        rows: Vec<Vec<Vec<Inline>>>,
+    },
+    TableHead(Vec<Vec<Inline>>),
+    TableRow(Vec<Vec<Inline>>),
+    TableCell,
+    Emphasis,
+    Strong,
+    Strikethrough,
+    Link {
+        url: String,
+        title: String,
+    },
+    Image {
+        url: String,
+        title: String,
+    },
+    CodeBlock {
+        info: String,
+        text: String,
+    },
+    HtmlBlock(String),
+    /// Markdown extensions that are not enabled; content goes to the parent
+    Other,
+}
+
+impl Frame {
+    fn new(kind: FrameKind, range: Range<usize>) -> Self {
+        Self {
+            kind,
+            range,
+            blocks: Vec::new(),
+            inlines: Vec::new(),
+        }
+    }
+
+    /// Whether block nodes may be added to the frame.
+    fn holds_blocks(&self) -> bool {
+        matches!(
+            self.kind,
+            FrameKind::Root
+                | FrameKind::BlockQuote
+                | FrameKind::Item { .. }
+                | FrameKind::FootnoteDefinition(_)
+                | FrameKind::Other
+        )
+    }
+
+    /// Move inline content held directly by a block container, as in tight
+    /// list items, into a paragraph.
+    fn flush_inlines(&mut self, span: Span) {
+        if self.inlines.is_empty() {
+            return;
+        }
+        if let FrameKind::Item { tight } = &mut self.kind {
+            *tight = true;
+        }
+        self.blocks.push(Node::Paragraph {
+            children: std::mem::take(&mut self.inlines),
+            span,
+        });
+    }
+}
+
+impl<'s> TreeBuilder<'s> {
+    fn new(source: &'s str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            source,
+            line_starts,
+        }
+    }
+
+    fn span(&self, range: Range<usize>) -> Span {
+        let line = self
+            .line_starts
+            .partition_point(|&start| start <= range.start);
+        let line_start = self.line_starts[line - 1];
+        Span {
+            start: range.start,
+            end: range.end,
+            line,
+            column: self.source[line_start..range.start].chars().count() + 1,
+        }
+    }
+
+    /// Parse the Markdown in `region` of the source. ESM is only read at the
+    /// top level of the document.
+    fn parse(&self, region: Range<usize>, top_level: bool) -> Result<Vec<Node>, ParseError> {
+        let options = Options::ENABLE_TABLES
+            | Options::ENABLE_FOOTNOTES
+            | Options::ENABLE_STRIKETHROUGH
+            | Options::ENABLE_TASKLISTS
+            | Options::ENABLE_HEADING_ATTRIBUTES;
+
+        let offset = region.start;
+        let parser = Parser::new_ext(&self.source[region.clone()], options).into_offset_iter();
+
+        let mut frames = vec![Frame::new(FrameKind::Root, region.clone())];
+        // Events of source already read into a node, such as an ESM block,
+        // a JSX element or a directive
+        let mut skip = 0..0;
+
+        for (event, range) in parser {
+            let range = range.start + offset..range.end + offset;
+            if skip.contains(&range.start) {
+                continue;
             }
+            // Blocks read straight from the source must not be nested in
+            // lists or quotes, whose markers would end up in their content
+            let at_root = frames.len() == 1;
 
-            Event::Text(text) => {
-                if let Some((ref info, start_line)) = current_code_block {
-                    let language = Language::from_info(info);
-                    let mode = BlockMode::from_info(info);
-                    let filename = extract_filename(info);
+            match event {
+                Event::Start(Tag::Paragraph) => {
+                    match self.source_block(range.clone(), at_root, top_level, region.end)? {
+                        Some((node, end)) => {
+                            skip = range.start..end.max(range.end);
+                            self.push_block(&mut frames, node);
+                        }
+                        None => frames.push(Frame::new(FrameKind::Paragraph, range)),
+                    }
+                }
 
-                    let mut block = CodeBlock::new(language, mode, text.to_string(), start_line);
-                    block.filename = filename;
-                    code_blocks.push(block);
-                } else if let Some((level, ref mut heading_text)) = current_heading {
-                    heading_text.push_str(&text);
-                    let _ = level; // Suppress unused warning
+                Event::Start(Tag::HtmlBlock) => {
+                    let raw = &self.source[range.clone()];
+                    let start = range.start + raw.len() - raw.trim_start().len();
+                    if let Some(name) = component_name(&self.source[start..]) {
+                        let end = element_end(self.source, start)
+                            .ok_or_else(|| self.unclosed(name, start))?;
+                        let element = self.element(start..end, false, at_root)?;
+                        skip = range.start..end;
+                        self.push_block(&mut frames, Node::Jsx(element));
+                    } else {
+                        frames.push(Frame::new(FrameKind::HtmlBlock(String::new()), range));
+                    }
                 }
 
-                // Count newlines in text for line tracking
-                line_number += text.matches('\n').count();
+                Event::Start(tag) => {
+                    let kind = match tag {
+                        Tag::Heading { level, id, .. } => {
+                            FrameKind::Heading(level as u8, id.map(|id| id.to_string()))
+                        }
+                        Tag::BlockQuote(_) => FrameKind::BlockQuote,
+                        Tag::List(start) => FrameKind::List {
+                            start,
+                            tight: false,
+                            items: Vec::new(),
+                        },
+                        Tag::Item => FrameKind::Item { tight: false },
+                        Tag::FootnoteDefinition(label) => {
+                            FrameKind::FootnoteDefinition(label.to_string())
+                        }
+                        Tag::Table(alignments) => FrameKind::Table {
+                            alignments: alignments.into_iter().map(alignment).collect(),
+                            head: Vec::new(),
+                            rows: Vec::new(),
+                        },
+                        Tag::TableHead => FrameKind::TableHead(Vec::new()),
+                        Tag::TableRow => FrameKind::TableRow(Vec::new()),
+                        Tag::TableCell => FrameKind::TableCell,
+                        Tag::Emphasis => FrameKind::Emphasis,
+                        Tag::Strong => FrameKind::Strong,
+                        Tag::Strikethrough => FrameKind::Strikethrough,
+                        Tag::Link {
+                            link_type,
+                            dest_url,
+                            title,
+                            ..
+                        } => FrameKind::Link {
+                            url: match link_type {
+                                LinkType::Email => format!("mailto:{dest_url}"),
+                                _ => dest_url.to_string(),
+                            },
+                            title: title.to_string(),
+                        },
+                        Tag::Image {
+                            dest_url, title, ..
+                        } => FrameKind::Image {
+                            url: dest_url.to_string(),
+                            title: title.to_string(),
+                        },
+                        Tag::CodeBlock(kind) => FrameKind::CodeBlock {
+                            info: match kind {
+                                CodeBlockKind::Fenced(info) => info.to_string(),
+                                CodeBlockKind::Indented => String::new(),
+                            },
+                            text: String::new(),
+                        },
+                        _ => FrameKind::Other,
+                    };
+                    frames.push(Frame::new(kind, range));
+                }
+
+                Event::End(_) if frames.len() > 1 => self.finish(&mut frames),
+
+                Event::Text(text) => match &mut frames.last_mut().expect("root frame").kind {
+                    FrameKind::CodeBlock { text: code, .. } => code.push_str(&text),
+                    FrameKind::HtmlBlock(html) => html.push_str(&text),
+                    _ => push_inline(&mut frames, Inline::Text(text.to_string())),
+                },
+
+                Event::Html(html) => {
+                    let top = frames.last_mut().expect("root frame");
+                    let holds_blocks = top.holds_blocks();
+                    match &mut top.kind {
+                        FrameKind::HtmlBlock(block) => block.push_str(&html),
+                        _ if holds_blocks => {
+                            let node = Node::Html {
+                                html: html.to_string(),
+                                span: self.span(range),
+                            };
+                            self.push_block(&mut frames, node);
+                        }
+                        _ => push_inline(&mut frames, Inline::Html(html.to_string())),
+                    }
+                }
+
+                Event::InlineHtml(html) => {
+                    if let Some(name) = component_name(&html) {
+                        let end = element_end(self.source, range.start)
+                            .ok_or_else(|| self.unclosed(name, range.start))?;
+                        let element = self.element(range.start..end, true, false)?;
+                        skip = range.start..end;
+                        push_inline(&mut frames, Inline::Jsx(element));
+                    } else {
+                        push_inline(&mut frames, Inline::Html(html.to_string()));
+                    }
+                }
+
+                Event::Code(code) => push_inline(&mut frames, Inline::Code(code.to_string())),
+                Event::FootnoteReference(label) => {
+                    push_inline(&mut frames, Inline::FootnoteReference(label.to_string()))
+                }
+                Event::TaskListMarker(checked) => {
+                    push_inline(&mut frames, Inline::TaskMarker(checked))
+                }
+                Event::SoftBreak => push_inline(&mut frames, Inline::SoftBreak),
+                Event::HardBreak => push_inline(&mut frames, Inline::HardBreak),
+                Event::Rule => {
+                    let node = Node::ThematicBreak {
+                        span: self.span(range),
+                    };
+                    self.push_block(&mut frames, node);
+                }
+
+                _ => {}
             }
+        }
+
+        while frames.len() > 1 {
+            self.finish(&mut frames);
+        }
+        let mut root = frames.pop().expect("root frame");
+        root.flush_inlines(self.span(region));
+        Ok(root.blocks)
+    }
+
+    /// Read a paragraph that is really an ESM block, a directive or a JSX
+    /// element on its own, returning the node and the end of its source.
+    fn source_block(
+        &self,
+        range: Range<usize>,
+        at_root: bool,
+        top_level: bool,
+        limit: usize,
+    ) -> Result<Option<(Node, usize)>, ParseError> {
+        let text = &self.source[range.clone()];
 
-            Event::End(TagEnd::CodeBlock) => {
-                current_code_block = None;
+        if at_root && top_level {
+            if let Some(kind) = esm_kind(text) {
+                let end = esm_end(self.source, range.start, range.end, limit);
+                let source = self.source[range.start..end].trim_end();
+                if let Err((message, error)) = check_module(source) {
+                    let start = range.start + error.start.min(source.len());
+                    let end = (range.start + error.end).clamp(start, range.start + source.len());
+                    return Err(ParseError::Parse(
+                        Diagnostic::new(format!("Invalid ESM: {message}"), self.span(start..end))
+                            .with_help(
+                                "paragraphs starting with `import` or `export` are code; \
+                                 capitalize the first word if this is prose",
+                            ),
+                    ));
+                }
+                let esm = EsmBlock {
+                    kind,
+                    source: source.to_string(),
+                    span: self.span(range.start..range.start + source.len()),
+                };
+                return Ok(Some((Node::Esm(esm), end)));
             }
+        }
+
+        if at_root {
+            let line_end = text.find('\n').map_or(range.end, |i| range.start + i + 1);
+            if let Some(fence) = directive_fence(&self.source[range.start..line_end]) {
+                let (content, end) = directive_end(self.source, line_end, fence.colons, limit);
+                let children = self.parse(content, false)?;
+                let span = self.span(range.start..self.source[..end].trim_end().len());
 
-            Event::Start(Tag::Heading { level, .. }) => {
-                current_heading = Some((level as u8, String::new()));
+                let node = match AdmonitionKind::from_name(&fence.name) {
+                    Some(kind) => Node::Admonition(Admonition {
+                        kind,
+                        title: fence.label,
+                        children,
+                        span,
+                    }),
+                    None => Node::Directive(Directive {
+                        name: fence.name,
+                        label: fence.label,
+                        attributes: fence.attributes,
+                        children,
+                        span,
+                    }),
+                };
+                return Ok(Some((node, end)));
             }
+        }
+
+        // An element that makes up a whole paragraph is a flow element.
+        // Unclosed elements are reported with the paragraph's inline HTML.
+        if component_name(text).is_none() {
+            return Ok(None);
+        }
+        match element_end(self.source, range.start) {
+            Some(end) if end >= range.start + text.trim_end().len() => {
+                let element = self.element(range.start..end, false, at_root)?;
+                Ok(Some((Node::Jsx(element), end)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Build the JSX element spanning `range`, parsing the Markdown content
+    /// of wrapping flow elements.
+    fn element(
+        &self,
+        range: Range<usize>,
+        inline: bool,
+        at_root: bool,
+    ) -> Result<JsxElement, ParseError> {
+        let source = self.source[range.clone()].trim_end();
+        let range = range.start..range.start + source.len();
+
+        let children = match element_content(self.source, range.clone()) {
+            Some(content) if !inline && at_root => self.parse(content, false)?,
+            _ => Vec::new(),
+        };
 
-            Event::End(TagEnd::Heading(_)) => {
-                if let Some((level, title)) = current_heading.take() {
-                    let id = slugify(&title);
-                    toc.push(TocEntry { title, id, level });
+        Ok(JsxElement {
+            name: component_name(source).unwrap_or_default().to_string(),
+            source: source.to_string(),
+            span: self.span(range),
+            inline,
+            children,
+        })
+    }
+
+    /// Error for a component element without a closing tag.
+    fn unclosed(&self, name: &str, start: usize) -> ParseError {
+        let end = tag_end(self.source, start).map_or(start + 1 + name.len(), |(end, _)| end);
+        ParseError::Parse(
+            Diagnostic::new(format!("Unclosed element <{name}>"), self.span(start..end))
+                .with_help(format!("close it with </{name}>, or end the tag with />")),
+        )
+    }
+
+    /// Add a block node to the innermost frame.
+    fn push_block(&self, frames: &mut [Frame], node: Node) {
+        let frame = frames.last_mut().expect("root frame");
+        frame.flush_inlines(self.span(frame.range.clone()));
+        frame.blocks.push(node);
+    }
+
+    /// Close the innermost frame and add its node to the parent.
+    fn finish(&self, frames: &mut Vec<Frame>) {
+        let mut frame = frames.pop().expect("open frame");
+        let span = self.span(frame.range.clone());
+        if frame.holds_blocks() {
+            frame.flush_inlines(span);
+        }
+
+        let node = match frame.kind {
+            FrameKind::Paragraph => Node::Paragraph {
+                children: frame.inlines,
+                span,
+            },
+            FrameKind::Heading(level, id) => Node::Heading {
+                level,
+                // Left empty for `assign_heading_ids`
+                id: id.unwrap_or_default(),
+                children: frame.inlines,
+                span,
+            },
+            FrameKind::BlockQuote => Node::BlockQuote {
+                children: frame.blocks,
+                span,
+            },
+            FrameKind::List {
+                start,
+                tight,
+                items,
+            } => Node::List {
+                start,
+                tight,
+                items,
+                span,
+            },
+            FrameKind::Item { tight } => {
+                if let Some(FrameKind::List {
+                    tight: list_tight,
+                    items,
+                    ..
+                }) = frames.last_mut().map(|parent| &mut parent.kind)
+                {
+                    *list_tight |= tight;
+                    items.push(ListItem {
+                        children: frame.blocks,
+                        span,
+                    });
+                }
+                return;
+            }
+            FrameKind::FootnoteDefinition(label) => Node::FootnoteDefinition {
+                label,
+                children: frame.blocks,
+                span,
+            },
+            FrameKind::Table {
+                alignments,
+                head,
+                rows,
+            } => Node::Table {
+                alignments,
+                head,
+                rows,
+                span,
+            },
+            FrameKind::TableHead(cells) => {
+                if let Some(FrameKind::Table { head, .. }) =
+                    frames.last_mut().map(|parent| &mut parent.kind)
+                {
+                    *head = cells;
+                }
+                return;
+            }
+            FrameKind::TableRow(cells) => {
+                if let Some(FrameKind::Table { rows, .. }) =
+                    frames.last_mut().map(|parent| &mut parent.kind)
+                {
+                    rows.push(cells);
                 }
+                return;
             }
+            FrameKind::TableCell => {
+                if let Some(FrameKind::TableHead(cells) | FrameKind::TableRow(cells)) =
+                    frames.last_mut().map(|parent| &mut parent.kind)
+                {
+                    cells.push(frame.inlines);
+                }
+                return;
+            }
+            FrameKind::Emphasis => return push_inline(frames, Inline::Emphasis(frame.inlines)),
+            FrameKind::Strong => return push_inline(frames, Inline::Strong(frame.inlines)),
+            FrameKind::Strikethrough => {
+                return push_inline(frames, Inline::Strikethrough(frame.inlines))
+            }
+            FrameKind::Link { url, title } => {
+                let link = Inline::Link {
+                    url,
+                    title,
+                    children: frame.inlines,
+                };
+                return push_inline(frames, link);
+            }
+            FrameKind::Image { url, title } => {
+                let image = Inline::Image {
+                    url,
+                    title,
+                    children: frame.inlines,
+                };
+                return push_inline(frames, image);
+            }
+            FrameKind::CodeBlock { info, text } => {
+                let mut block = CodeBlock::from_fence(info, &text, span.line);
+                block.span = span;
+                Node::Code(block)
+            }
+            FrameKind::HtmlBlock(html) => Node::Html { html, span },
+            FrameKind::Other | FrameKind::Root => {
+                let parent = frames.last_mut().expect("root frame");
+                parent.blocks.append(&mut frame.blocks);
+                parent.inlines.append(&mut frame.inlines);
+                return;
+            }
+        };
 
-            Event::SoftBreak | Event::HardBreak => {
-                line_number += 1;
+        self.push_block(frames, node);
+    }
+}
+
+/// Add an inline node to the innermost frame, merging adjacent text.
+fn push_inline(frames: &mut [Frame], inline: Inline) {
+    let inlines = &mut frames.last_mut().expect("root frame").inlines;
+    match (inlines.last_mut(), inline) {
+        (Some(Inline::Text(text)), Inline::Text(more)) => text.push_str(&more),
+        (_, inline) => inlines.push(inline),
+    }
+}
+
+fn alignment(alignment: pulldown_cmark::Alignment) -> Alignment {
+    match alignment {
+        pulldown_cmark::Alignment::None => Alignment::None,
+        pulldown_cmark::Alignment::Left => Alignment::Left,
+        pulldown_cmark::Alignment::Center => Alignment::Center,
+        pulldown_cmark::Alignment::Right => Alignment::Right,
+    }
+}
+
+/// Give headings without a `{#custom-id}` an ID slugified from their text.
+/// Custom IDs are kept as written, and a slug already in use gets a `-1`,
+/// `-2`, ... suffix.
+fn assign_heading_ids(nodes: &mut [Node]) {
+    let mut used = HashSet::new();
+    walk(nodes, &mut |node| {
+        if let Node::Heading { id, .. } = node {
+            if !id.is_empty() {
+                used.insert(id.clone());
             }
+        }
+    });
 
-            _ => {}
+    walk_mut(nodes, &mut |node| {
+        if let Node::Heading { id, children, .. } = node {
+            if !id.is_empty() {
+                return;
+            }
+            let mut slug = slugify(plain_text(children).trim());
+            if slug.is_empty() {
+                slug = "section".to_string();
+            }
+            let mut unique = slug.clone();
+            let mut n = 0;
+            while used.contains(&unique) {
+                n += 1;
+                unique = format!("{slug}-{n}");
+            }
+            used.insert(unique.clone());
+            *id = unique;
         }
-    }
+    });
+}
 
-    Ok(ParsedDoc {
-        frontmatter,
-        content: content.to_string(),
-        code_blocks,
-        toc,
-    })
+/// Make code block ids unique. Blocks of included partials are numbered by
+/// the lines of their own file, so they can share ids with the page's blocks
+/// or with another include of the same partial; later ones get a suffix.
+fn assign_block_ids(nodes: &mut [Node]) {
+    let mut used = HashSet::new();
+    walk_mut(nodes, &mut |node| {
+        if let Node::Code(block) = node {
+            let mut unique = block.id.clone();
+            let mut n = 0;
+            while used.contains(&unique) {
+                n += 1;
+                unique = format!("{}-{n}", block.id);
+            }
+            used.insert(unique.clone());
+            block.id = unique;
+        }
+    });
 }
 
 /// Convert a heading to a URL-safe slug.
@@ -151,6 +835,8 @@ fn slugify(text: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::codeblock::{BlockMode, Language};
+    use crate::esm::EsmKind;
 
     #[test]
     fn parses_complete_mdx() {
@@ -191,17 +877,21 @@ Different button styles.
         assert_eq!(live_block.mode, BlockMode::Live);
         assert!(live_block.source.contains("variant=\"primary\""));
 
+        assert_eq!(live_block.line_number, 10);
+        assert_eq!(live_block.id, "block-10");
+
         let source_block = &doc.code_blocks[1];
         assert_eq!(source_block.language, Language::Tsx);
         assert_eq!(source_block.mode, BlockMode::Source);
 
         // Check TOC
-        assert_eq!(doc.toc.len(), 2);
+        assert_eq!(doc.toc.len(), 1);
         assert_eq!(doc.toc[0].title, "Button");
         assert_eq!(doc.toc[0].level, 1);
         assert_eq!(doc.toc[0].id, "button");
-        assert_eq!(doc.toc[1].title, "Variants");
-        assert_eq!(doc.toc[1].level, 2);
+        assert_eq!(doc.toc[0].children.len(), 1);
+        assert_eq!(doc.toc[0].children[0].title, "Variants");
+        assert_eq!(doc.toc[0].children[0].level, 2);
     }
 
     #[test]
@@ -241,6 +931,211 @@ Different button styles.
         assert_eq!(live_blocks.len(), 2);
     }
 
+    #[test]
+    fn parses_esm_and_jsx() {
+        let source = r#"---
+title: Button
+---
+import { Button } from '../components/button';
+import { Badge } from '../components/badge';
+
+export const meta = {
+  status: 'stable',
+};
+
+# Button <Badge tone="info">New</Badge>
+
+Press <Button size="sm">Save</Button> to continue.
+
+<Button variant="outline">
+  Outline
+</Button>
+
+<Card title="Inline form">Alone in its paragraph</Card>
+
+- import is not ESM inside a list
+
+```tsx
+<Button>Not prose</Button>
+```
+"#;
+
+        let doc = parse_mdx(source).unwrap();
+
+        assert_eq!(doc.esm.len(), 2);
+        assert_eq!(doc.esm[0].kind, EsmKind::Import);
+        assert_eq!(doc.esm[0].span.line, 4);
+        assert_eq!(doc.esm[0].imported_names(), vec!["Button", "Badge"]);
+        assert_eq!(doc.esm[1].kind, EsmKind::Export);
+        assert!(doc.esm[1].source.ends_with("};"));
+
+        let elements: Vec<_> = doc
+            .jsx
+            .iter()
+            .map(|e| (e.name.as_str(), e.inline, e.span.line))
+            .collect();
+        assert_eq!(
+            elements,
+            vec![
+                ("Badge", true, 11),
+                ("Button", true, 13),
+                ("Button", false, 15),
+                ("Card", false, 19),
+            ]
+        );
+        assert_eq!(doc.jsx[1].source, r#"<Button size="sm">Save</Button>"#);
+        assert_eq!(&source[doc.jsx[2].span.range()], doc.jsx[2].source);
+        assert!(doc.jsx[2].source.ends_with("</Button>"));
+        assert!(matches!(doc.jsx[2].children[..], [Node::Paragraph { .. }]));
+        assert!(doc.jsx[3].children.is_empty());
+
+        assert_eq!(doc.toc[0].title, "Button");
+        assert_eq!(doc.code_blocks.len(), 1);
+    }
+
+    #[test]
+    fn keeps_prose_starting_with_import_or_export() {
+        let source = "Import settings are described below.\n\nExport the component as a default export.\n\nimport settings from the panel, then export the file.\n\nexport it as a default export.\n";
+        let doc = parse_mdx(source).unwrap();
+
+        assert!(doc.esm.is_empty());
+        assert_eq!(doc.nodes.len(), 4);
+        assert!(doc
+            .nodes
+            .iter()
+            .all(|node| matches!(node, Node::Paragraph { .. })));
+    }
+
+    #[test]
+    fn reads_esm_across_blank_lines() {
+        let source =
+            "export const meta = {\n  status: 'stable',\n\n  tags: ['form'],\n};\n\n# Button\n";
+        let doc = parse_mdx(source).unwrap();
+
+        assert_eq!(doc.esm.len(), 1);
+        assert_eq!(doc.esm[0].source, source[..source.find("\n\n# ").unwrap()]);
+        assert_eq!(doc.nodes.len(), 2);
+        assert_eq!(doc.toc[0].title, "Button");
+    }
+
+    #[test]
+    fn reports_invalid_esm() {
+        let Err(ParseError::Parse(diagnostic)) =
+            parse_mdx("# Button\n\nimport { Button from './button';\n")
+        else {
+            panic!("expected an ESM error");
+        };
+        assert!(
+            diagnostic.message.starts_with("Invalid ESM"),
+            "{}",
+            diagnostic.message
+        );
+        assert_eq!(diagnostic.span.line, 3);
+    }
+
+    #[test]
+    fn builds_document_tree() {
+        let source = r#"# Install
+
+- one
+- two **bold**
+
+:::details[Heads up]{.wide}
+Run this first:
+
+```sh
+veneer init
+```
+:::
+
+:::warning Breaking change
+Removed `size="xs"`.
+:::
+
+::::tip
+Nested:
+
+:::note
+Inner
+:::
+::::
+
+Done.
+"#;
+
+        let doc = parse_mdx(source).unwrap();
+
+        let [Node::Heading {
+            level: 1, id, span, ..
+        }, Node::List {
+            tight: true, items, ..
+        }, Node::Directive(directive), Node::Admonition(warning), Node::Admonition(tip), Node::Paragraph { children, .. }] =
+            &doc.nodes[..]
+        else {
+            panic!("unexpected tree: {:#?}", doc.nodes);
+        };
+        assert_eq!(id, "install");
+        assert_eq!((span.start, span.end, span.line), (0, 10, 1));
+        assert_eq!(items.len(), 2);
+        assert!(matches!(
+            &items[1].children[..],
+            [Node::Paragraph { children, .. }] if matches!(&children[..], [Inline::Text(t), Inline::Strong(_)] if t == "two ")
+        ));
+
+        assert_eq!(directive.name, "details");
+        assert_eq!(directive.label.as_deref(), Some("Heads up"));
+        assert_eq!(directive.attribute("class"), Some("wide"));
+        assert_eq!(directive.span.line, 6);
+        assert!(source[directive.span.range()].ends_with(":::"));
+        let [Node::Paragraph { .. }, Node::Code(block)] = &directive.children[..] else {
+            panic!("unexpected directive children: {:#?}", directive.children);
+        };
+        assert_eq!(block.span.line, 9);
+        assert_eq!(block.source, "veneer init\n");
+
+        assert_eq!(warning.kind, AdmonitionKind::Warning);
+        assert_eq!(warning.title(), "Breaking change");
+        assert_eq!(tip.title(), "Tip");
+        assert!(matches!(
+            &tip.children[..],
+            [Node::Paragraph { .. }, Node::Admonition(note)] if note.kind == AdmonitionKind::Note
+        ));
+
+        assert_eq!(children, &vec![Inline::Text("Done.".to_string())]);
+    }
+
+    #[test]
+    fn reports_positions() {
+        let source = "---\ntitle: Test\n---\n\nIntro with `code`.\n\n  ## Usage\n\n- item\n\n  ```tsx live\n  <Button />\n  ```\n";
+
+        let doc = parse_mdx(source).unwrap();
+        let heading = doc
+            .nodes
+            .iter()
+            .find(|node| matches!(node, Node::Heading { .. }))
+            .unwrap()
+            .span();
+        assert_eq!((heading.line, heading.column), (7, 3));
+        assert_eq!(&source[heading.range()], "## Usage\n");
+
+        let block = &doc.code_blocks[0];
+        assert_eq!((block.span.line, block.span.column), (11, 3));
+        assert_eq!(block.line_number, 11);
+
+        let source = "# Title\n\nPress <Button>Save to continue.\n";
+        let Err(ParseError::Parse(diagnostic)) = parse_mdx(source) else {
+            panic!("expected an unclosed element error");
+        };
+        assert_eq!(diagnostic.message, "Unclosed element <Button>");
+        assert_eq!((diagnostic.span.line, diagnostic.span.column), (3, 7));
+        assert_eq!(&source[diagnostic.span.range()], "<Button>");
+
+        let Err(error) = parse_mdx("---\ntitle: [oops\n---\n") else {
+            panic!("expected a frontmatter error");
+        };
+        assert_eq!(error.diagnostic().span.line, 2);
+    }
+
     #[test]
     fn slugify_works() {
         assert_eq!(slugify("Hello World"), "hello-world");
@@ -248,4 +1143,44 @@ Different button styles.
         assert_eq!(slugify("Button (Primary)"), "button-primary");
         assert_eq!(slugify("  Multiple   Spaces  "), "multiple-spaces");
     }
+
+    #[test]
+    fn assigns_unique_heading_ids() {
+        let doc = parse_mdx(
+            "# Usage\n\n## Usage\n\n## Props {#usage-1}\n\n:::note\n## Usage\n:::\n\n## ???\n",
+        )
+        .unwrap();
+
+        let toc = doc.toc_between(1, 6);
+        let ids: Vec<_> = toc[0]
+            .children
+            .iter()
+            .map(|entry| entry.id.as_str())
+            .collect();
+        assert_eq!(toc[0].id, "usage");
+        assert_eq!(ids, vec!["usage-2", "usage-1", "usage-3", "section"]);
+        assert_eq!(toc[0].children[1].title, "Props");
+    }
+
+    #[test]
+    fn nests_toc() {
+        let doc = parse_mdx(
+            "# Button\n\n## Usage\n\n### Sizes\n\n#### Small\n\n### Colors\n\n## Props\n\n# Related\n",
+        )
+        .unwrap();
+
+        assert_eq!(doc.toc.len(), 2);
+        let usage = &doc.toc[0].children[0];
+        assert_eq!(usage.title, "Usage");
+        assert_eq!(usage.children.len(), 2);
+        assert_eq!(usage.children[0].children[0].title, "Small");
+        assert_eq!(doc.toc[0].children[1].title, "Props");
+        assert!(doc.toc[1].children.is_empty());
+
+        let toc = doc.toc_between(2, 3);
+        let titles: Vec<_> = toc.iter().map(|entry| entry.title.as_str()).collect();
+        assert_eq!(titles, vec!["Usage", "Props"]);
+        assert_eq!(toc[0].children.len(), 2);
+        assert!(toc[0].children[0].children.is_empty());
+    }
 }
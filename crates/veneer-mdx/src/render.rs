@@ -0,0 +1,314 @@
+//! HTML rendering of the document tree.
+
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, HeadingLevel, LinkType, Tag, TagEnd};
+
+use crate::ast::{Alignment, Inline, Node};
+
+/// Render nodes to HTML.
+///
+/// Markdown renders as CommonMark HTML. Raw HTML and JSX elements that were
+/// not replaced pass through as written, ESM blocks render nothing, and
+/// directives and admonitions render as plain
+/// `<div class="directive directive-{name}">` containers. Code groups render
+/// their blocks in a `<div class="code-group">`; tabs and other themed
+/// markup are up to the site renderer.
+pub fn render_html(nodes: &[Node]) -> String {
+    let mut events = Vec::new();
+    for node in nodes {
+        block_events(node, false, &mut events);
+    }
+
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, events.into_iter());
+    html
+}
+
+/// Escape text for HTML content and attribute values.
+pub fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Push the events of a block. Paragraphs in tight list items render
+/// without `<p>` tags.
+fn block_events<'a>(node: &'a Node, tight: bool, events: &mut Vec<Event<'a>>) {
+    match node {
+        Node::Heading {
+            level,
+            id,
+            children,
+            ..
+        } => {
+            let level = HeadingLevel::try_from(*level as usize).unwrap_or(HeadingLevel::H6);
+            events.push(Event::Start(Tag::Heading {
+                level,
+                id: Some(id.as_str().into()),
+                classes: Vec::new(),
+                attrs: Vec::new(),
+            }));
+            inline_events(children, events);
+            events.push(Event::End(TagEnd::Heading(level)));
+        }
+
+        Node::Paragraph { children, .. } if tight => inline_events(children, events),
+
+        Node::Paragraph { children, .. } => {
+            events.push(Event::Start(Tag::Paragraph));
+            inline_events(children, events);
+            events.push(Event::End(TagEnd::Paragraph));
+        }
+
+        Node::Code(block) => {
+            let kind = CodeBlockKind::Fenced(block.lang().unwrap_or_default().into());
+            events.push(Event::Start(Tag::CodeBlock(kind)));
+            events.push(Event::Text(block.source.as_str().into()));
+            events.push(Event::End(TagEnd::CodeBlock));
+        }
+
+        Node::Jsx(element) if element.children.is_empty() => {
+            events.push(Event::Html(element.source.as_str().into()));
+            events.push(Event::Html("\n".into()));
+        }
+
+        Node::Jsx(element) => {
+            events.push(Event::Html(element.opening_tag().into()));
+            events.push(Event::Html("\n".into()));
+            for child in &element.children {
+                block_events(child, false, events);
+            }
+            events.push(Event::Html(element.closing_tag().into()));
+            events.push(Event::Html("\n".into()));
+        }
+
+        Node::Esm(_) => {}
+
+        Node::Directive(directive) => {
+            events.push(Event::Html(CowStr::from(format!(
+                "<div class=\"directive directive-{}\">\n",
+                directive.name
+            ))));
+            for child in &directive.children {
+                block_events(child, false, events);
+            }
+            events.push(Event::Html("</div>\n".into()));
+        }
+
+        Node::Admonition(admonition) => {
+            events.push(Event::Html(CowStr::from(format!(
+                "<div class=\"directive directive-{}\">\n",
+                admonition.kind.as_str()
+            ))));
+            for child in &admonition.children {
+                block_events(child, false, events);
+            }
+            events.push(Event::Html("</div>\n".into()));
+        }
+
+        Node::CodeGroup(group) => {
+            events.push(Event::Html("<div class=\"code-group\">\n".into()));
+            for child in &group.children {
+                block_events(child, false, events);
+            }
+            events.push(Event::Html("</div>\n".into()));
+        }
+
+        Node::Html { html, .. } => events.push(Event::Html(html.as_str().into())),
+
+        Node::BlockQuote { children, .. } => {
+            events.push(Event::Start(Tag::BlockQuote(None)));
+            for child in children {
+                block_events(child, false, events);
+            }
+            events.push(Event::End(TagEnd::BlockQuote(None)));
+        }
+
+        Node::List {
+            start,
+            tight,
+            items,
+            ..
+        } => {
+            events.push(Event::Start(Tag::List(*start)));
+            for item in items {
+                events.push(Event::Start(Tag::Item));
+                for child in &item.children {
+                    block_events(child, *tight, events);
+                }
+                events.push(Event::End(TagEnd::Item));
+            }
+            events.push(Event::End(TagEnd::List(start.is_some())));
+        }
+
+        Node::Table {
+            alignments,
+            head,
+            rows,
+            ..
+        } => {
+            let alignments = alignments.iter().map(|a| alignment(*a)).collect();
+            events.push(Event::Start(Tag::Table(alignments)));
+            events.push(Event::Start(Tag::TableHead));
+            cell_events(head, events);
+            events.push(Event::End(TagEnd::TableHead));
+            for row in rows {
+                events.push(Event::Start(Tag::TableRow));
+                cell_events(row, events);
+                events.push(Event::End(TagEnd::TableRow));
+            }
+            events.push(Event::End(TagEnd::Table));
+        }
+
+        Node::FootnoteDefinition {
+            label, children, ..
+        } => {
+            events.push(Event::Start(Tag::FootnoteDefinition(label.as_str().into())));
+            for child in children {
+                block_events(child, false, events);
+            }
+            events.push(Event::End(TagEnd::FootnoteDefinition));
+        }
+
+        Node::ThematicBreak { .. } => events.push(Event::Rule),
+    }
+}
+
+fn cell_events<'a>(cells: &'a [Vec<Inline>], events: &mut Vec<Event<'a>>) {
+    for cell in cells {
+        events.push(Event::Start(Tag::TableCell));
+        inline_events(cell, events);
+        events.push(Event::End(TagEnd::TableCell));
+    }
+}
+
+fn inline_events<'a>(inlines: &'a [Inline], events: &mut Vec<Event<'a>>) {
+    for inline in inlines {
+        match inline {
+            Inline::Text(text) => events.push(Event::Text(text.as_str().into())),
+            Inline::Code(code) => events.push(Event::Code(code.as_str().into())),
+            Inline::Html(html) => events.push(Event::InlineHtml(html.as_str().into())),
+            Inline::Jsx(element) => events.push(Event::InlineHtml(element.source.as_str().into())),
+            Inline::Emphasis(children) => {
+                events.push(Event::Start(Tag::Emphasis));
+                inline_events(children, events);
+                events.push(Event::End(TagEnd::Emphasis));
+            }
+            Inline::Strong(children) => {
+                events.push(Event::Start(Tag::Strong));
+                inline_events(children, events);
+                events.push(Event::End(TagEnd::Strong));
+            }
+            Inline::Strikethrough(children) => {
+                events.push(Event::Start(Tag::Strikethrough));
+                inline_events(children, events);
+                events.push(Event::End(TagEnd::Strikethrough));
+            }
+            Inline::Link {
+                url,
+                title,
+                children,
+            } => {
+                events.push(Event::Start(Tag::Link {
+                    link_type: LinkType::Inline,
+                    dest_url: url.as_str().into(),
+                    title: title.as_str().into(),
+                    id: CowStr::Borrowed(""),
+                }));
+                inline_events(children, events);
+                events.push(Event::End(TagEnd::Link));
+            }
+            Inline::Image {
+                url,
+                title,
+                children,
+            } => {
+                events.push(Event::Start(Tag::Image {
+                    link_type: LinkType::Inline,
+                    dest_url: url.as_str().into(),
+                    title: title.as_str().into(),
+                    id: CowStr::Borrowed(""),
+                }));
+                inline_events(children, events);
+                events.push(Event::End(TagEnd::Image));
+            }
+            Inline::FootnoteReference(label) => {
+                events.push(Event::FootnoteReference(label.as_str().into()))
+            }
+            Inline::TaskMarker(checked) => events.push(Event::TaskListMarker(*checked)),
+            Inline::SoftBreak => events.push(Event::SoftBreak),
+            Inline::HardBreak => events.push(Event::HardBreak),
+        }
+    }
+}
+
+fn alignment(alignment: Alignment) -> pulldown_cmark::Alignment {
+    match alignment {
+        Alignment::None => pulldown_cmark::Alignment::None,
+        Alignment::Left => pulldown_cmark::Alignment::Left,
+        Alignment::Center => pulldown_cmark::Alignment::Center,
+        Alignment::Right => pulldown_cmark::Alignment::Right,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_mdx;
+
+    #[test]
+    fn renders_like_commonmark() {
+        let source = r#"# Title *here* {#title-here}
+
+Text with `code`, a [link](/a "A") and<br>HTML.
+
+- [x] done
+- todo
+
+1. loose
+
+2. list
+
+> quoted
+
+| A | B |
+|:--|--:|
+| 1 | 2 |
+
+Footnote[^1].
+
+[^1]: The note.
+
+---
+
+```tsx live
+<Button />
+```
+"#;
+
+        let options = pulldown_cmark::Options::ENABLE_TABLES
+            | pulldown_cmark::Options::ENABLE_FOOTNOTES
+            | pulldown_cmark::Options::ENABLE_STRIKETHROUGH
+            | pulldown_cmark::Options::ENABLE_TASKLISTS
+            | pulldown_cmark::Options::ENABLE_HEADING_ATTRIBUTES;
+        let mut expected = String::new();
+        pulldown_cmark::html::push_html(
+            &mut expected,
+            pulldown_cmark::Parser::new_ext(source, options),
+        );
+
+        let doc = parse_mdx(source).unwrap();
+        assert_eq!(render_html(&doc.nodes), expected);
+    }
+
+    #[test]
+    fn renders_mdx_nodes() {
+        let source = "import { Tabs } from './tabs';\n\n<Tabs>\n\n```sh\nnpm i\n```\n\n</Tabs>\n\n:::note\nSee *this*.\n:::\n";
+        let html = render_html(&parse_mdx(source).unwrap().nodes);
+
+        assert_eq!(
+            html,
+            "<Tabs>\n<pre><code class=\"language-sh\">npm i\n</code></pre>\n</Tabs>\n<div class=\"directive directive-note\">\n<p>See <em>this</em>.</p>\n</div>\n"
+        );
+    }
+}
@@ -0,0 +1,243 @@
+//! Project-level frontmatter schemas.
+//!
+//! A schema lists fields with their type, whether they are required and the
+//! values they may take, as in `docs.toml`:
+//!
+//! ```toml
+//! [frontmatter.status]
+//! type = "string"
+//! required = true
+//! enum = ["beta", "stable", "deprecated"]
+//!
+//! [frontmatter.tags]
+//! type = "list"
+//! ```
+//!
+//! Fields not in the schema are not checked. Fields are checked the same way
+//! whatever the frontmatter format.
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::diagnostic::Diagnostic;
+use crate::frontmatter::{field_span, frontmatter_span, Frontmatter, FrontmatterValue};
+
+/// Rules for the frontmatter fields of every page.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct FrontmatterSchema {
+    /// Rules by field name
+    pub fields: BTreeMap<String, FieldSchema>,
+}
+
+/// Rules for one frontmatter field.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FieldSchema {
+    /// Type of the value
+    #[serde(default, rename = "type")]
+    pub ty: Option<FieldType>,
+
+    /// Whether every page must set the field
+    #[serde(default)]
+    pub required: bool,
+
+    /// Allowed values. Every item of a list must be one of them.
+    #[serde(default, rename = "enum")]
+    pub values: Option<Vec<String>>,
+}
+
+/// Type of a frontmatter field.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    String,
+    /// Integer or decimal
+    Number,
+    Integer,
+    Boolean,
+    List,
+    Map,
+}
+
+impl FieldType {
+    fn name(self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Integer => "integer",
+            Self::Boolean => "boolean",
+            Self::List => "list",
+            Self::Map => "map",
+        }
+    }
+
+    fn matches(self, value: &FrontmatterValue) -> bool {
+        matches!(
+            (self, value),
+            (Self::String, FrontmatterValue::String(_))
+                | (
+                    Self::Number,
+                    FrontmatterValue::Integer(_) | FrontmatterValue::Float(_)
+                )
+                | (Self::Integer, FrontmatterValue::Integer(_))
+                | (Self::Boolean, FrontmatterValue::Bool(_))
+                | (Self::List, FrontmatterValue::List(_))
+                | (Self::Map, FrontmatterValue::Map(_))
+        )
+    }
+}
+
+impl FrontmatterSchema {
+    /// Whether the schema has no rules.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Check the frontmatter of the document `source` against the schema.
+    pub fn validate(&self, frontmatter: Option<&Frontmatter>, source: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for (name, field) in &self.fields {
+            let value = frontmatter
+                .and_then(|f| f.field(name))
+                .filter(|value| *value != FrontmatterValue::Null);
+            let Some(value) = value else {
+                if field.required {
+                    diagnostics.push(
+                        Diagnostic::new(
+                            format!("Missing required frontmatter field `{name}`"),
+                            frontmatter_span(source),
+                        )
+                        .with_help(format!("add `{name}:` to the frontmatter")),
+                    );
+                }
+                continue;
+            };
+
+            let span = field_span(source, name);
+            if let Some(ty) = field.ty {
+                if !ty.matches(&value) {
+                    diagnostics.push(Diagnostic::new(
+                        format!(
+                            "Frontmatter field `{name}` should be a {}, found a {}",
+                            ty.name(),
+                            value.type_name()
+                        ),
+                        span,
+                    ));
+                    continue;
+                }
+            }
+
+            if let Some(allowed) = &field.values {
+                let items = match &value {
+                    FrontmatterValue::List(items) => items.clone(),
+                    _ => vec![value],
+                };
+                for item in items {
+                    let scalar = item.as_scalar().unwrap_or_default();
+                    if !allowed.contains(&scalar) {
+                        diagnostics.push(
+                            Diagnostic::new(
+                                format!("Frontmatter field `{name}` cannot be `{scalar}`"),
+                                span,
+                            )
+                            .with_help(format!("use one of: {}", allowed.join(", "))),
+                        );
+                    }
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Span;
+    use crate::frontmatter::extract_frontmatter;
+
+    fn schema() -> FrontmatterSchema {
+        toml::from_str(
+            r#"
+[status]
+type = "string"
+required = true
+enum = ["beta", "stable"]
+
+[since]
+type = "string"
+
+[tags]
+type = "list"
+enum = ["input", "form"]
+
+[description]
+required = true
+"#,
+        )
+        .unwrap()
+    }
+
+    fn check(source: &str) -> Vec<Diagnostic> {
+        let (frontmatter, _) = extract_frontmatter(source).unwrap();
+        schema().validate(frontmatter.as_ref(), source)
+    }
+
+    #[test]
+    fn accepts_valid_frontmatter() {
+        let source =
+            "---\ntitle: A\ndescription: B\nstatus: beta\nsince: \"2.3\"\ntags: [form]\n---\n";
+        assert!(check(source).is_empty());
+    }
+
+    #[test]
+    fn reports_violations() {
+        let source = "---\ntitle: A\nstatus: alpha\nsince: 2.3\ntags: [form, layout]\n---\n# A\n";
+        let diagnostics = check(source);
+
+        let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Missing required frontmatter field `description`",
+                "Frontmatter field `since` should be a string, found a number",
+                "Frontmatter field `status` cannot be `alpha`",
+                "Frontmatter field `tags` cannot be `layout`",
+            ]
+        );
+        assert_eq!(diagnostics[0].span.line, 1);
+        assert_eq!(
+            (diagnostics[1].span.line, diagnostics[1].span.column),
+            (4, 1)
+        );
+        assert_eq!(diagnostics[2].span.line, 3);
+        assert_eq!(
+            diagnostics[2].help.as_deref(),
+            Some("use one of: beta, stable")
+        );
+
+        let source = "+++\ntitle = \"A\"\nstatus = \"alpha\"\n+++\n";
+        let diagnostics = check(source);
+        assert_eq!(diagnostics[0].span.line, 1);
+        assert_eq!(
+            (diagnostics[1].span.line, diagnostics[1].span.column),
+            (3, 1)
+        );
+
+        let source = "{\n  \"title\": \"A\",\n  \"status\": \"alpha\"\n}\n";
+        let diagnostics = check(source);
+        assert_eq!(
+            (diagnostics[1].span.line, diagnostics[1].span.column),
+            (3, 4)
+        );
+
+        let diagnostics = check("# No frontmatter\n");
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].span, Span::locate("", 0..0));
+    }
+}
@@ -0,0 +1,132 @@
+//! Which pages include which partials.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use veneer_mdx::parse_mdx_file;
+use veneer_static::is_partial;
+
+/// Pages of the docs directory and the files they include, so a changed
+/// partial rebuilds every page that depends on it.
+#[derive(Debug, Default)]
+pub struct DependencyGraph {
+    /// Included files of each page
+    includes: HashMap<PathBuf, Vec<PathBuf>>,
+}
+
+impl DependencyGraph {
+    /// Scan the pages of a docs directory. Pages that fail to parse have no
+    /// dependencies until they are fixed.
+    pub fn scan(docs_dir: &Path) -> Self {
+        let mut graph = Self::default();
+        for entry in WalkDir::new(docs_dir).into_iter().filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+            let relative = path.strip_prefix(docs_dir).unwrap_or(path);
+            if !entry.file_type().is_file() || !matches!(ext, "mdx" | "md") || is_partial(relative)
+            {
+                continue;
+            }
+
+            let includes = fs::read_to_string(path)
+                .ok()
+                .and_then(|source| parse_mdx_file(&source, path).ok())
+                .map(|doc| doc.includes)
+                .unwrap_or_default();
+            graph.update(path, includes);
+        }
+        graph
+    }
+
+    /// Record the files a page includes.
+    pub fn update(&mut self, page: &Path, includes: Vec<PathBuf>) {
+        self.includes.insert(canonical(page), includes);
+    }
+
+    /// Forget a deleted page.
+    pub fn remove(&mut self, page: &Path) {
+        self.includes.remove(&canonical(page));
+    }
+
+    /// Pages to rebuild when `path` changes: the pages including it, and
+    /// the page itself if it is one.
+    pub fn affected(&self, path: &Path) -> Vec<PathBuf> {
+        let path = canonical(path);
+        let mut pages: Vec<PathBuf> = self
+            .includes
+            .iter()
+            .filter(|(page, includes)| **page == path || includes.contains(&path))
+            .map(|(page, _)| page.clone())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        pages.sort();
+        pages
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn tracks_included_partials() {
+        let temp = tempdir().unwrap();
+        let docs = temp.path();
+        fs::create_dir_all(docs.join("_partials")).unwrap();
+        fs::write(docs.join("_partials/install.mdx"), "Install it\n").unwrap();
+        fs::write(
+            docs.join("_partials/setup.mdx"),
+            "<Include src=\"install.mdx\" />\n",
+        )
+        .unwrap();
+        fs::write(
+            docs.join("a.mdx"),
+            "# A\n\n<Include src=\"_partials/setup.mdx\" />\n",
+        )
+        .unwrap();
+        fs::write(
+            docs.join("b.mdx"),
+            "# B\n\n<Include src=\"_partials/install.mdx\" />\n",
+        )
+        .unwrap();
+        fs::write(docs.join("c.mdx"), "# C\n").unwrap();
+
+        let mut graph = DependencyGraph::scan(docs);
+        let names = |pages: Vec<PathBuf>| -> Vec<String> {
+            pages
+                .iter()
+                .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+                .collect()
+        };
+
+        assert_eq!(
+            names(graph.affected(&docs.join("_partials/install.mdx"))),
+            vec!["a.mdx", "b.mdx"]
+        );
+        assert_eq!(
+            names(graph.affected(&docs.join("_partials/setup.mdx"))),
+            vec!["a.mdx"]
+        );
+        assert_eq!(names(graph.affected(&docs.join("c.mdx"))), vec!["c.mdx"]);
+
+        graph.update(&docs.join("b.mdx"), vec![]);
+        assert_eq!(
+            names(graph.affected(&docs.join("_partials/install.mdx"))),
+            vec!["a.mdx"]
+        );
+
+        graph.remove(&docs.join("a.mdx"));
+        assert!(graph
+            .affected(&docs.join("_partials/install.mdx"))
+            .is_empty());
+    }
+}
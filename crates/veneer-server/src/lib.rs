@@ -3,10 +3,12 @@
 //! Provides a fast development server with file watching and WebSocket-based
 //! hot module replacement.
 
+pub mod deps;
 pub mod server;
 pub mod watcher;
 pub mod websocket;
 
+pub use deps::DependencyGraph;
 pub use server::{DevServer, DevServerConfig, ServerError};
 pub use watcher::{FileWatcher, WatchEvent};
 pub use websocket::{HmrHub, HmrMessage};
@@ -16,9 +16,14 @@ use axum::{
 use tokio::sync::RwLock;
 use tower_http::services::ServeDir;
 
-use veneer_adapters::{FrameworkAdapter, ReactAdapter, TransformContext};
-use veneer_mdx::parse_mdx;
-
+use veneer_adapters::{adapter_for, ComponentRegistry, TransformContext};
+use veneer_mdx::frontmatter::today;
+use veneer_mdx::parse_mdx_file;
+use veneer_static::assets::AssetPipeline;
+use veneer_static::templates::TemplateEngine;
+use veneer_static::{is_partial, render_document};
+
+use crate::deps::DependencyGraph;
 use crate::watcher::{FileWatcher, WatchEvent};
 use crate::websocket::{hmr_client_script, HmrHub, HmrMessage};
 
@@ -31,6 +36,10 @@ pub struct DevServerConfig {
     /// Directory containing components
     pub components_dir: PathBuf,
 
+    /// Framework of the components directory ("react", "solid", "vue" or
+    /// "svelte")
+    pub framework: String,
+
     /// Port to listen on
     pub port: u16,
 
@@ -39,6 +48,9 @@ pub struct DevServerConfig {
 
     /// Open browser on start
     pub open: bool,
+
+    /// Directory of templates that replace the built-in ones
+    pub templates_dir: Option<PathBuf>,
 }
 
 impl Default for DevServerConfig {
@@ -46,9 +58,11 @@ impl Default for DevServerConfig {
         Self {
             docs_dir: PathBuf::from("docs"),
             components_dir: PathBuf::from("src/components"),
+            framework: "react".to_string(),
             port: 7777,
             host: "127.0.0.1".to_string(),
             open: true,
+            templates_dir: None,
         }
     }
 }
@@ -70,7 +84,12 @@ pub enum ServerError {
 struct ServerState {
     config: DevServerConfig,
     hmr: HmrHub,
-    adapter: ReactAdapter,
+    /// Picks the adapter for a modified component file
+    registry: ComponentRegistry,
+    /// Partials included by each page
+    deps: DependencyGraph,
+    /// Renders the parts of pages shared with the static build
+    templates: TemplateEngine,
 }
 
 /// Development server.
@@ -90,10 +109,21 @@ impl DevServer {
             .parse()
             .expect("Invalid address");
 
+        let mut registry = ComponentRegistry::new();
+        match adapter_for(&self.config.framework) {
+            Some(adapter) => registry.prefer(adapter),
+            None => tracing::warn!(
+                "Unknown framework '{}', using the React adapter",
+                self.config.framework
+            ),
+        }
+
         let state = Arc::new(RwLock::new(ServerState {
             config: self.config.clone(),
             hmr: HmrHub::new(),
-            adapter: ReactAdapter::new(),
+            registry,
+            deps: DependencyGraph::scan(&self.config.docs_dir),
+            templates: load_templates(&self.config),
         }));
 
         // Set up file watcher
@@ -146,12 +176,42 @@ impl DevServer {
 
 /// Handle file watch events.
 async fn handle_watch_event(state: &Arc<RwLock<ServerState>>, event: WatchEvent) {
-    let state = state.read().await;
+    let mut state = state.write().await;
 
     match event {
         WatchEvent::MdxModified(path) => {
             tracing::info!("MDX modified: {}", path.display());
 
+            // Rebuild the pages including a partial, or the page itself
+            let mut pages = state.deps.affected(&path);
+            let relative = path.strip_prefix(&state.config.docs_dir).unwrap_or(&path);
+            if pages.is_empty() && !is_partial(relative) {
+                pages.push(path.clone());
+            }
+            if is_partial(relative) {
+                tracing::info!("Rebuilding {} pages including it", pages.len());
+            }
+
+            // Show parse errors in the error overlay instead of reloading
+            // into a broken page
+            for page in pages {
+                let Ok(source) = std::fs::read_to_string(&page) else {
+                    continue;
+                };
+                match parse_mdx_file(&source, &page) {
+                    Ok(doc) => state.deps.update(&page, doc.includes),
+                    Err(e) => {
+                        let page = page.display().to_string();
+                        tracing::warn!("{}", e.diagnostic().render(&page, &source));
+                        state.hmr.send(HmrMessage::Error {
+                            html: e.diagnostic().render_html(&page, &source),
+                            path: page,
+                        });
+                        return;
+                    }
+                }
+            }
+
             // For now, just trigger a full reload
             // In a more sophisticated implementation, we'd re-render just the affected page
             state.hmr.send(HmrMessage::Reload);
@@ -160,6 +220,12 @@ async fn handle_watch_event(state: &Arc<RwLock<ServerState>>, event: WatchEvent)
         WatchEvent::ComponentModified(path) => {
             tracing::info!("Component modified: {}", path.display());
 
+            // Files no adapter handles (e.g., shared .ts helpers) need a full reload
+            let Some(adapter) = state.registry.adapter_for_path(&path) else {
+                state.hmr.send(HmrMessage::Reload);
+                return;
+            };
+
             // Try to re-transform the component
             if let Ok(source) = std::fs::read_to_string(&path) {
                 let tag_name = path
@@ -168,10 +234,7 @@ async fn handle_watch_event(state: &Arc<RwLock<ServerState>>, event: WatchEvent)
                     .map(|s| format!("{}-preview", s.to_lowercase()))
                     .unwrap_or_else(|| "component-preview".to_string());
 
-                match state
-                    .adapter
-                    .transform(&source, &tag_name, &TransformContext::default())
-                {
+                match adapter.transform(&source, &tag_name, &TransformContext::default()) {
                     Ok(result) => {
                         state.hmr.send(HmrMessage::UpdateComponent {
                             tag_name: result.tag_name,
@@ -186,13 +249,34 @@ async fn handle_watch_event(state: &Arc<RwLock<ServerState>>, event: WatchEvent)
             }
         }
 
-        WatchEvent::Created(_) | WatchEvent::Deleted(_) | WatchEvent::Modified(_) => {
+        WatchEvent::Deleted(path) => {
+            state.deps.remove(&path);
+            state.hmr.send(HmrMessage::Reload);
+        }
+
+        WatchEvent::Created(_) | WatchEvent::Modified(_) => {
             // For other changes, trigger a reload
             state.hmr.send(HmrMessage::Reload);
         }
     }
 }
 
+/// Template engine of the project's theme, or of the built-in templates when
+/// it has none.
+fn load_templates(config: &DevServerConfig) -> TemplateEngine {
+    match &config.templates_dir {
+        Some(dir) if !dir.is_dir() => {
+            tracing::warn!(
+                "Templates directory not found: {}, using the built-in templates",
+                dir.display()
+            );
+            TemplateEngine::new()
+        }
+        Some(dir) => TemplateEngine::with_templates_dir(dir),
+        None => TemplateEngine::new(),
+    }
+}
+
 /// Handler for the index page.
 async fn index_handler(State(state): State<Arc<RwLock<ServerState>>>) -> impl IntoResponse {
     let state = state.read().await;
@@ -202,7 +286,7 @@ async fn index_handler(State(state): State<Arc<RwLock<ServerState>>>) -> impl In
 
     let content = if index_path.exists() {
         match std::fs::read_to_string(&index_path) {
-            Ok(source) => match parse_mdx(&source) {
+            Ok(source) => match parse_mdx_file(&source, &index_path) {
                 Ok(doc) => {
                     let title = doc
                         .frontmatter
@@ -210,14 +294,32 @@ async fn index_handler(State(state): State<Arc<RwLock<ServerState>>>) -> impl In
                         .map(|f| f.title.clone())
                         .unwrap_or_else(|| "Documentation".to_string());
 
+                    // Drafts and scheduled pages are always shown while writing
+                    let today = today();
+                    let banner = doc
+                        .frontmatter
+                        .as_ref()
+                        .map(|f| state.templates.render_banner(f.draft, f.scheduled(&today)))
+                        .transpose()
+                        .unwrap_or_else(|e| {
+                            tracing::warn!("Failed to render banner: {}", e);
+                            None
+                        })
+                        .unwrap_or_default();
+
                     format!(
-                        r#"<h1>{}</h1>
+                        r#"{}<h1>{}</h1>
 <div class="content">{}</div>"#,
+                        banner,
                         title,
-                        render_markdown(&doc.content)
+                        render_document(doc.nodes)
                     )
                 }
-                Err(e) => format!("<p>Error parsing index.mdx: {}</p>", e),
+                Err(e) => format!(
+                    "<p>Error parsing index.mdx</p>\n{}",
+                    e.diagnostic()
+                        .render_html(&index_path.display().to_string(), &source)
+                ),
             },
             Err(e) => format!("<p>Error reading index.mdx: {}</p>", e),
         }
@@ -236,14 +338,18 @@ async fn index_handler(State(state): State<Arc<RwLock<ServerState>>>) -> impl In
     body {{ font-family: system-ui, sans-serif; max-width: 800px; margin: 2rem auto; padding: 0 1rem; }}
     h1 {{ font-size: 2rem; }}
     pre {{ background: #f5f5f5; padding: 1rem; border-radius: 0.5rem; overflow-x: auto; }}
+    {}
   </style>
 </head>
 <body>
   {}
+  <script>{}</script>
   <script src="/__hmr.js"></script>
 </body>
 </html>"#,
-        content
+        AssetPipeline::content_css(),
+        content,
+        AssetPipeline::content_js()
     ))
 }
 
@@ -283,22 +389,12 @@ async fn hmr_script_handler() -> impl IntoResponse {
     ([("content-type", "application/javascript")], script)
 }
 
-/// Simple markdown to HTML renderer.
-fn render_markdown(content: &str) -> String {
-    use pulldown_cmark::{html, Options, Parser};
-
-    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH;
-    let parser = Parser::new_ext(content, options);
-
-    let mut html_output = String::new();
-    html::push_html(&mut html_output, parser);
-
-    html_output
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+    use veneer_mdx::parse_mdx;
 
     #[test]
     fn creates_server_with_default_config() {
@@ -308,10 +404,52 @@ mod tests {
 
     #[test]
     fn renders_markdown() {
-        let md = "# Hello\n\nWorld";
-        let html = render_markdown(md);
+        let doc = parse_mdx("# Hello\n\nWorld").unwrap();
+        let html = render_document(doc.nodes);
 
-        assert!(html.contains("<h1>Hello</h1>"));
+        assert!(html.contains("<h1 id=\"hello\">Hello"));
+        assert!(html.contains("href=\"#hello\""));
         assert!(html.contains("<p>World</p>"));
     }
+
+    #[tokio::test]
+    async fn renders_theme_banner() {
+        let temp = tempdir().unwrap();
+        let docs = temp.path().join("docs");
+        let theme = temp.path().join("theme");
+
+        fs::create_dir_all(&docs).unwrap();
+        fs::create_dir_all(&theme).unwrap();
+        fs::write(
+            docs.join("index.mdx"),
+            "---\ntitle: Home\ndraft: true\n---\n# Home\n",
+        )
+        .unwrap();
+        fs::write(
+            theme.join("banner.html"),
+            "<aside class=\"theme-banner\">{% if draft %}Work in progress{% endif %}</aside>",
+        )
+        .unwrap();
+
+        let config = DevServerConfig {
+            docs_dir: docs.clone(),
+            templates_dir: Some(theme),
+            ..Default::default()
+        };
+        let state = Arc::new(RwLock::new(ServerState {
+            templates: load_templates(&config),
+            config,
+            hmr: HmrHub::new(),
+            registry: ComponentRegistry::new(),
+            deps: DependencyGraph::scan(&docs),
+        }));
+
+        let response = index_handler(State(state)).await.into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
+
+        assert!(html.contains(r#"<aside class="theme-banner">Work in progress</aside>"#));
+    }
 }
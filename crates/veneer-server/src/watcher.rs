@@ -97,7 +97,7 @@ fn classify_event(path: &Path, kind: &notify::EventKind) -> Option<WatchEvent> {
         EventKind::Modify(_) => {
             if ext == "mdx" || ext == "md" {
                 Some(WatchEvent::MdxModified(path.to_path_buf()))
-            } else if ext == "tsx" || ext == "jsx" || ext == "ts" || ext == "js" {
+            } else if matches!(ext, "tsx" | "jsx" | "ts" | "js" | "vue" | "svelte") {
                 Some(WatchEvent::ComponentModified(path.to_path_buf()))
             } else {
                 Some(WatchEvent::Modified(path.to_path_buf()))
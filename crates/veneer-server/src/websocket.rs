@@ -26,6 +26,14 @@ pub enum HmrMessage {
         html: String,
     },
 
+    /// Show a build error in the error overlay
+    Error {
+        /// Source file with the error
+        path: String,
+        /// Code frame as HTML
+        html: String,
+    },
+
     /// Connection established
     Connected,
 }
@@ -131,6 +139,20 @@ pub fn hmr_client_script(ws_url: &str) -> String {
         }}
         break;
 
+      case 'error':
+        let overlay = document.getElementById('veneer-error-overlay');
+        if (!overlay) {{
+          overlay = document.createElement('div');
+          overlay.id = 'veneer-error-overlay';
+          overlay.style.cssText = 'position:fixed;inset:0;z-index:2147483647;overflow:auto;' +
+            'padding:2rem;background:rgba(0,0,0,0.85);color:#fca5a5;font:14px/1.5 monospace;';
+          overlay.addEventListener('click', function() {{ overlay.remove(); }});
+          document.body.appendChild(overlay);
+        }}
+        overlay.innerHTML = msg.html;
+        console.error('[HMR] Error in', msg.path);
+        break;
+
       case 'connected':
         console.log('[HMR] Server acknowledged connection');
         break;
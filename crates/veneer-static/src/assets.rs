@@ -6,12 +6,28 @@ pub struct AssetPipeline;
 impl AssetPipeline {
     /// Generate the main CSS file.
     pub fn generate_css() -> String {
-        DEFAULT_CSS.to_string()
+        format!("{}{}", DEFAULT_CSS, Self::content_css())
+    }
+
+    /// CSS for rendered page content: draft banners, heading anchors,
+    /// admonitions, highlighted code and code groups. Also used by the dev
+    /// server.
+    pub fn content_css() -> String {
+        format!(
+            "{}{}{}{}{}",
+            DRAFT_CSS, HEADING_CSS, ADMONITION_CSS, HIGHLIGHT_CSS, CODE_GROUP_CSS
+        )
     }
 
     /// Generate the main JavaScript file.
     pub fn generate_js() -> String {
-        DEFAULT_JS.to_string()
+        format!("{}{}", DEFAULT_JS, Self::content_js())
+    }
+
+    /// JavaScript for rendered page content: code group tabs. Also used by
+    /// the dev server.
+    pub fn content_js() -> &'static str {
+        CODE_GROUP_JS
     }
 
     /// Minify CSS using lightningcss.
@@ -130,6 +146,16 @@ body {
   max-width: var(--content-max-width);
 }
 
+/* Full-width layout: no table of contents, no measure */
+.main-full {
+  grid-template-columns: 1fr;
+  max-width: none;
+}
+
+.main-full .doc {
+  max-width: none;
+}
+
 .content h1 {
   font-size: 2.5rem;
   font-weight: 700;
@@ -215,6 +241,33 @@ body {
   flex-wrap: wrap;
 }
 
+/* API table generated by <PropsTable of="..." /> */
+.props-table {
+  width: 100%;
+  border-collapse: collapse;
+  font-size: 0.875rem;
+  margin-bottom: 1.5rem;
+}
+
+.props-table th,
+.props-table td {
+  text-align: left;
+  padding: 0.5rem 0.75rem;
+  border-bottom: 1px solid var(--border);
+  vertical-align: top;
+}
+
+.props-table th {
+  font-weight: 600;
+  color: var(--muted-foreground);
+}
+
+.props-required {
+  font-size: 0.75rem;
+  color: var(--destructive, #dc2626);
+  margin-left: 0.25rem;
+}
+
 /* Copy button - uses Rafters button styling */
 .copy-btn {
   position: absolute;
@@ -275,16 +328,14 @@ body {
   color: var(--foreground);
 }
 
-.toc-level-2 {
-  padding-left: 0;
+.toc a.active {
+  color: var(--foreground);
+  font-weight: 500;
 }
 
-.toc-level-3 {
+.toc ul ul {
   padding-left: 1rem;
-}
-
-.toc-level-4 {
-  padding-left: 2rem;
+  margin-top: 0.25rem;
 }
 
 /* Responsive */
@@ -336,6 +387,329 @@ body {
 }
 "#;
 
+// Admonition callouts. Each kind sets an accent color and an icon, both
+// overridable through CSS variables.
+const DRAFT_CSS: &str = r#"
+/* Draft and scheduled page banner */
+.draft-banner {
+  margin: 0 0 1.5rem;
+  padding: 0.5rem 1rem;
+  border: 1px dashed var(--admonition-warning, #d97706);
+  border-radius: var(--radius, 0.375rem);
+  background: color-mix(in srgb, var(--admonition-warning, #d97706) 10%, transparent);
+  font-size: 0.875rem;
+  font-weight: 600;
+  text-transform: uppercase;
+  letter-spacing: 0.05em;
+}
+"#;
+
+const HEADING_CSS: &str = r#"
+/* Heading anchors */
+:is(h1, h2, h3, h4, h5, h6)[id] {
+  scroll-margin-top: 1.5rem;
+}
+
+.heading-anchor {
+  margin-left: 0.25rem;
+  color: var(--muted-foreground);
+  text-decoration: none;
+  opacity: 0;
+  transition: opacity 0.15s;
+}
+
+:is(h1, h2, h3, h4, h5, h6):hover .heading-anchor,
+.heading-anchor:focus-visible {
+  opacity: 1;
+}
+"#;
+
+const ADMONITION_CSS: &str = r#"
+/* Admonitions (:::note, :::tip, :::warning, :::danger) */
+.admonition {
+  --admonition-accent: var(--admonition-note, var(--primary, #2563eb));
+  --admonition-icon: "i";
+  margin: 0 0 1.5rem;
+  padding: 0.75rem 1rem;
+  border-left: 4px solid var(--admonition-accent);
+  border-radius: var(--radius, 0.375rem);
+  background: color-mix(in srgb, var(--admonition-accent) 8%, transparent);
+}
+
+.admonition-tip {
+  --admonition-accent: var(--admonition-tip, #16a34a);
+  --admonition-icon: "\2713";
+}
+
+.admonition-warning {
+  --admonition-accent: var(--admonition-warning, #d97706);
+  --admonition-icon: "!";
+}
+
+.admonition-danger {
+  --admonition-accent: var(--admonition-danger, var(--destructive, #dc2626));
+  --admonition-icon: "\00d7";
+}
+
+.admonition-title {
+  display: flex;
+  align-items: center;
+  gap: 0.5rem;
+  margin: 0 0 0.25rem;
+  font-weight: 600;
+  color: var(--admonition-accent);
+}
+
+.admonition-icon::before {
+  content: var(--admonition-icon);
+  display: inline-flex;
+  align-items: center;
+  justify-content: center;
+  width: 1.25rem;
+  height: 1.25rem;
+  border-radius: 9999px;
+  font-size: 0.75rem;
+  background: var(--admonition-accent);
+  color: var(--background, #fff);
+}
+
+.admonition-content > :last-child {
+  margin-bottom: 0;
+}
+"#;
+
+// Token colors for build-time highlighting; themes override the --code-* vars
+const HIGHLIGHT_CSS: &str = r#"
+/* Syntax highlighting */
+:root {
+  --code-comment: var(--muted-foreground, #6a737d);
+  --code-keyword: #cf222e;
+  --code-string: #0a3069;
+  --code-number: #0550ae;
+  --code-constant: #0550ae;
+  --code-function: #8250df;
+  --code-type: #953800;
+  --code-tag: #116329;
+  --code-attribute: #0550ae;
+  --code-property: #0550ae;
+  --code-variable: #953800;
+  --code-operator: #cf222e;
+  --code-punctuation: var(--card-foreground, inherit);
+  --code-meta: #6639ba;
+  --code-inserted: #116329;
+  --code-inserted-background: #dafbe1;
+  --code-deleted: #82071e;
+  --code-deleted-background: #ffebe9;
+  --code-highlight-background: color-mix(in srgb, var(--primary, #2563eb) 10%, transparent);
+  --code-highlight-border: var(--primary, #2563eb);
+}
+
+.hl-comment { color: var(--code-comment); font-style: italic; }
+.hl-keyword { color: var(--code-keyword); }
+.hl-string { color: var(--code-string); }
+.hl-number { color: var(--code-number); }
+.hl-constant { color: var(--code-constant); }
+.hl-function { color: var(--code-function); }
+.hl-type { color: var(--code-type); }
+.hl-tag { color: var(--code-tag); }
+.hl-attribute { color: var(--code-attribute); }
+.hl-property { color: var(--code-property); }
+.hl-variable { color: var(--code-variable); }
+.hl-operator { color: var(--code-operator); }
+.hl-punctuation { color: var(--code-punctuation); }
+.hl-meta { color: var(--code-meta); }
+
+.hl-inserted {
+  color: var(--code-inserted);
+  background: var(--code-inserted-background);
+}
+
+.hl-deleted {
+  color: var(--code-deleted);
+  background: var(--code-deleted-background);
+}
+
+/* Code block lines, titles and line numbers */
+.code-block {
+  margin: 0 0 1rem;
+}
+
+.code-title {
+  padding: 0.5rem 1rem;
+  border: 1px solid var(--border);
+  border-bottom: none;
+  border-radius: var(--radius, 0.5rem) var(--radius, 0.5rem) 0 0;
+  background: var(--muted);
+  color: var(--muted-foreground);
+  font-family: var(--font-mono, ui-monospace, monospace);
+  font-size: 0.8125rem;
+}
+
+.code-block pre {
+  margin: 0;
+  border-top-left-radius: 0;
+  border-top-right-radius: 0;
+}
+
+.line {
+  display: inline-block;
+  min-width: 100%;
+}
+
+.line.highlighted {
+  background: var(--code-highlight-background);
+  box-shadow: inset 2px 0 var(--code-highlight-border);
+}
+
+.line.diff-add {
+  background: var(--code-inserted-background);
+  box-shadow: inset 2px 0 var(--code-inserted);
+}
+
+.line.diff-remove {
+  background: var(--code-deleted-background);
+  box-shadow: inset 2px 0 var(--code-deleted);
+}
+
+.line-numbers code {
+  counter-reset: line;
+}
+
+.line-numbers .line::before {
+  counter-increment: line;
+  content: counter(line);
+  display: inline-block;
+  width: 2ch;
+  margin-right: 1rem;
+  text-align: right;
+  color: var(--code-comment);
+  user-select: none;
+}
+"#;
+
+const CODE_GROUP_CSS: &str = r#"
+/* Code groups */
+.code-group {
+  margin: 0 0 1rem;
+}
+
+.code-group-tabs {
+  display: flex;
+  gap: 0.25rem;
+  padding: 0.25rem 0.5rem 0;
+  overflow-x: auto;
+  border: 1px solid var(--border);
+  border-bottom: none;
+  border-radius: var(--radius, 0.5rem) var(--radius, 0.5rem) 0 0;
+  background: var(--muted);
+}
+
+.code-group-tabs [role="tab"] {
+  padding: 0.375rem 0.75rem;
+  border: none;
+  border-bottom: 2px solid transparent;
+  background: none;
+  color: var(--muted-foreground);
+  font-family: var(--font-mono, ui-monospace, monospace);
+  font-size: 0.8125rem;
+  cursor: pointer;
+}
+
+.code-group-tabs [role="tab"][aria-selected="true"] {
+  color: var(--foreground);
+  border-bottom-color: var(--primary);
+}
+
+.code-group-tabs [role="tab"]:focus-visible {
+  outline: 2px solid var(--ring, var(--primary));
+  outline-offset: -2px;
+}
+
+.code-group-panel > :last-child {
+  margin-bottom: 0;
+}
+
+.code-group-panel > pre:first-child,
+.code-group-panel > .preview-container:first-child {
+  border-top-left-radius: 0;
+  border-top-right-radius: 0;
+}
+"#;
+
+// Tabs for code groups. The chosen label is stored per group name, and
+// groups with the same name switch together.
+const CODE_GROUP_JS: &str = r#"
+(function() {
+  'use strict';
+
+  const groups = Array.from(document.querySelectorAll('.code-group'));
+  const storageKey = name => 'veneer-code-group:' + name;
+  const tabsOf = group => Array.from(group.querySelectorAll('[role="tab"]'));
+
+  function select(group, index, focus) {
+    const tabs = tabsOf(group);
+    tabs.forEach((tab, i) => {
+      const selected = i === index;
+      tab.setAttribute('aria-selected', String(selected));
+      tab.tabIndex = selected ? 0 : -1;
+      const panel = document.getElementById(tab.getAttribute('aria-controls'));
+      if (panel) panel.hidden = !selected;
+    });
+    if (focus) tabs[index].focus();
+  }
+
+  function selectLabel(group, label) {
+    const index = tabsOf(group).findIndex(tab => tab.textContent === label);
+    if (index >= 0) select(group, index, false);
+  }
+
+  function choose(group, index, focus) {
+    select(group, index, focus);
+    const name = group.dataset.group;
+    if (!name) return;
+
+    const label = tabsOf(group)[index].textContent;
+    try {
+      localStorage.setItem(storageKey(name), label);
+    } catch (err) {
+      // Storage may be unavailable; the choice just isn't remembered
+    }
+    groups
+      .filter(other => other !== group && other.dataset.group === name)
+      .forEach(other => selectLabel(other, label));
+  }
+
+  groups.forEach(group => {
+    const name = group.dataset.group;
+    if (name) {
+      try {
+        const saved = localStorage.getItem(storageKey(name));
+        if (saved !== null) selectLabel(group, saved);
+      } catch (err) {
+        // Keep the first tab
+      }
+    }
+
+    const tabs = tabsOf(group);
+    tabs.forEach((tab, index) => {
+      tab.addEventListener('click', () => choose(group, index, false));
+      tab.addEventListener('keydown', event => {
+        const last = tabs.length - 1;
+        const next = {
+          ArrowRight: index === last ? 0 : index + 1,
+          ArrowLeft: index === 0 ? last : index - 1,
+          Home: 0,
+          End: last,
+        }[event.key];
+        if (next === undefined) return;
+        event.preventDefault();
+        choose(group, next, true);
+      });
+    });
+  });
+})();
+"#;
+
 const DEFAULT_JS: &str = r#"// Rafters Docs - Runtime JavaScript
 (function() {
   'use strict';
@@ -387,6 +761,41 @@ const DEFAULT_JS: &str = r#"// Rafters Docs - Runtime JavaScript
 
     pre.appendChild(btn);
   });
+
+  // Highlight the table of contents entry of the section in view
+  const tocLinks = Array.from(document.querySelectorAll('.toc a'))
+    .map(link => ({ link, section: link.hash && document.getElementById(decodeURIComponent(link.hash.slice(1))) }))
+    .filter(entry => entry.section);
+
+  if (tocLinks.length) {
+    let ticking = false;
+
+    const updateToc = () => {
+      ticking = false;
+      let current = tocLinks[0].section;
+      for (const { section } of tocLinks) {
+        if (section.getBoundingClientRect().top > 96) break;
+        current = section;
+      }
+      tocLinks.forEach(({ link, section }) => {
+        const active = section === current;
+        link.classList.toggle('active', active);
+        if (active) {
+          link.setAttribute('aria-current', 'location');
+        } else {
+          link.removeAttribute('aria-current');
+        }
+      });
+    };
+
+    window.addEventListener('scroll', () => {
+      if (!ticking) {
+        ticking = true;
+        requestAnimationFrame(updateToc);
+      }
+    }, { passive: true });
+    updateToc();
+  }
 })();
 "#;
 
@@ -400,6 +809,12 @@ mod tests {
         assert!(css.contains(":root"));
         assert!(css.contains("--background"));
         assert!(css.contains("--primary"));
+        assert!(css.contains(".admonition-warning"));
+        assert!(css.contains(".draft-banner"));
+        assert!(css.contains(".hl-keyword"));
+        assert!(css.contains(".code-group-tabs"));
+        assert!(css.contains(".heading-anchor"));
+        assert!(css.contains(".toc a.active"));
     }
 
     #[test]
@@ -407,6 +822,8 @@ mod tests {
         let js = AssetPipeline::generate_js();
         assert!(js.contains("addEventListener"));
         assert!(js.contains("clipboard"));
+        assert!(js.contains("veneer-code-group:"));
+        assert!(js.contains("aria-current"));
     }
 
     #[test]
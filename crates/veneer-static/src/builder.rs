@@ -1,6 +1,6 @@
 //! Static site builder.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -10,12 +10,18 @@ use rayon::prelude::*;
 use walkdir::WalkDir;
 
 use veneer_adapters::{
-    parse_inline_jsx, to_custom_element, ComponentRegistry, FrameworkAdapter, ReactAdapter,
+    adapter_for, parse_inline_jsx, to_custom_element, ComponentRegistry, InlineJsx, PropDoc,
     TransformContext, TransformedBlock,
 };
-use veneer_mdx::{parse_mdx, CodeBlock, Frontmatter, ParsedDoc};
+use veneer_mdx::frontmatter::{field_span, today};
+use veneer_mdx::{
+    escape_html, parse_mdx_file, plain_text, walk_inlines_mut, walk_mut, CodeBlock, Diagnostic,
+    Frontmatter, FrontmatterSchema, Inline, JsxElement, Node, ParsedDoc, TocSettings,
+};
 
 use crate::assets::AssetPipeline;
+use crate::highlight::render_code_block;
+use crate::render::render_document;
 use crate::templates::{Context, NavItem, TemplateEngine, TocEntry};
 
 /// Configuration for building a static site.
@@ -30,6 +36,10 @@ pub struct BuildConfig {
     /// Components source directory (for looking up component definitions)
     pub components_dir: Option<PathBuf>,
 
+    /// Framework of the components directory ("react", "solid", "vue" or
+    /// "svelte")
+    pub framework: String,
+
     /// Minify HTML/CSS/JS output
     pub minify: bool,
 
@@ -41,6 +51,61 @@ pub struct BuildConfig {
 
     /// Paths to CSS stylesheets to include
     pub styles: Vec<String>,
+
+    /// Table of contents defaults, which pages can override
+    pub toc: TocConfig,
+
+    /// Rules for the frontmatter of every page
+    pub frontmatter_schema: FrontmatterSchema,
+
+    /// Build drafts and pages whose publish date is still to come
+    pub drafts: bool,
+
+    /// Template files of additional layouts, by the name pages select them
+    /// with (`layout: splash`)
+    pub layouts: BTreeMap<String, PathBuf>,
+
+    /// Directory of templates that replace the built-in ones
+    pub templates_dir: Option<PathBuf>,
+}
+
+/// Which headings the table of contents lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TocConfig {
+    /// Show a table of contents on pages
+    pub enabled: bool,
+
+    /// Shallowest heading level to include
+    pub min_depth: u8,
+
+    /// Deepest heading level to include
+    pub max_depth: u8,
+}
+
+impl Default for TocConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_depth: 1,
+            max_depth: 6,
+        }
+    }
+}
+
+impl TocConfig {
+    /// Resolve the settings of a page against these defaults. Returns the
+    /// heading levels to include, or `None` when the page has no TOC.
+    pub fn levels(&self, page: Option<TocSettings>) -> Option<(u8, u8)> {
+        match page {
+            Some(TocSettings::Enabled(false)) => None,
+            Some(TocSettings::Enabled(true)) => Some((self.min_depth, self.max_depth)),
+            Some(TocSettings::Depth(depth)) => Some((
+                depth.min.unwrap_or(self.min_depth),
+                depth.max.unwrap_or(self.max_depth),
+            )),
+            None => self.enabled.then_some((self.min_depth, self.max_depth)),
+        }
+    }
 }
 
 impl Default for BuildConfig {
@@ -49,10 +114,16 @@ impl Default for BuildConfig {
             docs_dir: PathBuf::from("docs"),
             output_dir: PathBuf::from("dist"),
             components_dir: None,
+            framework: "react".to_string(),
             minify: true,
             base_url: "/".to_string(),
             title: "Documentation".to_string(),
             styles: vec![],
+            toc: TocConfig::default(),
+            frontmatter_schema: FrontmatterSchema::default(),
+            drafts: false,
+            layouts: BTreeMap::new(),
+            templates_dir: None,
         }
     }
 }
@@ -79,9 +150,18 @@ pub enum BuildError {
     #[error("Failed to read docs directory: {0}")]
     ReadError(String),
 
-    #[error("Failed to parse MDX: {path}: {message}")]
+    /// `message` is a code frame that names the file
+    #[error("Failed to parse MDX: {message}")]
     ParseError { path: String, message: String },
 
+    /// `message` holds a code frame for each problem
+    #[error("Frontmatter does not match the schema ({count} problems):\n{message}")]
+    SchemaError { count: usize, message: String },
+
+    /// `message` is a code frame that names the file
+    #[error("Invalid layout: {message}")]
+    LayoutError { path: String, message: String },
+
     #[error("Failed to transform component: {0}")]
     TransformError(String),
 
@@ -111,15 +191,21 @@ struct PageInfo {
 /// Static site builder.
 pub struct StaticBuilder {
     config: BuildConfig,
-    adapter: ReactAdapter,
     registry: Arc<ComponentRegistry>,
-    templates: TemplateEngine,
 }
 
 impl StaticBuilder {
     /// Create a new static builder.
     pub fn new(config: BuildConfig) -> Self {
+        // The configured framework takes the extensions it shares with others
         let mut registry = ComponentRegistry::new();
+        match adapter_for(&config.framework) {
+            Some(adapter) => registry.prefer(adapter),
+            None => tracing::warn!(
+                "Unknown framework '{}', using the React adapter",
+                config.framework
+            ),
+        }
 
         // Scan components directory if configured
         if let Some(ref components_dir) = config.components_dir {
@@ -141,9 +227,7 @@ impl StaticBuilder {
 
         Self {
             config,
-            adapter: ReactAdapter::new(),
             registry: Arc::new(registry),
-            templates: TemplateEngine::new(),
         }
     }
 
@@ -155,8 +239,11 @@ impl StaticBuilder {
         fs::create_dir_all(&self.config.output_dir)
             .map_err(|e| BuildError::WriteError(e.to_string()))?;
 
+        // Load the built-in templates and the project's layouts
+        let templates = self.load_templates()?;
+
         // Find all MDX files
-        let pages = self.discover_pages()?;
+        let pages = self.discover_pages(&templates)?;
 
         // Build navigation from pages
         let nav = self.build_navigation(&pages);
@@ -164,7 +251,7 @@ impl StaticBuilder {
         // Transform and render pages in parallel
         let results: Vec<Result<(usize, usize), BuildError>> = pages
             .par_iter()
-            .map(|page| self.build_page(page, &nav))
+            .map(|page| self.build_page(page, &nav, &templates))
             .collect();
 
         // Collect results
@@ -196,9 +283,35 @@ impl StaticBuilder {
         })
     }
 
+    /// Create the template engine, with the theme and layouts of the
+    /// configuration.
+    fn load_templates(&self) -> Result<TemplateEngine, BuildError> {
+        let mut templates = match &self.config.templates_dir {
+            Some(dir) if !dir.is_dir() => {
+                return Err(BuildError::ReadError(format!(
+                    "Templates directory not found: {}",
+                    dir.display()
+                )));
+            }
+            Some(dir) => TemplateEngine::with_templates_dir(dir),
+            None => TemplateEngine::new(),
+        };
+        for (name, path) in &self.config.layouts {
+            let source = fs::read_to_string(path).map_err(|e| {
+                BuildError::ReadError(format!("Layout {name} ({}): {e}", path.display()))
+            })?;
+            templates.add_layout(name, source).map_err(|e| {
+                BuildError::TemplateError(format!("Layout {name} ({}): {e}", path.display()))
+            })?;
+        }
+        Ok(templates)
+    }
+
     /// Discover all MDX pages in the docs directory.
-    fn discover_pages(&self) -> Result<Vec<PageInfo>, BuildError> {
+    fn discover_pages(&self, templates: &TemplateEngine) -> Result<Vec<PageInfo>, BuildError> {
         let mut pages = Vec::new();
+        let mut schema_errors = Vec::new();
+        let today = today();
 
         if !self.config.docs_dir.exists() {
             return Err(BuildError::ReadError(format!(
@@ -223,20 +336,59 @@ impl StaticBuilder {
                 continue;
             }
 
+            // Calculate relative path
+            let relative_path = path
+                .strip_prefix(&self.config.docs_dir)
+                .unwrap_or(path)
+                .to_path_buf();
+
+            // Partials (`_partials/install.mdx`, `_note.mdx`) are only included
+            if is_partial(&relative_path) {
+                continue;
+            }
+
             // Read and parse the file
             let content = fs::read_to_string(path)
                 .map_err(|e| BuildError::ReadError(format!("{}: {}", path.display(), e)))?;
 
-            let doc = parse_mdx(&content).map_err(|e| BuildError::ParseError {
+            let doc = parse_mdx_file(&content, path).map_err(|e| BuildError::ParseError {
                 path: path.display().to_string(),
-                message: e.to_string(),
+                message: e.diagnostic().render(&path.display().to_string(), &content),
             })?;
 
-            // Calculate relative path
-            let relative_path = path
-                .strip_prefix(&self.config.docs_dir)
-                .unwrap_or(path)
-                .to_path_buf();
+            for diagnostic in self
+                .config
+                .frontmatter_schema
+                .validate(doc.frontmatter.as_ref(), &content)
+            {
+                schema_errors.push(diagnostic.render(&path.display().to_string(), &content));
+            }
+
+            let layout = doc.frontmatter.as_ref().and_then(|f| f.layout.as_deref());
+            if let Some(layout) = layout.filter(|l| templates.layout_template(l).is_none()) {
+                let layouts: Vec<_> = templates.layouts().collect();
+                let diagnostic = Diagnostic::new(
+                    format!("No layout named `{layout}`"),
+                    field_span(&content, "layout"),
+                )
+                .with_help(format!(
+                    "use one of: {}, or add it under [layouts] in docs.toml",
+                    layouts.join(", ")
+                ));
+                return Err(BuildError::LayoutError {
+                    path: path.display().to_string(),
+                    message: diagnostic.render(&path.display().to_string(), &content),
+                });
+            }
+
+            // Leave out drafts and scheduled pages
+            let published = doc
+                .frontmatter
+                .as_ref()
+                .is_none_or(|f| f.is_published(&today));
+            if !published && !self.config.drafts {
+                continue;
+            }
 
             // Calculate output path
             let output_path = self.calculate_output_path(&relative_path, &doc.frontmatter);
@@ -249,6 +401,13 @@ impl StaticBuilder {
             });
         }
 
+        if !schema_errors.is_empty() {
+            return Err(BuildError::SchemaError {
+                count: schema_errors.len(),
+                message: schema_errors.join("\n"),
+            });
+        }
+
         // Sort by order from frontmatter
         pages.sort_by(|a, b| {
             let order_a = a
@@ -377,7 +536,12 @@ impl StaticBuilder {
     }
 
     /// Build a single page.
-    fn build_page(&self, page: &PageInfo, nav: &[NavItem]) -> Result<(usize, usize), BuildError> {
+    fn build_page(
+        &self,
+        page: &PageInfo,
+        nav: &[NavItem],
+        templates: &TemplateEngine,
+    ) -> Result<(usize, usize), BuildError> {
         let mut components_count = 0;
         let mut web_components: Vec<TransformedBlock> = Vec::new();
         let mut generated_components: HashMap<String, String> = HashMap::new();
@@ -388,48 +552,20 @@ impl StaticBuilder {
             if block.is_live() {
                 // Try inline JSX parsing first (for documentation code blocks)
                 if let Some(jsx) = parse_inline_jsx(&block.source) {
-                    let component_name = &jsx.component;
-
                     // Look up component in registry
-                    if self.registry.contains(component_name) {
-                        // Generate unique tag name for this component type
-                        let tag_name = format!("{}-preview", component_name.to_lowercase());
-
-                        // Only generate Web Component JS once per component type
-                        if !generated_components.contains_key(component_name) {
-                            match self
-                                .registry
-                                .generate_web_component(component_name, &tag_name)
-                            {
-                                Ok(transformed) => {
-                                    generated_components
-                                        .insert(component_name.clone(), tag_name.clone());
-                                    web_components.push(transformed);
-                                }
-                                Err(e) => {
-                                    tracing::warn!(
-                                        "Failed to generate Web Component for {}: {}",
-                                        component_name,
-                                        e
-                                    );
-                                    continue;
-                                }
-                            }
+                    if self.registry.contains(&jsx.component) {
+                        if let Some(html) = self.component_preview(
+                            &jsx,
+                            &mut web_components,
+                            &mut generated_components,
+                        ) {
+                            block_replacements.insert(block.id.clone(), html);
+                            components_count += 1;
                         }
-
-                        // Convert inline JSX to custom element HTML
-                        let actual_tag = generated_components
-                            .get(component_name)
-                            .map(|s| s.as_str())
-                            .unwrap_or(&tag_name);
-                        let custom_element_html = to_custom_element(&jsx, actual_tag);
-
-                        block_replacements.insert(block.id.clone(), custom_element_html);
-                        components_count += 1;
                     } else {
                         tracing::warn!(
                             "Component '{}' not found in registry (block {} in {})",
-                            component_name,
+                            jsx.component,
                             block.id,
                             page.source_path.display()
                         );
@@ -455,24 +591,45 @@ impl StaticBuilder {
             }
         }
 
+        // Render registered components used in prose as previews; other JSX
+        // is left to the renderer as raw HTML
+        let mut nodes = page.doc.nodes.clone();
+        walk_mut(&mut nodes, &mut |node| {
+            let Node::Jsx(element) = node else {
+                return;
+            };
+            if let Some(html) =
+                self.jsx_preview(element, &mut web_components, &mut generated_components)
+            {
+                // Nested elements are part of the preview of their parent
+                *node = Node::Html {
+                    html: format!("<div class=\"preview-container\">{}</div>\n", html),
+                    span: element.span,
+                };
+                components_count += 1;
+            }
+        });
+        walk_inlines_mut(&mut nodes, &mut |inline| {
+            let Inline::Jsx(element) = inline else {
+                return;
+            };
+            if let Some(html) =
+                self.jsx_preview(element, &mut web_components, &mut generated_components)
+            {
+                *inline = Inline::Html(html);
+                components_count += 1;
+            }
+        });
+
         // Render markdown to HTML
-        let content_html = self.render_markdown(
-            &page.doc.content,
-            &page.doc.code_blocks,
-            &block_replacements,
-        );
+        let content_html = self.render_markdown(nodes, &block_replacements);
 
         // Build TOC
-        let toc: Vec<TocEntry> = page
-            .doc
-            .toc
-            .iter()
-            .map(|e| TocEntry {
-                title: e.title.clone(),
-                id: e.id.clone(),
-                level: e.level,
-            })
-            .collect();
+        let settings = page.doc.frontmatter.as_ref().and_then(|f| f.toc);
+        let toc = match self.config.toc.levels(settings) {
+            Some((min, max)) => toc_entries(&page.doc.toc_between(min, max)),
+            None => Vec::new(),
+        };
 
         // Build context
         let title = page
@@ -505,12 +662,31 @@ impl StaticBuilder {
                     format!("{}assets/{}", self.config.base_url, filename)
                 })
                 .collect(),
+            meta: page
+                .doc
+                .frontmatter
+                .as_ref()
+                .map(|f| f.extra.clone())
+                .unwrap_or_default(),
+            draft: page.doc.frontmatter.as_ref().is_some_and(|f| f.draft),
+            scheduled: page
+                .doc
+                .frontmatter
+                .as_ref()
+                .and_then(|f| f.scheduled(&today()))
+                .map(str::to_string),
         };
 
-        // Render template
-        let html = self
-            .templates
-            .render_page("doc.html", &context)
+        // Render the page's layout, checked when the page was discovered
+        let layout = page
+            .doc
+            .frontmatter
+            .as_ref()
+            .and_then(|f| f.layout.as_deref())
+            .unwrap_or("doc");
+        let template = templates.layout_template(layout).unwrap_or("doc.html");
+        let html = templates
+            .render_page(template, &context)
             .map_err(|e: minijinja::Error| BuildError::TemplateError(e.to_string()))?;
 
         // Ensure output directory exists
@@ -524,6 +700,58 @@ impl StaticBuilder {
         Ok((1, components_count))
     }
 
+    /// Render a component usage as its preview custom element, generating the
+    /// component's Web Component once per page.
+    fn component_preview(
+        &self,
+        jsx: &InlineJsx,
+        web_components: &mut Vec<TransformedBlock>,
+        generated_components: &mut HashMap<String, String>,
+    ) -> Option<String> {
+        let component_name = &jsx.component;
+
+        // Only generate Web Component JS once per component type
+        if !generated_components.contains_key(component_name) {
+            // Generate unique tag name for this component type
+            let tag_name = format!("{}-preview", component_name.to_lowercase());
+            match self
+                .registry
+                .generate_web_component(component_name, &tag_name)
+            {
+                Ok(transformed) => {
+                    generated_components.insert(component_name.clone(), tag_name);
+                    web_components.push(transformed);
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to generate Web Component for {}: {}",
+                        component_name,
+                        e
+                    );
+                    return None;
+                }
+            }
+        }
+
+        // Convert inline JSX to custom element HTML
+        let tag_name = &generated_components[component_name];
+        Some(to_custom_element(jsx, tag_name))
+    }
+
+    /// Render a registered component used in prose as its preview.
+    fn jsx_preview(
+        &self,
+        element: &JsxElement,
+        web_components: &mut Vec<TransformedBlock>,
+        generated_components: &mut HashMap<String, String>,
+    ) -> Option<String> {
+        let jsx = parse_inline_jsx(&element.source)?;
+        if !self.registry.contains(&jsx.component) {
+            return None;
+        }
+        self.component_preview(&jsx, web_components, generated_components)
+    }
+
     /// Transform a code block to a Web Component.
     fn transform_block(
         &self,
@@ -532,68 +760,73 @@ impl StaticBuilder {
     ) -> Result<TransformedBlock, BuildError> {
         let ctx = TransformContext::default();
 
-        self.adapter
+        let ext = match block.language {
+            veneer_mdx::Language::Jsx => "jsx",
+            veneer_mdx::Language::Vue => "vue",
+            veneer_mdx::Language::Svelte => "svelte",
+            _ => "tsx",
+        };
+        let adapter = self.registry.adapter_for_extension(ext).ok_or_else(|| {
+            BuildError::TransformError(format!("No adapter handles .{ext} blocks"))
+        })?;
+
+        adapter
             .transform(&block.source, tag_name, &ctx)
             .map_err(|e| BuildError::TransformError(e.to_string()))
     }
 
-    /// Render markdown to HTML, replacing live blocks with Web Components.
+    /// Render the document tree to HTML, replacing live blocks with Web
+    /// Components and `<PropsTable of="Button" />` elements with API tables.
     fn render_markdown(
         &self,
-        content: &str,
-        code_blocks: &[CodeBlock],
+        mut nodes: Vec<Node>,
         block_replacements: &HashMap<String, String>,
     ) -> String {
-        use pulldown_cmark::{html, Options, Parser};
-        use regex::Regex;
-
-        // First, replace live code blocks in the markdown with markers
-        let mut processed_content = content.to_string();
-
-        for block in code_blocks {
-            if block.is_live() {
-                if let Some(replacement_html) = block_replacements.get(&block.id) {
-                    // Find the code block in the content and replace with preview HTML
-                    // Code blocks are fenced with ```lang live ... ```
-                    // Note: Regex is compiled per-block because pattern includes dynamic source content.
-                    // This is acceptable since there are typically few live blocks per document.
-                    let escaped_source = regex::escape(&block.source);
-                    let pattern =
-                        format!(r"```[a-z]+\s+live[^\n]*\n{}\n?```", escaped_source.trim());
-
-                    if let Ok(re) = Regex::new(&pattern) {
-                        let preview = format!(
-                            r#"<div class="preview-container">{}</div>
-
-```{}
-{}
-```"#,
-                            replacement_html,
-                            match block.language {
-                                veneer_mdx::Language::Tsx => "tsx",
-                                veneer_mdx::Language::Jsx => "jsx",
-                                _ => "tsx",
-                            },
-                            block.source.trim()
-                        );
-                        processed_content =
-                            re.replace(&processed_content, preview.as_str()).to_string();
-                    }
+        walk_mut(&mut nodes, &mut |node| match node {
+            Node::Code(block) => {
+                if let Some(preview) = block_replacements.get(&block.id) {
+                    let code = render_code_block(block);
+                    *node = Node::Html {
+                        html: format!(
+                            "<div class=\"preview-container\">{}</div>\n{}",
+                            preview, code
+                        ),
+                        span: node.span(),
+                    };
                 }
             }
-        }
-
-        let options = Options::ENABLE_TABLES
-            | Options::ENABLE_FOOTNOTES
-            | Options::ENABLE_STRIKETHROUGH
-            | Options::ENABLE_TASKLISTS;
-
-        let parser = Parser::new_ext(&processed_content, options);
-
-        let mut html_output = String::new();
-        html::push_html(&mut html_output, parser);
+            Node::Jsx(element) if element.name == "PropsTable" => {
+                let of = parse_inline_jsx(&element.source).and_then(|jsx| {
+                    jsx.props
+                        .get("of")
+                        .and_then(|v| v.as_str())
+                        .map(String::from)
+                });
+                let html = match of {
+                    Some(name) => match self.registry.get(&name) {
+                        Some(component) => render_props_table(&component.structure.props),
+                        None => {
+                            tracing::warn!(
+                                "PropsTable: component '{}' not found in registry",
+                                name
+                            );
+                            String::new()
+                        }
+                    },
+                    None => {
+                        tracing::warn!("PropsTable without an `of` attribute");
+                        String::new()
+                    }
+                };
+                *node = Node::Html {
+                    html,
+                    span: element.span,
+                };
+            }
+            _ => {}
+        });
 
-        html_output
+        render_document(nodes)
     }
 
     /// Generate static assets.
@@ -659,12 +892,15 @@ impl StaticBuilder {
 
                 let url = self.path_to_url(&page.output_path);
 
-                // Extract text content (simplified)
+                // Extract text content (simplified) from the first paragraphs
                 let content = page
                     .doc
-                    .content
-                    .lines()
-                    .filter(|l| !l.starts_with('#') && !l.starts_with("```"))
+                    .nodes
+                    .iter()
+                    .filter_map(|node| match node {
+                        Node::Paragraph { children, .. } => Some(plain_text(children)),
+                        _ => None,
+                    })
                     .take(10)
                     .collect::<Vec<_>>()
                     .join(" ");
@@ -724,6 +960,59 @@ impl StaticBuilder {
     }
 }
 
+/// Render an API table for a component's props.
+///
+/// The table is written without blank lines so Markdown keeps it as one HTML block.
+fn render_props_table(props: &[PropDoc]) -> String {
+    let mut html = String::from(
+        "<table class=\"props-table\">\n<thead><tr><th>Prop</th><th>Type</th><th>Default</th><th>Description</th></tr></thead>\n<tbody>\n",
+    );
+
+    for prop in props {
+        let required = if prop.required {
+            r#" <span class="props-required">required</span>"#
+        } else {
+            ""
+        };
+        let default = prop
+            .default
+            .as_deref()
+            .map(|d| format!("<code>{}</code>", escape_html(d)))
+            .unwrap_or_else(|| "-".to_string());
+        html.push_str(&format!(
+            "<tr><td><code>{}</code>{}</td><td><code>{}</code></td><td>{}</td><td>{}</td></tr>\n",
+            escape_html(&prop.name),
+            required,
+            escape_html(&prop.ty),
+            default,
+            escape_html(prop.description.as_deref().unwrap_or("")),
+        ));
+    }
+
+    html.push_str("</tbody>\n</table>\n");
+    html
+}
+
+/// Whether a docs file is a partial, named or in a directory starting with `_`.
+pub fn is_partial(relative: &Path) -> bool {
+    relative
+        .components()
+        .any(|c| c.as_os_str().to_string_lossy().starts_with('_'))
+}
+
+/// Convert parsed TOC entries for the template.
+fn toc_entries(entries: &[veneer_mdx::TocEntry]) -> Vec<TocEntry> {
+    entries
+        .iter()
+        .map(|e| TocEntry {
+            title: e.title.clone(),
+            id: e.id.clone(),
+            level: e.level,
+            children: toc_entries(&e.children),
+        })
+        .collect()
+}
+
 /// Capitalize first letter of a string.
 fn capitalize(s: &str) -> String {
     let mut chars = s.chars();
@@ -737,6 +1026,7 @@ fn capitalize(s: &str) -> String {
 mod tests {
     use super::*;
     use tempfile::tempdir;
+    use veneer_mdx::FieldSchema;
 
     #[tokio::test]
     async fn builds_simple_site() {
@@ -768,6 +1058,124 @@ title: Home
         assert!(out.join("index.html").exists());
     }
 
+    #[tokio::test]
+    async fn renders_props_tables() {
+        let temp = tempdir().unwrap();
+        let docs = temp.path().join("docs");
+        let components = temp.path().join("components");
+        let out = temp.path().join("dist");
+
+        fs::create_dir_all(&docs).unwrap();
+        fs::create_dir_all(&components).unwrap();
+        fs::write(
+            components.join("button.tsx"),
+            r#"
+const variantClasses = { default: 'bg-primary', ghost: 'bg-transparent' };
+
+interface ButtonProps {
+  /** Visual style of the <button>. */
+  variant?: 'default' | 'ghost';
+}
+
+export function Button({ variant = 'default' }: ButtonProps) {}
+"#,
+        )
+        .unwrap();
+        fs::write(
+            docs.join("index.mdx"),
+            "---\ntitle: Button\n---\n# Button\n\n<PropsTable of=\"Button\" />\n",
+        )
+        .unwrap();
+
+        let config = BuildConfig {
+            docs_dir: docs,
+            output_dir: out.clone(),
+            components_dir: Some(components),
+            ..Default::default()
+        };
+
+        StaticBuilder::new(config).build().await.unwrap();
+
+        let html = fs::read_to_string(out.join("index.html")).unwrap();
+        assert!(html.contains(r#"<table class="props-table">"#));
+        assert!(html.contains("<code>'default' | 'ghost'</code>"));
+        assert!(html.contains("Visual style of the &lt;button&gt;."));
+        assert!(!html.contains("PropsTable"));
+    }
+
+    #[tokio::test]
+    async fn renders_jsx_in_prose() {
+        let temp = tempdir().unwrap();
+        let docs = temp.path().join("docs");
+        let components = temp.path().join("components");
+        let out = temp.path().join("dist");
+
+        fs::create_dir_all(&docs).unwrap();
+        fs::create_dir_all(&components).unwrap();
+        fs::write(
+            components.join("button.tsx"),
+            "const variantClasses = { default: 'bg-primary', outline: 'border' };\nexport function Button() {}",
+        )
+        .unwrap();
+        fs::write(
+            docs.join("index.mdx"),
+            r#"---
+title: Button
+---
+import { Button } from '../components/button';
+
+Press <Button>Save</Button> to continue.
+
+<Button variant="outline">Outline</Button>
+
+<Unknown>kept</Unknown>
+"#,
+        )
+        .unwrap();
+
+        let config = BuildConfig {
+            docs_dir: docs,
+            output_dir: out.clone(),
+            components_dir: Some(components),
+            ..Default::default()
+        };
+
+        let result = StaticBuilder::new(config).build().await.unwrap();
+        assert_eq!(result.components, 2);
+
+        let html = fs::read_to_string(out.join("index.html")).unwrap();
+        assert!(!html.contains("import { Button }"));
+        assert!(html.contains("Press <button-preview>Save</button-preview> to continue."));
+        assert!(html.contains(
+            r#"<div class="preview-container"><button-preview variant="outline">Outline</button-preview></div>"#
+        ));
+        assert!(html.contains("<Unknown>kept</Unknown>"));
+    }
+
+    #[tokio::test]
+    async fn reports_parse_errors_as_code_frames() {
+        let temp = tempdir().unwrap();
+        let docs = temp.path().join("docs");
+
+        fs::create_dir_all(&docs).unwrap();
+        fs::write(
+            docs.join("index.mdx"),
+            "---\ntitle: Home\n---\n# Home\n\nPress <Button>Save\n",
+        )
+        .unwrap();
+
+        let builder = StaticBuilder::new(BuildConfig {
+            docs_dir: docs,
+            output_dir: temp.path().join("dist"),
+            ..Default::default()
+        });
+
+        let message = builder.build().await.unwrap_err().to_string();
+        assert!(message.contains("Unclosed element <Button>"));
+        assert!(message.contains("index.mdx:6:7"));
+        assert!(message.contains("6 | Press <Button>Save\n  |       ^^^^^^^^"));
+    }
+
     #[tokio::test]
     async fn generates_search_index() {
         let temp = tempdir().unwrap();
@@ -792,4 +1200,316 @@ title: Home
         let index = fs::read_to_string(out.join("search-index.json")).unwrap();
         assert!(index.contains("Test"));
     }
+
+    #[tokio::test]
+    async fn includes_partials() {
+        let temp = tempdir().unwrap();
+        let docs = temp.path().join("docs");
+        let out = temp.path().join("dist");
+
+        fs::create_dir_all(docs.join("_partials")).unwrap();
+        fs::write(
+            docs.join("_partials/install.mdx"),
+            "## Install\n\n```sh\nnpm i veneer\n```\n",
+        )
+        .unwrap();
+        fs::write(
+            docs.join("index.mdx"),
+            "---\ntitle: Home\n---\n# Home\n\n<Include src=\"_partials/install.mdx\" />\n",
+        )
+        .unwrap();
+
+        let builder = StaticBuilder::new(BuildConfig {
+            docs_dir: docs,
+            output_dir: out.clone(),
+            ..Default::default()
+        });
+
+        let result = builder.build().await.unwrap();
+        assert_eq!(result.pages, 1);
+        assert!(!out.join("_partials").exists());
+
+        let html = fs::read_to_string(out.join("index.html")).unwrap();
+        assert!(html.contains(r#"<h2 id="install">"#));
+        assert!(html.contains("npm"));
+    }
+
+    #[tokio::test]
+    async fn keeps_previews_of_included_blocks_apart() {
+        let temp = tempdir().unwrap();
+        let docs = temp.path().join("docs");
+        let components = temp.path().join("components");
+        let out = temp.path().join("dist");
+
+        fs::create_dir_all(docs.join("_partials")).unwrap();
+        fs::create_dir_all(&components).unwrap();
+        fs::write(
+            components.join("button.tsx"),
+            "const variantClasses = { default: 'bg-primary' };\nexport function Button() {}",
+        )
+        .unwrap();
+        fs::write(
+            components.join("badge.tsx"),
+            "const variantClasses = { default: 'bg-muted' };\nexport function Badge() {}",
+        )
+        .unwrap();
+        // Both live blocks open on line 3 of their file
+        fs::write(
+            docs.join("_partials/badge.mdx"),
+            "Badges\n\n```tsx live\n<Badge>New</Badge>\n```\n",
+        )
+        .unwrap();
+        fs::write(
+            docs.join("index.mdx"),
+            "# Home\n\n```tsx live\n<Button>Save</Button>\n```\n\n<Include src=\"_partials/badge.mdx\" />\n\n<Include src=\"_partials/badge.mdx\" />\n",
+        )
+        .unwrap();
+
+        let result = StaticBuilder::new(BuildConfig {
+            docs_dir: docs,
+            output_dir: out.clone(),
+            components_dir: Some(components),
+            ..Default::default()
+        })
+        .build()
+        .await
+        .unwrap();
+        assert_eq!(result.components, 3);
+
+        let html = fs::read_to_string(out.join("index.html")).unwrap();
+        assert_eq!(
+            html.matches("<button-preview>Save</button-preview>")
+                .count(),
+            1
+        );
+        assert_eq!(
+            html.matches("<badge-preview>New</badge-preview>").count(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn validates_frontmatter() {
+        let temp = tempdir().unwrap();
+        let docs = temp.path().join("docs");
+
+        fs::create_dir_all(&docs).unwrap();
+        fs::write(
+            docs.join("index.mdx"),
+            "---\ntitle: Home\nstatus: alpha\n---\n# Home\n",
+        )
+        .unwrap();
+        fs::write(docs.join("other.mdx"), "---\ntitle: Other\n---\n").unwrap();
+
+        let builder = StaticBuilder::new(BuildConfig {
+            docs_dir: docs,
+            output_dir: temp.path().join("dist"),
+            frontmatter_schema: FrontmatterSchema {
+                fields: [(
+                    "status".to_string(),
+                    FieldSchema {
+                        required: true,
+                        values: Some(vec!["beta".to_string(), "stable".to_string()]),
+                        ..Default::default()
+                    },
+                )]
+                .into(),
+            },
+            ..Default::default()
+        });
+
+        let error = builder.build().await.unwrap_err();
+        let message = error.to_string();
+        assert!(matches!(error, BuildError::SchemaError { count: 2, .. }));
+        assert!(message.contains("index.mdx:3:1"));
+        assert!(message.contains("Frontmatter field `status` cannot be `alpha`"));
+        assert!(message.contains("other.mdx:1:1"));
+    }
+
+    #[tokio::test]
+    async fn leaves_out_drafts() {
+        let temp = tempdir().unwrap();
+        let docs = temp.path().join("docs");
+        let out = temp.path().join("dist");
+
+        fs::create_dir_all(&docs).unwrap();
+        fs::write(docs.join("index.mdx"), "---\ntitle: Home\n---\n# Home\n").unwrap();
+        fs::write(
+            docs.join("wip.mdx"),
+            "---\ntitle: Work in progress\ndraft: true\n---\n# WIP\n",
+        )
+        .unwrap();
+        fs::write(
+            docs.join("later.mdx"),
+            "---\ntitle: Coming later\npublish_date: 2999-01-01\n---\n# Later\n",
+        )
+        .unwrap();
+        fs::write(
+            docs.join("past.mdx"),
+            "---\ntitle: Already out\npublish_date: 2020-01-01\n---\n# Past\n",
+        )
+        .unwrap();
+
+        let config = BuildConfig {
+            docs_dir: docs,
+            output_dir: out.clone(),
+            ..Default::default()
+        };
+
+        let result = StaticBuilder::new(config.clone()).build().await.unwrap();
+        assert_eq!(result.pages, 2);
+        assert!(!out.join("wip/index.html").exists());
+        assert!(out.join("past/index.html").exists());
+        let index = fs::read_to_string(out.join("index.html")).unwrap();
+        assert!(!index.contains("Work in progress"));
+        assert!(!index.contains("Coming later"));
+        for file in ["sitemap.xml", "search-index.json"] {
+            let text = fs::read_to_string(out.join(file)).unwrap();
+            assert!(!text.contains("wip") && !text.contains("later"), "{file}");
+        }
+
+        let result = StaticBuilder::new(BuildConfig {
+            drafts: true,
+            ..config
+        })
+        .build()
+        .await
+        .unwrap();
+        assert_eq!(result.pages, 4);
+        let wip = fs::read_to_string(out.join("wip/index.html")).unwrap();
+        assert!(wip.contains(r#"class="draft-banner""#));
+        let later = fs::read_to_string(out.join("later/index.html")).unwrap();
+        assert!(later.contains("Scheduled for 2999-01-01"));
+    }
+
+    #[tokio::test]
+    async fn limits_toc_depth() {
+        let temp = tempdir().unwrap();
+        let docs = temp.path().join("docs");
+        let out = temp.path().join("dist");
+
+        fs::create_dir_all(&docs).unwrap();
+        fs::write(
+            docs.join("index.mdx"),
+            "---\ntitle: Home\ntoc: { max: 2 }\n---\n# Home\n\n## Usage\n\n### Sizes\n",
+        )
+        .unwrap();
+        fs::write(
+            docs.join("plain.mdx"),
+            "---\ntitle: Plain\ntoc: false\n---\n# Plain\n\n## Usage\n",
+        )
+        .unwrap();
+
+        let builder = StaticBuilder::new(BuildConfig {
+            docs_dir: docs,
+            output_dir: out.clone(),
+            toc: TocConfig {
+                min_depth: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        builder.build().await.unwrap();
+
+        let html = fs::read_to_string(out.join("index.html")).unwrap();
+        assert!(html.contains(r##"<a href="#usage">Usage</a>"##));
+        assert!(!html.contains(r##"<a href="#home">"##));
+        assert!(!html.contains(r##"<a href="#sizes">"##));
+
+        let html = fs::read_to_string(out.join("plain/index.html")).unwrap();
+        assert!(!html.contains(r#"class="toc""#));
+    }
+
+    #[tokio::test]
+    async fn renders_page_layouts() {
+        let temp = tempdir().unwrap();
+        let docs = temp.path().join("docs");
+        let out = temp.path().join("dist");
+
+        fs::create_dir_all(&docs).unwrap();
+        fs::write(
+            temp.path().join("splash.html"),
+            r#"{% extends "base.html" %}{% block content %}<section class="hero">{{ content | safe }}</section>{% endblock %}"#,
+        )
+        .unwrap();
+        fs::write(
+            docs.join("index.mdx"),
+            "---\ntitle: Home\nlayout: splash\n---\n# Home\n",
+        )
+        .unwrap();
+        fs::write(
+            docs.join("gallery.mdx"),
+            "---\ntitle: Gallery\nlayout: full-width\n---\n# Gallery\n\n## Buttons\n",
+        )
+        .unwrap();
+        fs::write(docs.join("guide.mdx"), "---\ntitle: Guide\n---\n# Guide\n").unwrap();
+
+        let config = BuildConfig {
+            docs_dir: docs.clone(),
+            output_dir: out.clone(),
+            layouts: [("splash".to_string(), temp.path().join("splash.html"))].into(),
+            ..Default::default()
+        };
+
+        StaticBuilder::new(config.clone()).build().await.unwrap();
+
+        let html = fs::read_to_string(out.join("index.html")).unwrap();
+        assert!(html.contains(r#"<section class="hero"><h1 id="home">"#));
+        let html = fs::read_to_string(out.join("gallery/index.html")).unwrap();
+        assert!(html.contains(r#"<main class="main main-full">"#));
+        assert!(!html.contains(r#"class="toc""#));
+        let html = fs::read_to_string(out.join("guide/index.html")).unwrap();
+        assert!(html.contains(r#"<article class="doc">"#));
+
+        fs::write(
+            docs.join("changelog.mdx"),
+            "---\ntitle: Changelog\nlayout: changelog\n---\n",
+        )
+        .unwrap();
+        let error = StaticBuilder::new(config).build().await.unwrap_err();
+        let message = error.to_string();
+        assert!(matches!(error, BuildError::LayoutError { .. }));
+        assert!(message.contains("No layout named `changelog`"));
+        assert!(message.contains("changelog.mdx:3:1"));
+        assert!(message.contains("use one of: doc, full-width, splash"));
+    }
+
+    #[tokio::test]
+    async fn uses_theme_templates() {
+        let temp = tempdir().unwrap();
+        let docs = temp.path().join("docs");
+        let theme = temp.path().join("theme");
+        let out = temp.path().join("dist");
+
+        fs::create_dir_all(&docs).unwrap();
+        fs::create_dir_all(&theme).unwrap();
+        fs::write(docs.join("index.mdx"), "---\ntitle: Home\n---\n# Home\n").unwrap();
+        fs::write(
+            theme.join("base.html"),
+            r#"<html><body class="themed">{% block content %}{% endblock %}</body></html>"#,
+        )
+        .unwrap();
+
+        let config = BuildConfig {
+            docs_dir: docs,
+            output_dir: out.clone(),
+            templates_dir: Some(theme.clone()),
+            ..Default::default()
+        };
+
+        StaticBuilder::new(config.clone()).build().await.unwrap();
+        let html = fs::read_to_string(out.join("index.html")).unwrap();
+        assert!(html.starts_with(r#"<html><body class="themed">"#));
+        assert!(html.contains(r#"<article class="doc">"#));
+
+        let error = StaticBuilder::new(BuildConfig {
+            templates_dir: Some(temp.path().join("missing")),
+            ..config
+        })
+        .build()
+        .await
+        .unwrap_err();
+        assert!(error.to_string().contains("Templates directory not found"));
+    }
 }
@@ -0,0 +1,1371 @@
+//! Build-time syntax highlighting for code blocks.
+//!
+//! Each language has a small hand-written lexer. Tokens render as
+//! `<span class="hl-{kind}">` elements colored by the theme through CSS
+//! variables, so pages need no client-side JavaScript for highlighting.
+//! Spans never cross a line break, so every line of the output is balanced
+//! HTML on its own.
+
+use std::ops::Range;
+
+use veneer_mdx::{escape_html, CodeBlock, Language, LineDiff};
+
+/// Kind of a highlighted token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Keyword,
+    String,
+    Comment,
+    Number,
+    /// `true`, `null` and other literal names
+    Constant,
+    /// Called functions, macros and shell commands
+    Function,
+    Type,
+    /// Markup and JSX tag names, and CSS element selectors
+    Tag,
+    Attribute,
+    /// Object keys, CSS properties and configuration keys
+    Property,
+    Variable,
+    Operator,
+    Punctuation,
+    /// Doctypes, Rust attributes, TOML tables and diff headers
+    Meta,
+    /// Added diff lines
+    Inserted,
+    /// Removed diff lines
+    Deleted,
+}
+
+impl TokenKind {
+    /// CSS class of the token.
+    pub fn class(&self) -> &'static str {
+        match self {
+            TokenKind::Keyword => "hl-keyword",
+            TokenKind::String => "hl-string",
+            TokenKind::Comment => "hl-comment",
+            TokenKind::Number => "hl-number",
+            TokenKind::Constant => "hl-constant",
+            TokenKind::Function => "hl-function",
+            TokenKind::Type => "hl-type",
+            TokenKind::Tag => "hl-tag",
+            TokenKind::Attribute => "hl-attribute",
+            TokenKind::Property => "hl-property",
+            TokenKind::Variable => "hl-variable",
+            TokenKind::Operator => "hl-operator",
+            TokenKind::Punctuation => "hl-punctuation",
+            TokenKind::Meta => "hl-meta",
+            TokenKind::Inserted => "hl-inserted",
+            TokenKind::Deleted => "hl-deleted",
+        }
+    }
+}
+
+/// Render a code block as a highlighted `<pre><code>` element, with the
+/// same `language-*` class as plain Markdown rendering.
+///
+/// Each line is a `<span class="line">`, with `highlighted`, `diff-add` or
+/// `diff-remove` classes from the fence meta. Line numbers are CSS counters,
+/// so they are not copied with the code. A title or filename becomes a
+/// caption above the block.
+pub fn render_code_block(block: &CodeBlock) -> String {
+    let class = match block.lang() {
+        Some(lang) => format!(" class=\"language-{}\"", escape_html(lang)),
+        None => String::new(),
+    };
+    let pre_class = if block.line_numbers {
+        " class=\"line-numbers\""
+    } else {
+        ""
+    };
+
+    let mut code = String::new();
+    let highlighted = highlight(&block.source, block.language);
+    let source_lines = block.source.lines().count();
+    for (i, line) in highlighted.split('\n').take(source_lines).enumerate() {
+        let number = i + 1;
+        let mut classes = String::from("line");
+        if block.is_highlighted(number) {
+            classes.push_str(" highlighted");
+        }
+        match block.line_diff(number) {
+            Some(LineDiff::Added) => classes.push_str(" diff-add"),
+            Some(LineDiff::Removed) => classes.push_str(" diff-remove"),
+            None => {}
+        }
+        code.push_str(&format!("<span class=\"{}\">{}</span>\n", classes, line));
+    }
+
+    let pre = format!("<pre{}><code{}>{}</code></pre>\n", pre_class, class, code);
+    match block.title.as_ref().or(block.filename.as_ref()) {
+        Some(title) => format!(
+            "<figure class=\"code-block\">\n<figcaption class=\"code-title\">{}</figcaption>\n{}</figure>\n",
+            escape_html(title),
+            pre
+        ),
+        None => pre,
+    }
+}
+
+/// Highlight source code as HTML. Code in an unknown language is only
+/// escaped.
+pub fn highlight(source: &str, language: Language) -> String {
+    let mut lexer = Lexer::new(source);
+    match language {
+        Language::TypeScript | Language::JavaScript => lexer.script(&JS, false),
+        Language::Tsx | Language::Jsx => lexer.script(&JSX, false),
+        Language::Rust => lexer.script(&RUST, false),
+        Language::Html => lexer.markup(Markup::Html),
+        Language::Vue => lexer.markup(Markup::Vue),
+        Language::Svelte => lexer.markup(Markup::Svelte),
+        Language::Css => lexer.css(),
+        Language::Json => lexer.json(),
+        Language::Bash => lexer.bash(),
+        Language::Toml => lexer.toml(),
+        Language::Yaml => lexer.yaml(),
+        Language::Diff => lexer.diff(),
+        Language::Unknown => {}
+    }
+    lexer.into_html()
+}
+
+/// Words of a C-like language.
+struct Script {
+    keywords: &'static [&'static str],
+    constants: &'static [&'static str],
+    types: &'static [&'static str],
+    jsx: bool,
+    rust: bool,
+}
+
+const JS_KEYWORDS: &[&str] = &[
+    "abstract",
+    "as",
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "declare",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "finally",
+    "for",
+    "from",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "keyof",
+    "let",
+    "namespace",
+    "new",
+    "of",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "return",
+    "satisfies",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "try",
+    "type",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "yield",
+];
+
+const JS_CONSTANTS: &[&str] = &["true", "false", "null", "undefined", "NaN", "Infinity"];
+
+const JS_TYPES: &[&str] = &[
+    "any", "bigint", "boolean", "never", "number", "object", "string", "symbol", "unknown",
+];
+
+const JS: Script = Script {
+    keywords: JS_KEYWORDS,
+    constants: JS_CONSTANTS,
+    types: JS_TYPES,
+    jsx: false,
+    rust: false,
+};
+
+const JSX: Script = Script { jsx: true, ..JS };
+
+const RUST: Script = Script {
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+        "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "type",
+        "unsafe", "use", "where", "while",
+    ],
+    constants: &["true", "false"],
+    types: &[
+        "bool", "char", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "str", "u8",
+        "u16", "u32", "u64", "u128", "usize",
+    ],
+    jsx: false,
+    rust: true,
+};
+
+const BASH_KEYWORDS: &[&str] = &[
+    "case", "declare", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function",
+    "if", "in", "local", "readonly", "return", "select", "then", "until", "while",
+];
+
+/// Flavor of HTML-like markup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Markup {
+    Html,
+    /// `{{ expression }}` interpolation
+    Vue,
+    /// `{expression}` and `{#if}` blocks
+    Svelte,
+}
+
+/// Position in a CSS rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CssState {
+    Selector,
+    Property,
+    Value,
+}
+
+/// Splits source code into tokens.
+struct Lexer<'a> {
+    source: &'a str,
+    pos: usize,
+    /// End of the text being lexed; narrowed for code embedded in markup
+    end: usize,
+    /// Tokens in source order; `None` is plain text
+    tokens: Vec<(Option<TokenKind>, Range<usize>)>,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            pos: 0,
+            end: source.len(),
+            tokens: Vec::new(),
+        }
+    }
+
+    /// Render the tokens, and any text left unlexed, as HTML.
+    fn into_html(mut self) -> String {
+        self.end = self.source.len();
+        let rest = self.rest().len();
+        self.plain(rest);
+
+        let mut html = String::new();
+        for (kind, range) in &self.tokens {
+            for (i, line) in self.source[range.clone()].split('\n').enumerate() {
+                if i > 0 {
+                    html.push('\n');
+                }
+                match kind {
+                    _ if line.is_empty() => {}
+                    Some(kind) => {
+                        html.push_str(&format!(
+                            "<span class=\"{}\">{}</span>",
+                            kind.class(),
+                            escape_html(line)
+                        ));
+                    }
+                    None => html.push_str(&escape_html(line)),
+                }
+            }
+        }
+        html
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.pos..self.end]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn peek_second(&self) -> Option<char> {
+        self.rest().chars().nth(1)
+    }
+
+    fn at(&self, prefix: &str) -> bool {
+        self.rest().starts_with(prefix)
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.pos == 0 || self.source[..self.pos].ends_with('\n')
+    }
+
+    /// Emit the next `len` bytes, merging with a previous token of the
+    /// same kind.
+    fn emit(&mut self, kind: Option<TokenKind>, len: usize) {
+        if len == 0 {
+            return;
+        }
+        let end = self.pos + len;
+        match self.tokens.last_mut() {
+            Some((last, range)) if *last == kind && range.end == self.pos => range.end = end,
+            _ => self.tokens.push((kind, self.pos..end)),
+        }
+        self.pos = end;
+    }
+
+    fn token(&mut self, kind: TokenKind, len: usize) {
+        self.emit(Some(kind), len);
+    }
+
+    fn plain(&mut self, len: usize) {
+        self.emit(None, len);
+    }
+
+    /// Emit the next character as plain text.
+    fn skip(&mut self) {
+        let len = self.peek().map_or(0, char::len_utf8);
+        self.plain(len);
+    }
+
+    fn len_while(&self, f: impl Fn(char) -> bool) -> usize {
+        let rest = self.rest();
+        rest.find(|c| !f(c)).unwrap_or(rest.len())
+    }
+
+    fn line_len(&self) -> usize {
+        self.len_while(|c| c != '\n')
+    }
+
+    /// Length up to and including `close`, searched from byte `from`, or
+    /// to the end when it is missing.
+    fn len_through(&self, close: &str, from: usize) -> usize {
+        let rest = self.rest();
+        rest.get(from..)
+            .and_then(|r| r.find(close))
+            .map_or(rest.len(), |i| from + i + close.len())
+    }
+
+    /// Length of a string literal opened by `quote`, with backslash
+    /// escapes. Unless `multiline`, an unclosed string ends at the line end.
+    fn string_len(&self, quote: char, multiline: bool) -> usize {
+        let rest = self.rest();
+        let mut escaped = false;
+        for (i, c) in rest.char_indices().skip(1) {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '\n' if !multiline => return i,
+                c if c == quote => return i + c.len_utf8(),
+                _ => {}
+            }
+        }
+        rest.len()
+    }
+
+    /// Length of a number: digits with letters for hex, exponents, suffixes
+    /// and units, underscores and decimal points.
+    fn number_len(&self) -> usize {
+        let rest = self.rest();
+        let mut len = 0;
+        for (i, c) in rest.char_indices() {
+            let decimal = c == '.' && rest[i + 1..].starts_with(|c: char| c.is_ascii_digit());
+            if c.is_ascii_alphanumeric() || c == '_' || decimal {
+                len = i + 1;
+            } else {
+                break;
+            }
+        }
+        len
+    }
+
+    fn ident_len(&self) -> usize {
+        match self.peek() {
+            Some(c) if is_ident_start(c) => self.len_while(is_ident),
+            _ => 0,
+        }
+    }
+
+    fn whitespace(&mut self) {
+        let len = self.len_while(char::is_whitespace);
+        self.plain(len);
+    }
+
+    /// Lex C-like code: JavaScript, TypeScript and Rust, with JSX for
+    /// `script.jsx`. When `nested`, stop before a `}` that closes an
+    /// enclosing `{`, as in JSX and template expressions.
+    fn script(&mut self, script: &Script, nested: bool) {
+        let mut depth = 0usize;
+        // Whether a `<` here would open a JSX tag rather than compare
+        let mut expression_start = true;
+
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                self.whitespace();
+                continue;
+            }
+            if self.at("//") {
+                let len = self.line_len();
+                self.token(TokenKind::Comment, len);
+                continue;
+            }
+            if self.at("/*") {
+                let len = self.len_through("*/", 2);
+                self.token(TokenKind::Comment, len);
+                continue;
+            }
+
+            expression_start = match c {
+                '{' => {
+                    depth += 1;
+                    self.token(TokenKind::Punctuation, 1);
+                    true
+                }
+                '}' if depth == 0 && nested => return,
+                '}' => {
+                    depth = depth.saturating_sub(1);
+                    self.token(TokenKind::Punctuation, 1);
+                    true
+                }
+                '\'' if script.rust => {
+                    self.rust_quote();
+                    false
+                }
+                '"' | '\'' | '`' => {
+                    let len = self.string_len(c, c == '`');
+                    self.token(TokenKind::String, len);
+                    false
+                }
+                '#' if script.rust && (self.at("#[") || self.at("#![")) => {
+                    let len = self.bracketed_len('[', ']');
+                    self.token(TokenKind::Meta, len);
+                    true
+                }
+                '<' if script.jsx
+                    && expression_start
+                    && self
+                        .peek_second()
+                        .is_some_and(|c| c.is_ascii_alphabetic() || c == '>') =>
+                {
+                    self.jsx_element(script);
+                    false
+                }
+                c if c.is_ascii_digit() => {
+                    let len = self.number_len();
+                    self.token(TokenKind::Number, len);
+                    false
+                }
+                c if is_ident_start(c) => self.script_word(script),
+                '=' | '+' | '-' | '*' | '/' | '%' | '!' | '&' | '|' | '^' | '~' | '?' | ':'
+                | '<' | '>' => {
+                    self.token(TokenKind::Operator, 1);
+                    true
+                }
+                '(' | '[' | ',' | ';' | '.' => {
+                    self.token(TokenKind::Punctuation, 1);
+                    true
+                }
+                ')' | ']' => {
+                    self.token(TokenKind::Punctuation, 1);
+                    false
+                }
+                _ => {
+                    self.skip();
+                    false
+                }
+            };
+        }
+    }
+
+    /// Lex an identifier in C-like code. Returns whether an expression may
+    /// follow it.
+    fn script_word(&mut self, script: &Script) -> bool {
+        let mut len = self.ident_len();
+        let word = &self.rest()[..len];
+        let after = &self.rest()[len..];
+
+        let kind = if script.keywords.contains(&word) {
+            Some(TokenKind::Keyword)
+        } else if script.constants.contains(&word) {
+            Some(TokenKind::Constant)
+        } else if script.types.contains(&word) {
+            Some(TokenKind::Type)
+        } else if script.rust && after.starts_with('!') && !after.starts_with("!=") {
+            // Macro invocation, `!` included
+            len += 1;
+            Some(TokenKind::Function)
+        } else if after.starts_with('(') {
+            Some(TokenKind::Function)
+        } else if word.starts_with(|c: char| c.is_ascii_uppercase()) {
+            Some(TokenKind::Type)
+        } else {
+            None
+        };
+
+        self.emit(kind, len);
+        kind == Some(TokenKind::Keyword)
+    }
+
+    /// Lex a Rust character literal or lifetime.
+    fn rust_quote(&mut self) {
+        let rest = self.rest();
+        let mut chars = rest.chars().skip(1);
+        let literal = matches!(
+            (chars.next(), chars.next()),
+            (Some('\\'), _) | (Some(_), Some('\''))
+        );
+
+        if literal {
+            let len = self.string_len('\'', false);
+            self.token(TokenKind::String, len);
+        } else {
+            let len = 1 + rest[1..].find(|c| !is_ident(c)).unwrap_or(rest.len() - 1);
+            self.token(TokenKind::Type, len);
+        }
+    }
+
+    /// Length of a bracketed group starting at the next `open`, with
+    /// nested groups.
+    fn bracketed_len(&self, open: char, close: char) -> usize {
+        let rest = self.rest();
+        let mut depth = 0;
+        for (i, c) in rest.char_indices() {
+            if c == open {
+                depth += 1;
+            } else if c == close {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+        }
+        rest.len()
+    }
+
+    /// Lex a `{...}` expression in JSX or markup.
+    fn braced_script(&mut self, script: &Script) {
+        self.token(TokenKind::Punctuation, 1);
+        self.script(script, true);
+        if self.peek() == Some('}') {
+            self.token(TokenKind::Punctuation, 1);
+        }
+    }
+
+    /// Lex a JSX element starting at `<`, with its children.
+    fn jsx_element(&mut self, script: &Script) {
+        self.token(TokenKind::Punctuation, 1);
+        let closing = self.peek() == Some('/');
+        if closing {
+            self.token(TokenKind::Punctuation, 1);
+        }
+        let len = self.len_while(|c| is_ident(c) || matches!(c, '.' | ':' | '-'));
+        self.token(TokenKind::Tag, len);
+
+        loop {
+            match self.peek() {
+                None => return,
+                Some('>') => {
+                    self.token(TokenKind::Punctuation, 1);
+                    break;
+                }
+                Some('/') if self.at("/>") => {
+                    self.token(TokenKind::Punctuation, 2);
+                    return;
+                }
+                Some('{') => self.braced_script(script),
+                Some(quote @ ('"' | '\'')) => {
+                    let len = self.string_len(quote, true);
+                    self.token(TokenKind::String, len);
+                }
+                Some('=') => self.token(TokenKind::Operator, 1),
+                Some(c) if is_ident_start(c) => {
+                    let len = self.len_while(|c| is_ident(c) || matches!(c, '-' | ':'));
+                    self.token(TokenKind::Attribute, len);
+                }
+                Some(_) => self.skip(),
+            }
+        }
+        if closing {
+            return;
+        }
+
+        // Children are text, elements and expressions up to the closing tag
+        while let Some(c) = self.peek() {
+            match c {
+                '<' if self.at("</") => {
+                    self.jsx_element(script);
+                    return;
+                }
+                '<' => self.jsx_element(script),
+                '{' => self.braced_script(script),
+                _ => self.skip(),
+            }
+        }
+    }
+
+    /// Lex HTML, or a Vue or Svelte component.
+    fn markup(&mut self, markup: Markup) {
+        while let Some(c) = self.peek() {
+            if self.at("<!--") {
+                let len = self.len_through("-->", 4);
+                self.token(TokenKind::Comment, len);
+            } else if self.at("<!") {
+                let len = self.len_through(">", 2);
+                self.token(TokenKind::Meta, len);
+            } else if c == '<'
+                && self
+                    .rest()
+                    .trim_start_matches(['<', '/'])
+                    .starts_with(|c: char| c.is_ascii_alphabetic())
+            {
+                self.markup_tag(markup);
+            } else if markup == Markup::Vue && self.at("{{") {
+                self.token(TokenKind::Punctuation, 2);
+                self.script(&JS, true);
+                if self.at("}}") {
+                    self.token(TokenKind::Punctuation, 2);
+                }
+            } else if markup == Markup::Svelte && c == '{' {
+                self.svelte_block();
+            } else if c == '&' {
+                let len = 1 + self.rest()[1..]
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '#'))
+                    .unwrap_or(0);
+                if self.rest()[len..].starts_with(';') {
+                    self.token(TokenKind::Constant, len + 1);
+                } else {
+                    self.skip();
+                }
+            } else {
+                self.skip();
+            }
+        }
+    }
+
+    /// Lex a markup tag starting at `<`. The content of `<script>` and
+    /// `<style>` elements is lexed as JavaScript and CSS.
+    fn markup_tag(&mut self, markup: Markup) {
+        self.token(TokenKind::Punctuation, 1);
+        let closing = self.peek() == Some('/');
+        if closing {
+            self.token(TokenKind::Punctuation, 1);
+        }
+        let len = self.len_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | ':' | '.'));
+        let name = self.rest()[..len].to_ascii_lowercase();
+        self.token(TokenKind::Tag, len);
+
+        loop {
+            match self.peek() {
+                None => return,
+                Some('>') => {
+                    self.token(TokenKind::Punctuation, 1);
+                    break;
+                }
+                Some('/') if self.at("/>") => {
+                    self.token(TokenKind::Punctuation, 2);
+                    return;
+                }
+                Some(quote @ ('"' | '\'')) => {
+                    let len = self.string_len(quote, true);
+                    self.token(TokenKind::String, len);
+                }
+                Some('{') if markup == Markup::Svelte => self.braced_script(&JS),
+                Some('=') => self.token(TokenKind::Operator, 1),
+                Some(c) if c.is_whitespace() => self.whitespace(),
+                Some(_) => {
+                    let len = self.len_while(|c| {
+                        !c.is_whitespace() && !matches!(c, '=' | '>' | '/' | '"' | '\'')
+                    });
+                    if len == 0 {
+                        self.skip();
+                    } else {
+                        self.token(TokenKind::Attribute, len);
+                    }
+                }
+            }
+        }
+
+        if !closing && (name == "script" || name == "style") {
+            let close = self
+                .rest()
+                .find(&format!("</{name}"))
+                .map_or(self.end, |i| self.pos + i);
+            let end = std::mem::replace(&mut self.end, close);
+            if name == "script" {
+                self.script(&JS, false);
+            } else {
+                self.css();
+            }
+            self.end = end;
+        }
+    }
+
+    /// Lex a Svelte `{expression}` or `{#block ...}` tag.
+    fn svelte_block(&mut self) {
+        self.token(TokenKind::Punctuation, 1);
+        if matches!(self.peek(), Some('#' | ':' | '/' | '@')) {
+            let len = 1 + self.rest()[1..]
+                .find(|c: char| !c.is_ascii_alphanumeric())
+                .unwrap_or(self.rest().len() - 1);
+            self.token(TokenKind::Keyword, len);
+        }
+        self.script(&JS, true);
+        if self.peek() == Some('}') {
+            self.token(TokenKind::Punctuation, 1);
+        }
+    }
+
+    /// Lex a CSS stylesheet.
+    fn css(&mut self) {
+        let mut depth = 0usize;
+        let mut state = CssState::Selector;
+        let mut statement_start = true;
+
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                self.whitespace();
+                continue;
+            }
+            if self.at("/*") {
+                let len = self.len_through("*/", 2);
+                self.token(TokenKind::Comment, len);
+                continue;
+            }
+            if statement_start {
+                let rest = self.rest();
+                let selector = rest
+                    .find(['{', ';', '}'])
+                    .is_some_and(|i| rest.as_bytes()[i] == b'{');
+                state = if depth == 0 || c == '@' || selector {
+                    CssState::Selector
+                } else {
+                    CssState::Property
+                };
+                statement_start = false;
+            }
+
+            match c {
+                '"' | '\'' => {
+                    let len = self.string_len(c, false);
+                    self.token(TokenKind::String, len);
+                }
+                '{' | '}' | ';' => {
+                    match c {
+                        '{' => depth += 1,
+                        '}' => depth = depth.saturating_sub(1),
+                        _ => {}
+                    }
+                    self.token(TokenKind::Punctuation, 1);
+                    statement_start = true;
+                }
+                ':' if state == CssState::Property => {
+                    self.token(TokenKind::Punctuation, 1);
+                    state = CssState::Value;
+                }
+                ':' if state == CssState::Selector => {
+                    // Pseudo-classes and pseudo-elements
+                    let colons = self.len_while(|c| c == ':');
+                    let len = colons
+                        + self.rest()[colons..]
+                            .find(|c| !is_css_ident(c))
+                            .unwrap_or(0);
+                    self.token(TokenKind::Attribute, len);
+                }
+                '@' => {
+                    let len = 1 + self.rest()[1..].find(|c| !is_css_ident(c)).unwrap_or(0);
+                    self.token(TokenKind::Keyword, len);
+                }
+                '!' if self.at("!important") => self.token(TokenKind::Keyword, 10),
+                '#' if state == CssState::Value => {
+                    let len = 1 + self.rest()[1..]
+                        .find(|c: char| !c.is_ascii_alphanumeric())
+                        .unwrap_or(self.rest().len() - 1);
+                    self.token(TokenKind::Number, len);
+                }
+                '#' | '.'
+                    if state == CssState::Selector
+                        && self.peek_second().is_some_and(is_css_ident) =>
+                {
+                    let len = 1 + self.rest()[1..].find(|c| !is_css_ident(c)).unwrap_or(0);
+                    self.token(TokenKind::Type, len);
+                }
+                c if c.is_ascii_digit()
+                    || (matches!(c, '.' | '-')
+                        && self.peek_second().is_some_and(|c| c.is_ascii_digit())) =>
+                {
+                    let sign = usize::from(c == '-');
+                    self.pos += sign;
+                    let mut len = self.number_len().max(1);
+                    self.pos -= sign;
+                    len += sign;
+                    if self.rest()[len..].starts_with('%') {
+                        len += 1;
+                    }
+                    self.token(TokenKind::Number, len);
+                }
+                c if is_css_ident(c) => {
+                    let len = self.len_while(is_css_ident);
+                    let word = &self.rest()[..len];
+                    let kind = match state {
+                        CssState::Selector => TokenKind::Tag,
+                        _ if word.starts_with("--") => TokenKind::Variable,
+                        CssState::Property => TokenKind::Property,
+                        CssState::Value if self.rest()[len..].starts_with('(') => {
+                            TokenKind::Function
+                        }
+                        CssState::Value => TokenKind::Constant,
+                    };
+                    self.token(kind, len);
+                }
+                '>' | '+' | '~' | '*' | '=' => self.token(TokenKind::Operator, 1),
+                '(' | ')' | '[' | ']' | ',' | ':' => self.token(TokenKind::Punctuation, 1),
+                _ => self.skip(),
+            }
+        }
+    }
+
+    /// Lex JSON, with `//` comments as in `tsconfig.json`.
+    fn json(&mut self) {
+        while let Some(c) = self.peek() {
+            match c {
+                '"' => {
+                    let len = self.string_len('"', false);
+                    let key = self.rest()[len..].trim_start().starts_with(':');
+                    let kind = if key {
+                        TokenKind::Property
+                    } else {
+                        TokenKind::String
+                    };
+                    self.token(kind, len);
+                }
+                '/' if self.at("//") => {
+                    let len = self.line_len();
+                    self.token(TokenKind::Comment, len);
+                }
+                '-' if self.peek_second().is_some_and(|c| c.is_ascii_digit()) => {
+                    self.pos += 1;
+                    let len = self.number_len();
+                    self.pos -= 1;
+                    self.token(TokenKind::Number, len + 1);
+                }
+                c if c.is_ascii_digit() => {
+                    let len = self.number_len();
+                    self.token(TokenKind::Number, len);
+                }
+                c if c.is_ascii_alphabetic() => {
+                    let len = self.len_while(|c| c.is_ascii_alphanumeric());
+                    let kind = matches!(&self.rest()[..len], "true" | "false" | "null")
+                        .then_some(TokenKind::Constant);
+                    self.emit(kind, len);
+                }
+                '{' | '}' | '[' | ']' | ',' | ':' => self.token(TokenKind::Punctuation, 1),
+                _ => self.skip(),
+            }
+        }
+    }
+
+    /// Lex a shell script or session. The first word of each command is
+    /// highlighted as a function, and a leading `$ ` as a prompt.
+    fn bash(&mut self) {
+        // Whether the next word is a command name
+        let mut command = true;
+        // Whether the next character starts a word
+        let mut word_start = true;
+
+        while let Some(c) = self.peek() {
+            let at_word_start = std::mem::replace(&mut word_start, false);
+            match c {
+                '\n' => {
+                    self.plain(1);
+                    command = true;
+                    word_start = true;
+                }
+                c if c.is_whitespace() => {
+                    self.whitespace();
+                    word_start = true;
+                }
+                '#' if at_word_start => {
+                    let len = self.line_len();
+                    self.token(TokenKind::Comment, len);
+                }
+                '\\' => {
+                    let len = 1 + self.peek_second().map_or(0, char::len_utf8);
+                    self.plain(len);
+                }
+                '\'' | '"' | '`' => {
+                    let len = self.string_len(c, true);
+                    self.token(TokenKind::String, len);
+                    command = false;
+                }
+                '$' if self.at("$ ") && self.at_line_start() => {
+                    self.token(TokenKind::Meta, 1);
+                    word_start = true;
+                }
+                '$' if self.at("$(") => {
+                    self.token(TokenKind::Punctuation, 2);
+                    command = true;
+                    word_start = true;
+                }
+                '$' => {
+                    let len = if self.at("${") {
+                        self.len_through("}", 2)
+                    } else {
+                        let rest = &self.rest()[1..];
+                        match rest.chars().next() {
+                            Some(c) if is_ident(c) => {
+                                1 + rest.find(|c| !is_ident(c)).unwrap_or(rest.len())
+                            }
+                            Some('?' | '@' | '#' | '*' | '!' | '$' | '-') => 2,
+                            _ => 1,
+                        }
+                    };
+                    self.token(TokenKind::Variable, len);
+                    command = false;
+                }
+                ';' | '|' | '&' | '(' | ')' | '<' | '>' => {
+                    let len =
+                        self.len_while(|c| matches!(c, ';' | '|' | '&' | '(' | ')' | '<' | '>'));
+                    self.token(TokenKind::Operator, len);
+                    command = c != '<' && c != '>' && c != ')';
+                    word_start = true;
+                }
+                _ => self.bash_word(&mut command, at_word_start),
+            }
+        }
+    }
+
+    /// Lex a word of a shell command.
+    fn bash_word(&mut self, command: &mut bool, at_word_start: bool) {
+        let len = self.len_while(|c| {
+            !c.is_whitespace()
+                && !matches!(
+                    c,
+                    ';' | '|' | '&' | '(' | ')' | '<' | '>' | '\'' | '"' | '`' | '$' | '\\'
+                )
+        });
+        if len == 0 {
+            self.skip();
+            return;
+        }
+        let word = &self.rest()[..len];
+
+        if at_word_start && BASH_KEYWORDS.contains(&word) {
+            self.token(TokenKind::Keyword, len);
+            *command = !matches!(word, "case" | "for" | "function" | "in" | "select");
+        } else if let Some(name) = word
+            .split_once('=')
+            .map(|(name, _)| name)
+            .filter(|name| *command && !name.is_empty() && name.chars().all(is_ident))
+        {
+            // `NAME=value` before a command
+            let name_len = name.len();
+            self.token(TokenKind::Variable, name_len);
+            self.token(TokenKind::Operator, 1);
+            self.plain(len - name_len - 1);
+        } else if *command && at_word_start {
+            self.token(TokenKind::Function, len);
+            *command = false;
+        } else if word.starts_with('-') {
+            self.token(TokenKind::Attribute, len);
+        } else {
+            self.plain(len);
+        }
+    }
+
+    /// Lex a TOML document.
+    fn toml(&mut self) {
+        // Whether the next bare word is a key
+        let mut key = true;
+        let mut inline_tables = 0usize;
+        let mut arrays = 0usize;
+
+        while let Some(c) = self.peek() {
+            match c {
+                '\n' => {
+                    self.plain(1);
+                    if inline_tables == 0 && arrays == 0 {
+                        key = true;
+                    }
+                }
+                c if c.is_whitespace() => self.whitespace(),
+                '#' => {
+                    let len = self.line_len();
+                    self.token(TokenKind::Comment, len);
+                }
+                '[' if key && inline_tables == 0 => {
+                    // Table header, `[table]` or `[[array.of.tables]]`
+                    let close = if self.at("[[") { "]]" } else { "]" };
+                    let line = self.line_len();
+                    let len = self.rest()[..line]
+                        .find(close)
+                        .map_or(line, |i| i + close.len());
+                    self.token(TokenKind::Meta, len);
+                }
+                '"' | '\'' => {
+                    let triple = if c == '"' { "\"\"\"" } else { "'''" };
+                    let len = if self.at(triple) {
+                        self.len_through(triple, 3)
+                    } else {
+                        self.string_len(c, false)
+                    };
+                    let kind = if key {
+                        TokenKind::Property
+                    } else {
+                        TokenKind::String
+                    };
+                    self.token(kind, len);
+                }
+                '=' => {
+                    self.token(TokenKind::Operator, 1);
+                    key = false;
+                }
+                '{' => {
+                    self.token(TokenKind::Punctuation, 1);
+                    inline_tables += 1;
+                    key = true;
+                }
+                '}' => {
+                    self.token(TokenKind::Punctuation, 1);
+                    inline_tables = inline_tables.saturating_sub(1);
+                    key = false;
+                }
+                '[' => {
+                    self.token(TokenKind::Punctuation, 1);
+                    arrays += 1;
+                }
+                ']' => {
+                    self.token(TokenKind::Punctuation, 1);
+                    arrays = arrays.saturating_sub(1);
+                }
+                ',' => {
+                    self.token(TokenKind::Punctuation, 1);
+                    key = inline_tables > 0 && arrays == 0;
+                }
+                '.' if key => self.token(TokenKind::Punctuation, 1),
+                _ if key => {
+                    let len =
+                        self.len_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'));
+                    if len == 0 {
+                        self.skip();
+                    } else {
+                        self.token(TokenKind::Property, len);
+                    }
+                }
+                c if c.is_ascii_digit() || matches!(c, '+' | '-') => {
+                    // Numbers, and dates such as 1979-05-27T07:32:00Z
+                    let len = self.len_while(|c| {
+                        c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | ':' | '-' | '+')
+                    });
+                    self.token(TokenKind::Number, len);
+                }
+                c if c.is_ascii_alphabetic() => {
+                    let len = self.len_while(|c| c.is_ascii_alphanumeric());
+                    let kind = match &self.rest()[..len] {
+                        "true" | "false" => Some(TokenKind::Constant),
+                        "inf" | "nan" => Some(TokenKind::Number),
+                        _ => None,
+                    };
+                    self.emit(kind, len);
+                }
+                _ => self.skip(),
+            }
+        }
+    }
+
+    /// Lex a YAML document, line by line.
+    fn yaml(&mut self) {
+        // Indentation of the line that opened a `|` or `>` block scalar
+        let mut block_scalar: Option<usize> = None;
+
+        while self.peek().is_some() {
+            let line = &self.rest()[..self.line_len()];
+            let indent = line.len() - line.trim_start().len();
+
+            match block_scalar {
+                Some(owner) if line.trim().is_empty() || indent > owner => {
+                    self.plain(indent);
+                    let len = self.line_len();
+                    self.token(TokenKind::String, len);
+                }
+                _ => {
+                    block_scalar = None;
+                    self.plain(indent);
+                    if self.yaml_line() {
+                        block_scalar = Some(indent);
+                    }
+                }
+            }
+
+            if self.peek() == Some('\n') {
+                self.plain(1);
+            }
+        }
+    }
+
+    /// Lex the rest of a YAML line. Returns whether it opens a block scalar.
+    fn yaml_line(&mut self) -> bool {
+        let line = &self.rest()[..self.line_len()];
+        if matches!(line.trim_end(), "---" | "...") {
+            self.token(TokenKind::Meta, line.len());
+            return false;
+        }
+
+        // Sequence entries
+        while self.at("- ") || self.rest()[..self.line_len()].trim_end() == "-" {
+            self.token(TokenKind::Punctuation, 1);
+            self.whitespace_in_line();
+        }
+
+        // Mapping key, up to a `:` followed by a space or the line end
+        let line = &self.rest()[..self.line_len()];
+        if !line.starts_with(['#', '"', '\'', '[', '{']) {
+            let key = line.char_indices().find(|&(i, c)| {
+                c == ':' && line[i + 1..].chars().next().is_none_or(char::is_whitespace)
+            });
+            if let Some((i, _)) = key.filter(|&(i, _)| !line[..i].contains(" #")) {
+                self.token(TokenKind::Property, i);
+                self.token(TokenKind::Punctuation, 1);
+            }
+        }
+
+        self.whitespace_in_line();
+        if self.at("|") || self.at(">") {
+            let len = self.len_while(|c| matches!(c, '|' | '>' | '-' | '+') || c.is_ascii_digit());
+            self.token(TokenKind::Operator, len);
+            self.whitespace_in_line();
+            self.yaml_values();
+            return true;
+        }
+        self.yaml_values();
+        false
+    }
+
+    /// Lex YAML values up to the line end.
+    fn yaml_values(&mut self) {
+        while let Some(c) = self.peek() {
+            match c {
+                '\n' => return,
+                c if c.is_whitespace() => self.whitespace_in_line(),
+                '#' => {
+                    let len = self.line_len();
+                    self.token(TokenKind::Comment, len);
+                }
+                '"' | '\'' => {
+                    let len = self.string_len(c, false);
+                    self.token(TokenKind::String, len);
+                }
+                '&' | '*' | '!' => {
+                    let len =
+                        self.len_while(|c| !c.is_whitespace() && !matches!(c, ',' | ']' | '}'));
+                    let kind = if c == '!' {
+                        TokenKind::Type
+                    } else {
+                        TokenKind::Variable
+                    };
+                    self.token(kind, len.max(1));
+                }
+                '[' | ']' | '{' | '}' | ',' => self.token(TokenKind::Punctuation, 1),
+                _ => {
+                    // A plain scalar, up to a flow delimiter or comment
+                    let line = &self.rest()[..self.line_len()];
+                    let end = line
+                        .char_indices()
+                        .find(|&(i, c)| {
+                            matches!(c, ',' | ']' | '}')
+                                || (c == '#' && line[..i].ends_with(char::is_whitespace))
+                        })
+                        .map_or(line.len(), |(i, _)| i);
+                    let len = line[..end].trim_end().len().max(c.len_utf8());
+                    let kind = yaml_scalar_kind(&line[..len]);
+                    self.token(kind, len);
+                }
+            }
+        }
+    }
+
+    fn whitespace_in_line(&mut self) {
+        let len = self.len_while(|c| c.is_whitespace() && c != '\n');
+        self.plain(len);
+    }
+
+    /// Lex a unified diff, line by line.
+    fn diff(&mut self) {
+        while self.peek().is_some() {
+            let len = self.line_len();
+            let line = &self.rest()[..len];
+            let kind = if ["+++", "---", "@@", "diff ", "index "]
+                .iter()
+                .any(|prefix| line.starts_with(prefix))
+            {
+                Some(TokenKind::Meta)
+            } else if line.starts_with('+') {
+                Some(TokenKind::Inserted)
+            } else if line.starts_with('-') {
+                Some(TokenKind::Deleted)
+            } else {
+                None
+            };
+            self.emit(kind, len);
+
+            if self.peek() == Some('\n') {
+                self.plain(1);
+            }
+        }
+    }
+}
+
+/// Kind of a plain YAML scalar.
+fn yaml_scalar_kind(scalar: &str) -> TokenKind {
+    match scalar {
+        "true" | "false" | "yes" | "no" | "on" | "off" | "null" | "~" => TokenKind::Constant,
+        _ if scalar.parse::<f64>().is_ok() => TokenKind::Number,
+        _ => TokenKind::String,
+    }
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '$'
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+fn is_css_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlights_tsx() {
+        let html = highlight(
+            "import { Button } from './button';\n\n// Render it\nexport const App = () => (\n  <Button variant=\"primary\" count={1 < 2}>Don't panic</Button>\n);\n",
+            Language::Tsx,
+        );
+
+        assert!(html.starts_with(
+            r#"<span class="hl-keyword">import</span> <span class="hl-punctuation">{</span> <span class="hl-type">Button</span> <span class="hl-punctuation">}</span> <span class="hl-keyword">from</span> <span class="hl-string">'./button'</span>"#
+        ));
+        assert!(html.contains(r#"<span class="hl-comment">// Render it</span>"#));
+        assert!(html.contains(r#"<span class="hl-tag">Button</span> <span class="hl-attribute">variant</span><span class="hl-operator">=</span><span class="hl-string">&quot;primary&quot;</span>"#));
+        assert!(html.contains(r#"<span class="hl-number">1</span> <span class="hl-operator">&lt;</span> <span class="hl-number">2</span>"#));
+        assert!(html.contains(r#"&gt;</span>Don't panic<span class="hl-punctuation">&lt;/</span><span class="hl-tag">Button</span>"#));
+    }
+
+    #[test]
+    fn keeps_spans_within_lines() {
+        let html = highlight(
+            "/* a\n   b */\n.card:hover { color: #fff; }\n",
+            Language::Css,
+        );
+
+        assert_eq!(
+            html,
+            concat!(
+                "<span class=\"hl-comment\">/* a</span>\n",
+                "<span class=\"hl-comment\">   b */</span>\n",
+                "<span class=\"hl-type\">.card</span><span class=\"hl-attribute\">:hover</span> ",
+                "<span class=\"hl-punctuation\">{</span> <span class=\"hl-property\">color</span>",
+                "<span class=\"hl-punctuation\">:</span> <span class=\"hl-number\">#fff</span>",
+                "<span class=\"hl-punctuation\">;</span> <span class=\"hl-punctuation\">}</span>\n",
+            )
+        );
+    }
+
+    #[test]
+    fn highlights_other_languages() {
+        let rust = highlight(
+            "#[derive(Debug)]\nfn main() { println!(\"hi\"); }",
+            Language::Rust,
+        );
+        assert!(rust.contains(r#"<span class="hl-meta">#[derive(Debug)]</span>"#));
+        assert!(rust.contains(r#"<span class="hl-function">println!</span>"#));
+
+        let vue = highlight(
+            "<template>\n  <p :title=\"t\">{{ count + 1 }}</p>\n</template>\n<script setup>\nconst count = 1\n</script>",
+            Language::Vue,
+        );
+        assert!(vue.contains(r#"<span class="hl-attribute">:title</span>"#));
+        assert!(vue
+            .contains(r#"<span class="hl-number">1</span> <span class="hl-punctuation">}}&lt;/"#));
+        assert!(vue.contains(r#"<span class="hl-keyword">const</span>"#));
+
+        let bash = highlight("$ npm install --save-dev veneer # dev only", Language::Bash);
+        assert_eq!(
+            bash,
+            r#"<span class="hl-meta">$</span> <span class="hl-function">npm</span> install <span class="hl-attribute">--save-dev</span> veneer <span class="hl-comment"># dev only</span>"#
+        );
+
+        let json = highlight(r#"{"name": "veneer", "private": true}"#, Language::Json);
+        assert!(json.contains(r#"<span class="hl-property">&quot;name&quot;</span>"#));
+        assert!(json.contains(r#"<span class="hl-string">&quot;veneer&quot;</span>"#));
+        assert!(json.contains(r#"<span class="hl-constant">true</span>"#));
+
+        let toml = highlight("[package]\nname = \"veneer\"\nversion = 1", Language::Toml);
+        assert!(toml.starts_with(r#"<span class="hl-meta">[package]</span>"#));
+        assert!(toml.contains(r#"<span class="hl-property">name</span>"#));
+
+        let yaml = highlight(
+            "title: Button\norder: 2\nbody: |\n  text: here\n",
+            Language::Yaml,
+        );
+        assert!(yaml.contains(r#"<span class="hl-property">title</span><span class="hl-punctuation">:</span> <span class="hl-string">Button</span>"#));
+        assert!(yaml.contains(r#"<span class="hl-number">2</span>"#));
+        assert!(yaml.contains(r#"  <span class="hl-string">text: here</span>"#));
+
+        let diff = highlight("@@ -1 +1 @@\n-old\n+new\n same", Language::Diff);
+        assert!(diff.contains(r#"<span class="hl-deleted">-old</span>"#));
+        assert!(diff.contains(r#"<span class="hl-inserted">+new</span>"#));
+    }
+
+    #[test]
+    fn renders_code_blocks() {
+        let mut block = CodeBlock::new(
+            Language::Unknown,
+            Default::default(),
+            "a < b\n\nc".to_string(),
+            1,
+        );
+        block.info = "text title=x".to_string();
+        assert_eq!(
+            render_code_block(&block),
+            "<pre><code class=\"language-text\"><span class=\"line\">a &lt; b</span>\n<span class=\"line\"></span>\n<span class=\"line\">c</span>\n</code></pre>\n"
+        );
+
+        let block = CodeBlock::from_fence(
+            "ts {1} title=\"<Api>\" showLineNumbers".to_string(),
+            "let a = 1;\nlet b = 2; // [!code ++]\n",
+            1,
+        );
+        assert_eq!(
+            render_code_block(&block),
+            r#"<figure class="code-block">
+<figcaption class="code-title">&lt;Api&gt;</figcaption>
+<pre class="line-numbers"><code class="language-ts"><span class="line highlighted"><span class="hl-keyword">let</span> a <span class="hl-operator">=</span> <span class="hl-number">1</span><span class="hl-punctuation">;</span></span>
+<span class="line diff-add"><span class="hl-keyword">let</span> b <span class="hl-operator">=</span> <span class="hl-number">2</span><span class="hl-punctuation">;</span></span>
+</code></pre>
+</figure>
+"#
+        );
+    }
+}
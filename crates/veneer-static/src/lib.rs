@@ -4,6 +4,9 @@
 
 pub mod assets;
 pub mod builder;
+pub mod highlight;
+pub mod render;
 pub mod templates;
 
-pub use builder::{BuildConfig, BuildError, BuildResult, StaticBuilder};
+pub use builder::{is_partial, BuildConfig, BuildError, BuildResult, StaticBuilder, TocConfig};
+pub use render::render_document;
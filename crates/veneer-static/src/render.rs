@@ -0,0 +1,167 @@
+//! Page rendering from the MDX document tree.
+//!
+//! Renders Markdown with `veneer_mdx::render_html`, adding the site's themed
+//! markup for nodes that have one, such as admonitions, highlighted code
+//! and code group tabs, and anchor links to headings.
+
+use veneer_mdx::{escape_html, render_html, walk_mut, Admonition, CodeGroup, Inline, Node};
+
+use crate::highlight::render_code_block;
+
+/// Render a document tree to page content HTML.
+pub fn render_document(mut nodes: Vec<Node>) -> String {
+    walk_mut(&mut nodes, &mut |node| match node {
+        Node::Heading { id, children, .. } => {
+            children.push(Inline::Html(format!(
+                " <a class=\"heading-anchor\" href=\"#{}\" aria-label=\"Link to this section\">#</a>",
+                escape_html(id)
+            )));
+        }
+        Node::Admonition(admonition) => {
+            *node = Node::Html {
+                html: render_admonition(admonition),
+                span: admonition.span,
+            };
+        }
+        Node::Code(block) => {
+            *node = Node::Html {
+                html: render_code_block(block),
+                span: block.span,
+            };
+        }
+        Node::CodeGroup(group) => {
+            *node = Node::Html {
+                html: render_code_group(group),
+                span: group.span,
+            };
+        }
+        _ => {}
+    });
+
+    render_html(&nodes)
+}
+
+/// Render an admonition as a callout. The icon slot is empty; themes fill
+/// it per kind from CSS.
+fn render_admonition(admonition: &Admonition) -> String {
+    format!(
+        r#"<aside class="admonition admonition-{kind}" role="note">
+<p class="admonition-title"><span class="admonition-icon" aria-hidden="true"></span>{title}</p>
+<div class="admonition-content">
+{content}</div>
+</aside>
+"#,
+        kind = admonition.kind.as_str(),
+        title = escape_html(admonition.title()),
+        content = render_document(admonition.children.clone()),
+    )
+}
+
+/// Render a code group as tabs, following the WAI-ARIA tabs pattern. The
+/// first tab is selected; the site script switches tabs and restores the
+/// one chosen last for groups of the same name.
+fn render_code_group(group: &CodeGroup) -> String {
+    let id = format!("code-group-{}", group.span.line);
+    let mut tabs = String::new();
+    let mut panels = String::new();
+
+    for (i, child) in group.children.iter().enumerate() {
+        let label = group.labels.get(i).map_or("Code", String::as_str);
+        let selected = i == 0;
+        tabs.push_str(&format!(
+            "<button type=\"button\" role=\"tab\" id=\"{id}-tab-{i}\" aria-controls=\"{id}-panel-{i}\" aria-selected=\"{selected}\" tabindex=\"{}\">{}</button>\n",
+            if selected { "0" } else { "-1" },
+            escape_html(label),
+        ));
+
+        // The tab shows the title, so blocks don't repeat it
+        let mut child = child.clone();
+        if let Node::Code(block) = &mut child {
+            block.title = None;
+            block.filename = None;
+        }
+        panels.push_str(&format!(
+            "<div class=\"code-group-panel\" role=\"tabpanel\" id=\"{id}-panel-{i}\" aria-labelledby=\"{id}-tab-{i}\" tabindex=\"0\"{}>\n{}</div>\n",
+            if selected { "" } else { " hidden" },
+            render_document(vec![child]),
+        ));
+    }
+
+    let name = group.name.as_deref().unwrap_or("Code");
+    let data = match &group.name {
+        Some(name) => format!(" data-group=\"{}\"", escape_html(name)),
+        None => String::new(),
+    };
+    format!(
+        "<div class=\"code-group\"{data}>\n<div class=\"code-group-tabs\" role=\"tablist\" aria-label=\"{}\">\n{tabs}</div>\n{panels}</div>\n",
+        escape_html(name),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use veneer_mdx::parse_mdx;
+
+    #[test]
+    fn renders_admonitions() {
+        let doc = parse_mdx(
+            ":::warning Size <xs> removed\nUse `sm`.\n:::\n\n::::tip\n:::danger\nNested\n:::\n::::\n",
+        )
+        .unwrap();
+
+        let html = render_document(doc.nodes);
+
+        assert!(html.starts_with(
+            r#"<aside class="admonition admonition-warning" role="note">
+<p class="admonition-title"><span class="admonition-icon" aria-hidden="true"></span>Size &lt;xs&gt; removed</p>
+<div class="admonition-content">
+<p>Use <code>sm</code>.</p>
+</div>
+</aside>
+"#
+        ));
+        assert!(
+            html.contains("<span class=\"admonition-icon\" aria-hidden=\"true\"></span>Tip</p>")
+        );
+        assert!(html.contains(r#"<aside class="admonition admonition-danger" role="note">"#));
+        assert!(!html.contains("directive"));
+    }
+
+    #[test]
+    fn links_headings() {
+        let doc = parse_mdx("## Props\n\n## Props\n").unwrap();
+
+        let html = render_document(doc.nodes);
+
+        assert_eq!(
+            html,
+            concat!(
+                "<h2 id=\"props\">Props <a class=\"heading-anchor\" href=\"#props\" aria-label=\"Link to this section\">#</a></h2>\n",
+                "<h2 id=\"props-1\">Props <a class=\"heading-anchor\" href=\"#props-1\" aria-label=\"Link to this section\">#</a></h2>\n",
+            )
+        );
+    }
+
+    #[test]
+    fn renders_code_groups() {
+        let doc = parse_mdx(
+            "```sh group=\"install\" title=\"npm\"\nnpm i\n```\n```sh group=\"install\" title=\"pnpm\"\npnpm add\n```\n",
+        )
+        .unwrap();
+
+        let html = render_document(doc.nodes);
+
+        assert!(html.starts_with(
+            r#"<div class="code-group" data-group="install">
+<div class="code-group-tabs" role="tablist" aria-label="install">
+<button type="button" role="tab" id="code-group-1-tab-0" aria-controls="code-group-1-panel-0" aria-selected="true" tabindex="0">npm</button>
+<button type="button" role="tab" id="code-group-1-tab-1" aria-controls="code-group-1-panel-1" aria-selected="false" tabindex="-1">pnpm</button>
+</div>
+<div class="code-group-panel" role="tabpanel" id="code-group-1-panel-0" aria-labelledby="code-group-1-tab-0" tabindex="0">
+<pre><code class="language-sh">"#
+        ));
+        assert!(html.contains(r#"aria-labelledby="code-group-1-tab-1" tabindex="0" hidden>"#));
+        assert!(!html.contains("code-title"));
+    }
+}
@@ -1,6 +1,10 @@
 //! Template engine for rendering documentation pages.
 
-use minijinja::{context, Environment};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use minijinja::{context, path_loader, Environment};
+use veneer_mdx::FrontmatterValue;
 
 /// A navigation item.
 #[derive(Debug, Clone, serde::Serialize)]
@@ -24,6 +28,8 @@ pub struct TocEntry {
     pub id: String,
     /// Heading level (1-6)
     pub level: u8,
+    /// Nested entries
+    pub children: Vec<TocEntry>,
 }
 
 /// Context for rendering a page template.
@@ -45,31 +51,90 @@ pub struct Context {
     pub web_components: Vec<String>,
     /// Paths to CSS stylesheets to include
     pub styles: Vec<String>,
+    /// Extra frontmatter fields of the page, such as `meta.status`
+    pub meta: BTreeMap<String, FrontmatterValue>,
+    /// Whether the page is a draft
+    pub draft: bool,
+    /// Publish date of a page that is not live yet
+    pub scheduled: Option<String>,
 }
 
 /// Template engine using minijinja.
 pub struct TemplateEngine {
     env: Environment<'static>,
+    /// Template of each layout, by the name pages select it with
+    layouts: BTreeMap<String, String>,
 }
 
 impl TemplateEngine {
     /// Create a new template engine with default templates.
     pub fn new() -> Self {
+        Self::create(None)
+    }
+
+    /// Create a template engine whose templates in `dir` (`base.html`,
+    /// `nav.html`, partials) replace or add to the built-in ones.
+    pub fn with_templates_dir(dir: &Path) -> Self {
+        Self::create(Some(dir))
+    }
+
+    fn create(templates_dir: Option<&Path>) -> Self {
         let mut env = Environment::new();
 
-        // Add base template
-        env.add_template_owned("base.html".to_string(), BASE_TEMPLATE.to_string())
-            .expect("Failed to add base template");
+        // Templates load on first use, from the theme first, so includes and
+        // extends resolve across theme and built-in templates alike
+        let theme = templates_dir.map(path_loader);
+        env.set_loader(move |name| {
+            if let Some(theme) = &theme {
+                if let Some(source) = theme(name)? {
+                    return Ok(Some(source));
+                }
+            }
+            Ok(BUILTIN_TEMPLATES
+                .iter()
+                .find(|(builtin, _)| *builtin == name)
+                .map(|(_, source)| source.to_string()))
+        });
 
-        // Add doc template
-        env.add_template_owned("doc.html".to_string(), DOC_TEMPLATE.to_string())
-            .expect("Failed to add doc template");
+        let layouts = BTreeMap::from([
+            ("doc".to_string(), "doc.html".to_string()),
+            ("full-width".to_string(), "full-width.html".to_string()),
+        ]);
 
-        // Add nav template
-        env.add_template_owned("nav.html".to_string(), NAV_TEMPLATE.to_string())
-            .expect("Failed to add nav template");
+        Self { env, layouts }
+    }
 
-        Self { env }
+    /// Register a layout that pages select with `layout: name`. The template
+    /// can extend `base.html`; a layout named like a built-in replaces it.
+    pub fn add_layout(&mut self, name: &str, source: String) -> Result<(), minijinja::Error> {
+        let template = format!("layouts/{name}.html");
+        self.env.add_template_owned(template.clone(), source)?;
+        self.layouts.insert(name.to_string(), template);
+        Ok(())
+    }
+
+    /// Template of the layout `name`, if there is one.
+    pub fn layout_template(&self, name: &str) -> Option<&str> {
+        self.layouts.get(name).map(String::as_str)
+    }
+
+    /// Render the draft or scheduled banner of a page, as the layouts show
+    /// it. Empty for a published page.
+    pub fn render_banner(
+        &self,
+        draft: bool,
+        scheduled: Option<&str>,
+    ) -> Result<String, minijinja::Error> {
+        let html = self
+            .env
+            .get_template("banner.html")?
+            .render(context! { draft, scheduled })?;
+        Ok(html.trim().to_string())
+    }
+
+    /// Names of the available layouts.
+    pub fn layouts(&self) -> impl Iterator<Item = &str> {
+        self.layouts.keys().map(String::as_str)
     }
 
     /// Render a page using the specified template.
@@ -89,6 +154,9 @@ impl TemplateEngine {
             base_url => &context.base_url,
             web_components => &context.web_components,
             styles => &context.styles,
+            meta => &context.meta,
+            draft => context.draft,
+            scheduled => &context.scheduled,
         })
     }
 }
@@ -99,6 +167,15 @@ impl Default for TemplateEngine {
     }
 }
 
+/// Built-in templates by name, as `veneer eject-theme` writes them out.
+pub const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    ("banner.html", BANNER_TEMPLATE),
+    ("base.html", BASE_TEMPLATE),
+    ("doc.html", DOC_TEMPLATE),
+    ("full-width.html", FULL_WIDTH_TEMPLATE),
+    ("nav.html", NAV_TEMPLATE),
+];
+
 const BASE_TEMPLATE: &str = r##"<!DOCTYPE html>
 <html lang="en">
 <head>
@@ -113,7 +190,7 @@ const BASE_TEMPLATE: &str = r##"<!DOCTYPE html>
     <nav class="sidebar">
       {% include "nav.html" %}
     </nav>
-    <main class="main">
+    <main class="main{% block main_class %}{% endblock %}">
       {% block content %}{% endblock %}
     </main>
   </div>
@@ -128,6 +205,7 @@ const DOC_TEMPLATE: &str = r##"{% extends "base.html" %}
 
 {% block content %}
 <article class="doc">
+  {% include "banner.html" %}
   <div class="content">
     {{ content | safe }}
   </div>
@@ -137,9 +215,12 @@ const DOC_TEMPLATE: &str = r##"{% extends "base.html" %}
 <aside class="toc">
   <h2>On this page</h2>
   <ul>
-  {% for entry in toc %}
+  {% for entry in toc recursive %}
     <li class="toc-level-{{ entry.level }}">
       <a href="#{{ entry.id }}">{{ entry.title }}</a>
+      {% if entry.children %}
+      <ul>{{ loop(entry.children) }}</ul>
+      {% endif %}
     </li>
   {% endfor %}
   </ul>
@@ -147,6 +228,25 @@ const DOC_TEMPLATE: &str = r##"{% extends "base.html" %}
 {% endif %}
 {% endblock %}"##;
 
+const FULL_WIDTH_TEMPLATE: &str = r##"{% extends "base.html" %}
+
+{% block main_class %} main-full{% endblock %}
+
+{% block content %}
+<article class="doc">
+  {% include "banner.html" %}
+  <div class="content">
+    {{ content | safe }}
+  </div>
+</article>
+{% endblock %}"##;
+
+const BANNER_TEMPLATE: &str = r##"{% if draft %}
+<div class="draft-banner" role="note">Draft</div>
+{% elif scheduled %}
+<div class="draft-banner" role="note">Scheduled for {{ scheduled }}</div>
+{% endif %}"##;
+
 const NAV_TEMPLATE: &str = r##"<div class="nav-header">
   <a href="{{ base_url }}" class="nav-logo">{{ site_title }}</a>
 </div>
@@ -170,20 +270,34 @@ const NAV_TEMPLATE: &str = r##"<div class="nav-header">
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    /// A page titled "Button" on a site titled "Docs", with nothing else.
+    fn context() -> Context {
+        Context {
+            title: "Button".to_string(),
+            site_title: "Docs".to_string(),
+            content: String::new(),
+            nav: vec![],
+            toc: vec![],
+            base_url: "/".to_string(),
+            web_components: vec![],
+            styles: vec![],
+            meta: BTreeMap::new(),
+            draft: false,
+            scheduled: None,
+        }
+    }
 
     #[test]
     fn renders_basic_page() {
         let engine = TemplateEngine::new();
 
         let context = Context {
-            title: "Button".to_string(),
             site_title: "My Docs".to_string(),
             content: "<p>Hello world</p>".to_string(),
-            nav: vec![],
-            toc: vec![],
-            base_url: "/".to_string(),
-            web_components: vec![],
-            styles: vec![],
+            ..context()
         };
 
         let html = engine.render_page("doc.html", &context).unwrap();
@@ -198,8 +312,6 @@ mod tests {
 
         let context = Context {
             title: "Home".to_string(),
-            site_title: "Docs".to_string(),
-            content: "".to_string(),
             nav: vec![
                 NavItem {
                     title: "Home".to_string(),
@@ -219,10 +331,7 @@ mod tests {
                     active: false,
                 },
             ],
-            toc: vec![],
-            base_url: "/".to_string(),
-            web_components: vec![],
-            styles: vec![],
+            ..context()
         };
 
         let html = engine.render_page("doc.html", &context).unwrap();
@@ -237,18 +346,165 @@ mod tests {
         let engine = TemplateEngine::new();
 
         let context = Context {
-            title: "Test".to_string(),
-            site_title: "Docs".to_string(),
-            content: "".to_string(),
-            nav: vec![],
-            toc: vec![],
-            base_url: "/".to_string(),
             web_components: vec!["class MyButton extends HTMLElement {}".to_string()],
-            styles: vec![],
+            ..context()
         };
 
         let html = engine.render_page("doc.html", &context).unwrap();
 
         assert!(html.contains("class MyButton extends HTMLElement"));
     }
+
+    #[test]
+    fn renders_nested_toc() {
+        let engine = TemplateEngine::new();
+
+        let entry = |title: &str, level, children| TocEntry {
+            title: title.to_string(),
+            id: title.to_lowercase(),
+            level,
+            children,
+        };
+        let context = Context {
+            toc: vec![
+                entry("Usage", 2, vec![entry("Sizes", 3, vec![])]),
+                entry("Props", 2, vec![]),
+            ],
+            ..context()
+        };
+
+        let html = engine.render_page("doc.html", &context).unwrap();
+        let compact: String = html.split_whitespace().collect();
+
+        assert!(compact.contains(
+            r##"<ahref="#usage">Usage</a><ul><liclass="toc-level-3"><ahref="#sizes">Sizes</a></li></ul></li>"##
+        ));
+        assert!(compact.contains(r##"<liclass="toc-level-2"><ahref="#props">Props</a></li>"##));
+    }
+
+    #[test]
+    fn exposes_frontmatter_fields() {
+        let mut engine = TemplateEngine::new();
+        engine
+            .env
+            .add_template(
+                "meta.html",
+                "{{ meta.status }} {{ meta.tags | join(\", \") }}",
+            )
+            .unwrap();
+
+        let context = Context {
+            meta: BTreeMap::from([
+                (
+                    "status".to_string(),
+                    FrontmatterValue::String("beta".to_string()),
+                ),
+                (
+                    "tags".to_string(),
+                    FrontmatterValue::List(vec![
+                        FrontmatterValue::String("input".to_string()),
+                        FrontmatterValue::String("form".to_string()),
+                    ]),
+                ),
+            ]),
+            ..context()
+        };
+
+        let html = engine.render_page("meta.html", &context).unwrap();
+
+        assert_eq!(html, "beta input, form");
+    }
+
+    #[test]
+    fn shows_draft_banner() {
+        let engine = TemplateEngine::new();
+
+        let mut context = context();
+
+        let html = engine.render_page("doc.html", &context).unwrap();
+        assert!(!html.contains("draft-banner"));
+
+        context.draft = true;
+        let html = engine.render_page("doc.html", &context).unwrap();
+        assert!(html.contains(r#"<div class="draft-banner" role="note">Draft</div>"#));
+
+        assert_eq!(
+            engine.render_banner(false, Some("2999-01-01")).unwrap(),
+            r#"<div class="draft-banner" role="note">Scheduled for 2999-01-01</div>"#
+        );
+        assert_eq!(engine.render_banner(false, None).unwrap(), "");
+    }
+
+    #[test]
+    fn renders_layouts() {
+        let mut engine = TemplateEngine::new();
+        engine
+            .add_layout(
+                "splash",
+                r#"{% extends "base.html" %}{% block content %}<section class="hero">{{ content | safe }}</section>{% endblock %}"#
+                    .to_string(),
+            )
+            .unwrap();
+
+        let context = Context {
+            content: "<p>Welcome</p>".to_string(),
+            toc: vec![TocEntry {
+                title: "Usage".to_string(),
+                id: "usage".to_string(),
+                level: 2,
+                children: vec![],
+            }],
+            ..context()
+        };
+
+        assert_eq!(
+            engine.layouts().collect::<Vec<_>>(),
+            vec!["doc", "full-width", "splash"]
+        );
+        assert_eq!(engine.layout_template("nav"), None);
+
+        let splash = engine.layout_template("splash").unwrap();
+        let html = engine.render_page(splash, &context).unwrap();
+        assert!(html.contains(r#"<section class="hero"><p>Welcome</p></section>"#));
+        assert!(html.contains(r#"<nav class="sidebar">"#));
+
+        let full_width = engine.layout_template("full-width").unwrap();
+        let html = engine.render_page(full_width, &context).unwrap();
+        assert!(html.contains(r#"<main class="main main-full">"#));
+        assert!(!html.contains(r#"<aside class="toc">"#));
+    }
+
+    #[test]
+    fn overrides_builtin_templates() {
+        let temp = tempdir().unwrap();
+        fs::create_dir_all(temp.path().join("partials")).unwrap();
+        fs::write(
+            temp.path().join("nav.html"),
+            r#"<a class="brand">{{ site_title }}</a>{% include "partials/links.html" %}"#,
+        )
+        .unwrap();
+        fs::write(
+            temp.path().join("partials/links.html"),
+            r#"<a href="https://example.com">GitHub</a>"#,
+        )
+        .unwrap();
+
+        let context = Context {
+            content: "<p>Hello</p>".to_string(),
+            ..context()
+        };
+
+        let engine = TemplateEngine::with_templates_dir(temp.path());
+        let html = engine.render_page("doc.html", &context).unwrap();
+        assert!(
+            html.contains(r#"<a class="brand">Docs</a><a href="https://example.com">GitHub</a>"#)
+        );
+        assert!(!html.contains("nav-list"));
+        assert!(html.contains("<p>Hello</p>"));
+
+        let html = TemplateEngine::new()
+            .render_page("doc.html", &context)
+            .unwrap();
+        assert!(html.contains("nav-list"));
+    }
 }
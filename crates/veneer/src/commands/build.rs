@@ -1,27 +1,39 @@
 //! Static site build command.
 
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
 use anyhow::Result;
 use serde::Deserialize;
-use veneer_static::{BuildConfig, StaticBuilder};
+use veneer_mdx::FrontmatterSchema;
+use veneer_static::{BuildConfig, StaticBuilder, TocConfig};
 
 /// Configuration file structure (docs.toml).
 #[derive(Debug, Deserialize, Default)]
-struct ConfigFile {
+pub(crate) struct ConfigFile {
     #[serde(default)]
-    docs: DocsConfig,
+    pub(crate) docs: DocsConfig,
     #[serde(default)]
-    components: ComponentsConfig,
+    pub(crate) components: ComponentsConfig,
     #[serde(default)]
     build: BuildSettings,
+    #[serde(default)]
+    toc: TocSettings,
+    /// Rules for the frontmatter of every page, by field name
+    #[serde(default)]
+    frontmatter: FrontmatterSchema,
+    /// Template files of additional layouts, by name
+    #[serde(default)]
+    layouts: BTreeMap<String, String>,
+    #[serde(default)]
+    pub(crate) theme: ThemeConfig,
 }
 
-#[derive(Debug, Deserialize, Default)]
-struct DocsConfig {
+#[derive(Debug, Deserialize)]
+pub(crate) struct DocsConfig {
     #[serde(default = "default_docs_dir")]
-    dir: String,
+    pub(crate) dir: String,
     #[serde(default = "default_output")]
     output: String,
     #[serde(default = "default_title")]
@@ -32,9 +44,24 @@ struct DocsConfig {
     styles: Option<Vec<String>>,
 }
 
+impl Default for DocsConfig {
+    fn default() -> Self {
+        Self {
+            dir: default_docs_dir(),
+            output: default_output(),
+            title: default_title(),
+            base_url: default_base_url(),
+            styles: None,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Default)]
-struct ComponentsConfig {
-    dir: Option<String>,
+pub(crate) struct ComponentsConfig {
+    pub(crate) dir: Option<String>,
+    /// Framework the components are written in ("react", "solid", "vue" or
+    /// "svelte")
+    pub(crate) framework: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -43,6 +70,22 @@ struct BuildSettings {
     minify: bool,
 }
 
+/// Table of contents defaults; pages override them with `toc:` frontmatter.
+#[derive(Debug, Deserialize, Default)]
+struct TocSettings {
+    enabled: Option<bool>,
+    /// Shallowest heading level to list
+    min: Option<u8>,
+    /// Deepest heading level to list
+    max: Option<u8>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub(crate) struct ThemeConfig {
+    /// Directory of templates that replace the built-in ones
+    pub(crate) templates_dir: Option<String>,
+}
+
 fn default_docs_dir() -> String {
     "docs".to_string()
 }
@@ -61,7 +104,7 @@ fn default_minify() -> bool {
 
 /// Load configuration from docs.toml if it exists.
 /// Returns an error if the config file exists but is malformed.
-fn load_config() -> Result<ConfigFile> {
+pub(crate) fn load_config() -> Result<ConfigFile> {
     let config_path = PathBuf::from("docs.toml");
     if config_path.exists() {
         let content = fs::read_to_string(&config_path)
@@ -75,7 +118,7 @@ fn load_config() -> Result<ConfigFile> {
 }
 
 /// Run the build command.
-pub async fn run(output: Option<PathBuf>, minify: Option<bool>) -> Result<()> {
+pub async fn run(output: Option<PathBuf>, minify: Option<bool>, drafts: bool) -> Result<()> {
     tracing::info!("Building static site...");
 
     let file_config = load_config()?;
@@ -84,10 +127,30 @@ pub async fn run(output: Option<PathBuf>, minify: Option<bool>) -> Result<()> {
         docs_dir: PathBuf::from(&file_config.docs.dir),
         output_dir: output.unwrap_or_else(|| PathBuf::from(&file_config.docs.output)),
         components_dir: file_config.components.dir.map(PathBuf::from),
+        framework: file_config
+            .components
+            .framework
+            .unwrap_or_else(|| "react".to_string()),
         minify: minify.unwrap_or(file_config.build.minify),
         base_url: file_config.docs.base_url,
         title: file_config.docs.title,
         styles: file_config.docs.styles.unwrap_or_default(),
+        toc: {
+            let default = TocConfig::default();
+            TocConfig {
+                enabled: file_config.toc.enabled.unwrap_or(default.enabled),
+                min_depth: file_config.toc.min.unwrap_or(default.min_depth),
+                max_depth: file_config.toc.max.unwrap_or(default.max_depth),
+            }
+        },
+        frontmatter_schema: file_config.frontmatter,
+        drafts,
+        layouts: file_config
+            .layouts
+            .into_iter()
+            .map(|(name, path)| (name, PathBuf::from(path)))
+            .collect(),
+        templates_dir: file_config.theme.templates_dir.map(PathBuf::from),
     };
 
     let result = StaticBuilder::new(config).build().await?;
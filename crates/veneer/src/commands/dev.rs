@@ -1,16 +1,34 @@
 //! Development server command.
 
+use std::path::PathBuf;
+
 use anyhow::Result;
 use veneer_server::{DevServer, DevServerConfig};
 
+use super::build::load_config;
+
 /// Run the dev server.
 pub async fn run(port: u16, open: bool) -> Result<()> {
     tracing::info!("Starting development server on port {}", port);
 
+    let file_config = load_config()?;
+    let default = DevServerConfig::default();
+
     let config = DevServerConfig {
+        docs_dir: PathBuf::from(&file_config.docs.dir),
+        components_dir: file_config
+            .components
+            .dir
+            .map(PathBuf::from)
+            .unwrap_or(default.components_dir),
+        framework: file_config
+            .components
+            .framework
+            .unwrap_or(default.framework),
         port,
         open,
-        ..Default::default()
+        templates_dir: file_config.theme.templates_dir.map(PathBuf::from),
+        ..default
     };
 
     DevServer::new(config).start().await?;
@@ -0,0 +1,46 @@
+//! Copy the built-in templates out for editing.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use veneer_static::templates::BUILTIN_TEMPLATES;
+
+use super::build::load_config;
+
+/// Run the eject-theme command.
+pub async fn run(dir: Option<PathBuf>, force: bool) -> Result<()> {
+    let configured = load_config()?.theme.templates_dir.map(PathBuf::from);
+    let templates_dir = dir
+        .or_else(|| configured.clone())
+        .unwrap_or_else(|| PathBuf::from("theme"));
+
+    fs::create_dir_all(&templates_dir).with_context(|| {
+        format!(
+            "Failed to create templates directory {}",
+            templates_dir.display()
+        )
+    })?;
+
+    for (name, source) in BUILTIN_TEMPLATES {
+        let path = templates_dir.join(name);
+        if path.exists() && !force {
+            tracing::warn!(
+                "{} already exists. Use --force to overwrite.",
+                path.display()
+            );
+            continue;
+        }
+        fs::write(&path, source).with_context(|| format!("Failed to write {}", path.display()))?;
+        tracing::info!("Created {}", path.display());
+    }
+
+    if configured.as_ref() != Some(&templates_dir) {
+        tracing::info!(
+            "Add templates_dir = \"{}\" under [theme] in docs.toml to use them.",
+            templates_dir.display()
+        );
+    }
+
+    Ok(())
+}
@@ -84,6 +84,25 @@ dir = "src/components"
 [build]
 # Enable minification
 minify = true
+
+# [toc]
+# Heading levels listed in the table of contents
+# min = 2
+# max = 3
+
+# [frontmatter.status]
+# Checks a frontmatter field of every page
+# type = "string"
+# required = true
+# enum = ["beta", "stable", "deprecated"]
+
+# [layouts]
+# Templates pages select with `layout: splash`
+# splash = "theme/splash.html"
+
+# [theme]
+# Templates here replace the built-in ones; `veneer eject-theme` copies them out
+# templates_dir = "theme"
 "#;
 
 const DEFAULT_INDEX: &str = r#"---
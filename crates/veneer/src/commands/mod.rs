@@ -2,5 +2,6 @@
 
 pub mod build;
 pub mod dev;
+pub mod eject_theme;
 pub mod init;
 pub mod serve;
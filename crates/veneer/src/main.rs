@@ -54,6 +54,21 @@ enum Commands {
         /// Skip minification
         #[arg(long)]
         no_minify: bool,
+
+        /// Include drafts and pages scheduled for later
+        #[arg(long)]
+        drafts: bool,
+    },
+
+    /// Copy the built-in templates out for editing
+    EjectTheme {
+        /// Directory to write to (defaults to theme.templates_dir or "theme")
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+
+        /// Overwrite templates that already exist
+        #[arg(long)]
+        force: bool,
     },
 
     /// Preview built documentation
@@ -89,9 +104,16 @@ async fn main() -> Result<()> {
         Commands::Dev { port, no_open } => {
             commands::dev::run(port, !no_open).await?;
         }
-        Commands::Build { output, no_minify } => {
+        Commands::Build {
+            output,
+            no_minify,
+            drafts,
+        } => {
             let minify = if no_minify { Some(false) } else { None };
-            commands::build::run(output, minify).await?;
+            commands::build::run(output, minify, drafts).await?;
+        }
+        Commands::EjectTheme { dir, force } => {
+            commands::eject_theme::run(dir, force).await?;
         }
         Commands::Serve { port, dir } => {
             commands::serve::run(port, dir).await?;